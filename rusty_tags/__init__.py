@@ -1,5 +1,5 @@
 from .core import HtmlString, HtmlElement
-from .utils import Page, show, template, create_template, page_template, AttrDict, when, unless
+from .utils import Page, show, template, create_template, page_template, AttrDict, when, unless, script_src, stylesheet, responsive_img, picture, progress_bar, meter_bar, testid
 
 """
 RustyTags - High-performance HTML generation library
@@ -15,6 +15,7 @@ from .core import (  # noqa: E402
     # Fragment and utilities
     Fragment,
     Safe,
+    Portal,
     
     # HTML tags
     A, Aside, B, Body, Br, Button, Code, Div, Em, Form,
@@ -39,11 +40,54 @@ from .core import (  # noqa: E402
     Data, Datalist, Dd, Del, Dfn, Dialog, Dl, Dt, Embed, Fieldset,
     Hgroup, Ins, Kbd, Legend, Map, Mark, Menu, Meter, Noscript,
     Object, Optgroup, OptionEl, Picture, Pre, Progress, Q, Rp, Rt,
-    Ruby, S, Samp, Small, Source, Style, Sub, Sup, Template, Time,
+    Ruby, S, Samp, Slot, Small, Source, Style, Sub, Sup, Template, Time,
     Track, U, Var, Video, Wbr,
     
     # Custom tag function
     CustomTag,
+
+    # Standalone attribute rendering
+    render_attrs, with_attrs, combine,
+
+    # Batch rendering
+    render_each, render_fragment_list, concat, sorted_children,
+    render_to_file, render_cache_key, render_chunks_for_sse, table_from_records,
+
+    # Rendering configuration
+    configure, get_config, reset_config,
+    render_profile, reset_profile,
+
+    # Introspection
+    debug_tree, pool_stats, reset_pool_stats, map_attr, normalize_tag, count_nodes,
+    is_known_tag, register_tag,
+    is_void_tag, is_raw_text_tag, is_escapable_raw_text_tag, register_void_tag, register_raw_text_tag,
+    is_boolean_attr, register_boolean_attrs, unregister_boolean_attrs,
+
+    # Formatting
+    render_pretty, render_variants, strip_comments, strip_tags,
+    HtmlToken, tokenize, diff, css, render_svg, json_ld, render_email, conditional_comment,
+    cache_bust, resource_hints,
+
+    # Template inheritance
+    block, extends,
+
+    # Request context
+    RequestContext, request_context, get_request_context, csrf_input, csp_nonce,
+
+    # Custom escaping
+    EscapeScope, escape_with,
+
+    # Inline event handlers
+    UnsafeAttr, unsafe_attr,
+
+    # Id scoping
+    IdScope, id_scope, scoped_id, check_id_references,
+
+    # Precompiled static markup
+    FrozenHtml, freeze,
+
+    # Custom child renderers
+    register_child_renderer, unregister_child_renderer,
 )
 
 from .datastar import DS, signals, Signals, reactive_class, attribute_generator, SSE, ElementPatchMode, EventType
@@ -51,6 +95,9 @@ from .datastar import DS, signals, Signals, reactive_class, attribute_generator,
 # Create alias for proper HTML tag name  
 Option = OptionEl
 
+# The <template> element, named apart from the template()/page_template() layout helpers
+HtmlTemplate = Template
+
 # Import Datastar utilities
 
 __author__ = "Nikola Dendic"
@@ -61,7 +108,7 @@ __all__ = [
     "HtmlString", "HtmlElement", "TagBuilder",
 
     # Fragment and utilities
-    "Fragment", "Safe",
+    "Fragment", "Safe", "Portal",
     
     # HTML tags - organized alphabetically
     "A", "Aside", "B", "Body", "Br", "Button", "Code", "Div", "Em", "Form",
@@ -86,14 +133,58 @@ __all__ = [
     "Data", "Datalist", "Dd", "Del", "Dfn", "Dialog", "Dl", "Dt", "Embed", "Fieldset",
     "Hgroup", "Ins", "Kbd", "Legend", "Map", "Mark", "Menu", "Meter", "Noscript",
     "Object", "Optgroup", "OptionEl", "Option", "Picture", "Pre", "Progress", "Q", "Rp", "Rt",
-    "Ruby", "S", "Samp", "Small", "Source", "Style", "Sub", "Sup", "Template", "Time",
+    "Ruby", "S", "Samp", "Slot", "Small", "Source", "Style", "Sub", "Sup", "Template", "HtmlTemplate", "Time",
     "Track", "U", "Var", "Video", "Wbr",
     
     # Custom tag function
     "CustomTag",
 
+    # Standalone attribute rendering
+    "render_attrs", "with_attrs", "combine",
+
+    # Batch rendering
+    "render_each", "render_fragment_list", "concat", "sorted_children",
+    "render_to_file", "render_cache_key", "render_chunks_for_sse", "table_from_records",
+
+    # Rendering configuration
+    "configure", "get_config", "reset_config",
+    "render_profile", "reset_profile",
+
+    # Introspection
+    "debug_tree", "pool_stats", "reset_pool_stats", "map_attr", "normalize_tag", "count_nodes",
+    "is_known_tag", "register_tag",
+    "is_void_tag", "is_raw_text_tag", "is_escapable_raw_text_tag", "register_void_tag", "register_raw_text_tag",
+    "is_boolean_attr", "register_boolean_attrs", "unregister_boolean_attrs",
+
+    # Formatting
+    "render_pretty", "render_variants", "strip_comments", "strip_tags",
+    "HtmlToken", "tokenize", "diff", "css", "render_svg", "json_ld", "render_email", "conditional_comment",
+    "cache_bust", "resource_hints",
+
+    # Template inheritance
+    "block", "extends",
+
+    # Request context
+    "RequestContext", "request_context", "get_request_context", "csrf_input", "csp_nonce",
+
+    # Custom escaping
+    "EscapeScope", "escape_with",
+
+    # Inline event handlers
+    "UnsafeAttr", "unsafe_attr",
+
+    # Id scoping
+    "IdScope", "id_scope", "scoped_id", "check_id_references",
+
+    # Precompiled static markup
+    "FrozenHtml", "freeze",
+
+    # Custom child renderers
+    "register_child_renderer", "unregister_child_renderer",
+
     # Core utilities
     "Page", "show", "template", "create_template", "page_template", "AttrDict", "when", "unless",
+    "script_src", "stylesheet", "responsive_img", "picture", "progress_bar", "meter_bar", "testid",
 
     # Datastar utilities
     "DS", "signals", "Signals", "reactive_class", "attribute_generator", "SSE", "ElementPatchMode", "EventType",
@@ -2,23 +2,88 @@
 Type stubs for RustyTags - High-performance HTML generation library
 """
 
-from typing import Any, Union, overload, Optional
+import os
+from typing import Any, Callable, Iterable, Literal, Union, overload, Optional
 
 # Type aliases for better type hints
 AttributeValue = Union[str, int, float, bool, dict[str, str]]
 Child = Union[str, int, float, bool, "HtmlString", "HtmlElement", "TagBuilder", Any]
 
 class HtmlString:
-    """Core HTML content container with optimized memory layout"""
+    """Core HTML content container with optimized memory layout
+
+    trusted=True (the default, and everything RustyTags renders) marks content
+    as markup that is embedded verbatim. HtmlString(text, trusted=False) holds
+    raw text: it is HTML-escaped exactly once wherever it becomes markup - as a
+    child, or via str(), render(), __html__(), encode() - and that result is
+    trusted, so nesting never escapes it twice. content is the stored value.
+    """
     content: str
+    trusted: bool
+
+    def __init__(self, content: str, trusted: bool = True) -> None: ...
 
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
     def render(self) -> str: ...
     def _repr_html_(self) -> str: ...
+    def _repr_mimebundle_(self, include: Iterable[str] | None = None, exclude: Iterable[str] | None = None) -> dict[str, str]: ...
     def __html__(self) -> str: ...
     def encode(self, encoding: str = "utf-8", errors: str | None = None) -> bytes: ...
     def __bytes__(self) -> bytes: ...
+    def __buffer__(self, flags: int, /) -> memoryview:
+        """Read-only zero-copy view of the UTF-8 content
+
+        memoryview(html) and sock.send(html) read the bytes in place. The view
+        keeps the HtmlString alive; HtmlString content is immutable, so the
+        bytes never change under a view. Writable views, and views of an
+        untrusted HtmlString, raise BufferError.
+        """
+        ...
+    def line_count(self) -> int:
+        """Number of lines, same as len(self.splitlines())"""
+        ...
+    def splitlines(self, keepends: bool = False) -> list[str]:
+        """Content split on "\n" ("\r\n" endings are stripped too unless keepends)"""
+        ...
+    def indent(self, prefix: str) -> "HtmlString":
+        """Prepend prefix to each line, like textwrap.indent
+
+        Whitespace-only lines are left as they are. This only shifts existing
+        lines (no pretty-printing) and also indents lines inside <pre> and
+        <textarea>, where the added whitespace is visible.
+        """
+        ...
+    def replace(self, old: str, new: "str | HtmlString | Any", count: int = -1) -> "HtmlString":
+        """Substring replacement in Rust, returning a new HtmlString
+
+        A blind text replace with str.replace semantics - not structure-aware, so
+        the pattern also matches inside text and unrelated attributes. new may be
+        a str, HtmlString or element. count < 0 replaces every occurrence.
+        """
+        ...
+    def etag(self) -> str:
+        """Stable content hash for ETag headers, as 32 lowercase hex digits
+
+        SipHash-2-4 with 128-bit output and a fixed zero key over the UTF-8
+        markup: the same content gives the same value in every process and
+        run. A cache validator, not a cryptographic digest. Quote it for the
+        header: response.headers["ETag"] = f'"{html.etag()}"'
+        """
+        ...
+    def write_gzip(self, path: str | os.PathLike[str]) -> int:
+        """Write the content gzip-compressed to path, returning the compressed size"""
+        ...
+    def __contains__(self, item: "str | HtmlString") -> bool:
+        """Substring test over the markup, same as item in str(self); an
+        untrusted HtmlString is searched in its escaped form"""
+        ...
+    def __format__(self, spec: str) -> str:
+        """f"{html}" is the markup and f"{html:pretty}" the render_pretty() form;
+        other specs apply to the markup as a str (f"{html:>40}")"""
+        ...
+    def __mul__(self, count: int) -> "HtmlString": ...
+    def __rmul__(self, count: int) -> "HtmlString": ...
     def parse(self) -> "HtmlElement":
         """Parse HTML string into an HtmlElement tree for inspection/modification"""
         ...
@@ -375,7 +440,12 @@ def Form(**kwargs: AttributeValue) -> TagBuilder: ...
 @overload  
 def Form(*children: Child, **kwargs: AttributeValue) -> HtmlString: ...
 def Form(*children: Child, **kwargs: AttributeValue) -> Union[TagBuilder, HtmlString]:
-    """Defines a html form"""
+    """Defines a html form
+
+    A hidden CSRF input is inserted as the first child when csrf="token" is
+    passed, or - for non-GET forms - when request_context(csrf=...) is active.
+    csrf=True requires a context token; csrf=False opts out.
+    """
     ...
 
 @overload
@@ -463,7 +533,20 @@ def Html(**kwargs: AttributeValue) -> TagBuilder: ...
 @overload  
 def Html(*children: Child, **kwargs: AttributeValue) -> HtmlString: ...
 def Html(*children: Child, **kwargs: AttributeValue) -> Union[TagBuilder, HtmlString]:
-    """Defines a html document"""
+    """Defines a html document
+
+    By default the output starts with <!doctype html>, Portal(...) contents are
+    moved into <head>/<body>, and configure(trailing_newline=True) applies.
+    Pass auto_structure=False for a plain <html>...</html> wrapper with no
+    doctype and no hoisting (e.g. for XML output or hand-managed structure).
+    configure(html_auto_structure=False) turns hoisting off by default but
+    keeps the doctype; configure(html_doctype=False) drops it. The trailing
+    newline applies to every form.
+
+    Set the document language and direction with lang= and dir=:
+        Html(Body(...), lang="ar", dir="rtl")
+    In strict mode dir must be "ltr", "rtl" or "auto".
+    """
     ...
 
 @overload
@@ -639,7 +722,12 @@ def OptionEl(**kwargs: AttributeValue) -> TagBuilder: ...
 @overload  
 def OptionEl(*children: Child, **kwargs: AttributeValue) -> HtmlString: ...
 def OptionEl(*children: Child, **kwargs: AttributeValue) -> Union[TagBuilder, HtmlString]:
-    """Defines a select option"""
+    """Defines a select option
+
+    Option(label, value=x, selected=..., disabled=...): selected/disabled are
+    boolean attributes (True bare, False/None omitted). value is data, so a
+    bool value renders value="true"/"false" rather than a bare attribute.
+    """
     ...
 
 # Alias for proper HTML tag name
@@ -730,7 +818,11 @@ def Script(**kwargs: AttributeValue) -> TagBuilder: ...
 @overload  
 def Script(*children: Child, **kwargs: AttributeValue) -> HtmlString: ...
 def Script(*children: Child, **kwargs: AttributeValue) -> Union[TagBuilder, HtmlString]:
-    """Defines a client-side script"""
+    """Defines a client-side script
+
+    module=True is shorthand for type="module" and raises ValueError when
+    combined with a different type.
+    """
     ...
 
 @overload
@@ -757,6 +849,19 @@ def Small(*children: Child, **kwargs: AttributeValue) -> Union[TagBuilder, HtmlS
     """Defines a small text"""
     ...
 
+@overload
+def Slot(**kwargs: AttributeValue) -> TagBuilder: ...
+@overload  
+def Slot(*children: Child, **kwargs: AttributeValue) -> HtmlString: ...
+def Slot(*children: Child, **kwargs: AttributeValue) -> Union[TagBuilder, HtmlString]:
+    """Defines a shadow DOM slot for web components
+
+    The native <slot> element: name= picks the light-DOM children marked with
+    a matching slot= attribute, and the children are fallback content shown
+    when none are assigned. Unrelated to block()/extends() template blocks.
+    """
+    ...
+
 @overload
 def Source(**kwargs: AttributeValue) -> TagBuilder: ...
 @overload  
@@ -842,9 +947,15 @@ def Template(**kwargs: AttributeValue) -> TagBuilder: ...
 @overload  
 def Template(*children: Child, **kwargs: AttributeValue) -> HtmlString: ...
 def Template(*children: Child, **kwargs: AttributeValue) -> Union[TagBuilder, HtmlString]:
-    """Defines a template container"""
+    """Defines a template container
+
+    The HTML <template> element: its content is parsed but inert until cloned
+    by script. Not related to the template()/page_template() layout helpers.
+    """
     ...
 
+HtmlTemplate = Template
+
 @overload
 def Textarea(**kwargs: AttributeValue) -> TagBuilder: ...
 @overload  
@@ -1136,6 +1247,122 @@ def CustomTag(tag_name: str, *children: Child, **kwargs: AttributeValue) -> Unio
     """Creates a custom HTML tag with any tag name"""
     ...
 
+def render_each(elements: Iterable[Child]) -> list[HtmlString]:
+    """Render each element separately in one call, returning one HtmlString per item
+
+    Accepts any iterable (lists, tuples, generators). None items are left out,
+    or kept as "" with configure(none_children="empty").
+    """
+    ...
+
+def render_fragment_list(children: Iterable[Child]) -> list[str]:
+    """Render each child to its own plain str, one entry per input item
+
+    Children are converted exactly as inside Fragment(...) - HtmlString/__html__
+    objects stay markup, text is written as-is, nested lists and generators
+    are flattened into their item's entry - but nothing is concatenated, so
+    results can be interleaved with the caller's own output. None items are
+    left out, or kept as "" with configure(none_children="empty") so indexes
+    line up with the input. Use Fragment when you want the joined markup.
+    """
+    ...
+
+def concat(*parts: Child) -> HtmlString:
+    """Join any number of children into one HtmlString in a single allocation
+
+    Parts are converted exactly as Fragment children (None becomes "", lists and
+    generators are flattened); the result is sized once instead of growing.
+    """
+    ...
+
+def sorted_children(
+    items: Iterable[Any],
+    key: Optional[Callable[[Any], Any]] = None,
+    template: Optional[Callable[[Any], Child]] = None,
+    reverse: bool = False,
+) -> HtmlString:
+    """Render items in sorted(items, key=key, reverse=reverse) order
+
+    Sorting happens on the data before template(item) renders each item, and
+    is stable. Items or keys that cannot be compared (mixed types) raise
+    TypeError; pass a key mapping every item to a comparable value.
+    """
+    ...
+
+def render_chunks_for_sse(elements: Iterable[Child], event: Optional[str] = None) -> list[str]:
+    """Render each element as a server-sent event frame ("data: ...\n\n")
+
+    Multi-line markup gets one "data: " line per line (\r\n, \r or \n), which
+    the browser joins back with "\n". event adds an "event: <name>" line to
+    every frame; names with line breaks raise ValueError.
+    """
+    ...
+
+def table_from_records(
+    records: Iterable[dict[str, Any]],
+    columns: list[str] | None = None,
+    totals: dict[str, Literal["sum", "avg", "count", "min", "max"]] | None = None,
+    totals_label: Child = None,
+    **attrs: AttributeValue,
+) -> HtmlString:
+    """Render a list of dicts as a <table> with <thead>, <tbody> and optional <tfoot>
+
+    columns picks and orders the keys (default: the first record's keys); a
+    missing key or None is an empty cell. totals adds a footer row computed in
+    Rust, skipping None: sum/avg/min/max use numeric values (int, float and
+    number-like objects, not bool) and leave the cell blank for non-numeric
+    columns; count counts non-None values. Integer sums stay exact up to the
+    64-bit range. totals_label fills the first footer cell when that column
+    has no aggregate. Other keywords are <table> attributes. Unknown
+    aggregates, or totals for columns not in the table, raise ValueError.
+    """
+    ...
+
+def render_cache_key(element: Child) -> str:
+    """Content hash of a rendered element - the same value as HtmlString.etag()"""
+    ...
+
+def render_to_file(
+    element: Child,
+    path: str | os.PathLike[str],
+    compress: Literal["gzip", "brotli"] | None = None,
+    trailing_newline: bool | None = None,
+) -> int:
+    """Render an element and write it to path, optionally pre-compressed
+
+    Returns the number of bytes written (the compressed size when compressing).
+    Compression runs in Rust with the GIL released; gzip output carries no
+    timestamp, so the same content always gives the same bytes. trailing_newline
+    defaults to the configure(trailing_newline=...) setting.
+    """
+    ...
+
+def render_attrs(mapping: dict[str, AttributeValue]) -> str:
+    """Render just the attribute string for a mapping, with a leading space
+
+    Applies the same alias mapping (cls -> class, for_ -> for, ...) and Datastar
+    handling as tag functions. An empty mapping renders "".
+    """
+    ...
+
+def with_attrs(element: Child, **attrs: AttributeValue) -> HtmlString:
+    """Add or override attributes on the root element of rendered markup
+
+    Keywords use the tag function aliases. Class names are appended to the
+    existing class list, other attributes are replaced, and None/False removes
+    an attribute. Raises ValueError unless there is exactly one root element.
+    """
+    ...
+
+def combine(base: dict[str, AttributeValue], *overrides: dict[str, AttributeValue]) -> dict[str, AttributeValue]:
+    """Merge attribute dicts left to right into one dict for ** expansion
+
+    Keys rendering as the same attribute ("cls", "class_") are one key, keeping
+    the first spelling. Class strings concatenate without duplicates; every
+    other key takes the last value.
+    """
+    ...
+
 # Fragment - renders children without wrapper tag
 @overload
 def Fragment(**kwargs: AttributeValue) -> TagBuilder: ...
@@ -1145,6 +1372,15 @@ def Fragment(*children: Child, **kwargs: AttributeValue) -> Union[TagBuilder, Ht
     """Fragment renders its children without creating a wrapper element"""
     ...
 
+def Portal(target: str, *children: Child) -> HtmlString:
+    """Render children into another part of the document
+
+    target is "head" or "body" (end of body). Contents are moved when the
+    enclosing Html(...) is assembled; identical contents per target are emitted
+    once. Outside Html the content stays in place wrapped in comment markers.
+    """
+    ...
+
 # Safe - HTML escaping for displaying text safely
 def Safe(text: str) -> HtmlString:
     """Renders text with HTML escaping to prevent XSS and display HTML as text
@@ -1160,6 +1396,579 @@ def Safe(text: str) -> HtmlString:
     """
     ...
 
+# Rendering configuration
+def configure(**options: Any) -> None:
+    """Update process-wide rendering options
+
+    Options:
+        mode: Output dialect, "html" (default), "xml" or "xhtml". HTML only lets
+            void elements (br, img, ...) omit their end tag; `<div/>` is parsed as
+            an unclosed <div>. XML/polyglot output may self-close any element.
+            "xhtml" writes every childless element as `<div />`.
+        self_close_empty: In "xml" mode, emit every childless element as
+            `<div/>` instead of `<div></div>`. Ignored in "html" mode (default False)
+        svg_self_closing: Emit childless SVG leaf elements (circle, rect, path, ...)
+            as `<circle .../>` instead of `<circle ...></circle>` (default False)
+        warn_deprecated_aliases: Emit DeprecationWarning when nonstandard attribute
+            aliases (htmlClass, klass, fr, htmlFor) are used (default False)
+        strict: Validate markup rules and raise ValueError on violations, e.g.
+            Td/Th colspan/rowspan must be positive integers, dir must be "ltr",
+            "rtl" or "auto", and CustomTag names
+            must be known HTML/SVG elements, hyphenated custom elements or
+            registered with register_tag(). Html(...) documents may contain at
+            most one <base>, and warn (UserWarning) about for/aria-* references
+            to missing ids (default False)
+        profile: Record per-tag render timings, read back with render_profile().
+            Costs a single atomic load per tag when off (default False)
+        trailing_newline: End Html(...) documents and render_to_file output with
+            "\n", for EOF-newline linters and pre-commit hooks (default False)
+        guard_event_handlers: Require inline on* attributes (onclick, onload, ...)
+            to be passed through unsafe_attr(); plain values raise ValueError.
+            Datastar on_* attributes are unaffected (default False)
+        no_pool: Allocate render buffers directly and skip the tag/attribute name
+            caches, for CLI tools and serverless functions that render once and
+            exit. Output is identical; long-running servers should keep the pool
+            (default False)
+        none_children: How None children are handled. "skip" (default) leaves them
+            out; "error" raises TypeError, catching values that unexpectedly
+            became None; "empty" renders them as "". Inside elements "skip" and
+            "empty" give the same markup; render_each and render_fragment_list
+            drop None items under "skip" and keep a "" entry under "empty".
+            Applies to tag functions, Fragment, HtmlElement trees, render_each
+            and render_fragment_list
+        strip_testids: Drop data-testid attributes (testid(), data_testid=...)
+            from the output, so test hooks can stay in the source while
+            production HTML ships without them (default False)
+        max_attr_value_len: In strict mode, the longest attribute value in bytes;
+            longer values raise ValueError naming the attribute, catching blobs
+            or file contents passed as attributes by mistake. Positive int or
+            None (default None, no limit)
+        flatten_depth: How many levels of list, tuple and iterator children are
+            flattened into their parent; deeper ones go through the normal
+            conversion (registered renderers, __str__). None (default) flattens
+            every level, 0 turns flattening off. Generators are consumed when
+            the element is built, so a generator can only fill one element
+        lowercase_attrs: Emit attribute names in lowercase (dataFoo -> datafoo,
+            including Datastar attributes), for style guides that require it.
+            Case-sensitive SVG camelCase attributes such as viewBox and
+            preserveAspectRatio keep their spelling (default False)
+        max_document_size: Largest output in bytes any element, Fragment or
+            Html(...) document may reach; larger output raises ValueError with
+            the size reached and the tag being built. Checked as children
+            accumulate, so a runaway list stops near the limit. Positive int or
+            None (default None, no limit)
+        attr_name_policy: What happens to attribute names containing characters
+            HTML does not allow in a name (whitespace, quotes, <, >, /, =,
+            control characters), which could otherwise break out of the tag
+            when names come from user input. "passthrough" (default) emits them
+            unchanged, "strip" removes those characters (dropping the attribute
+            if nothing is left), "error" raises ValueError
+        html_auto_structure: Default for Html(..., auto_structure=...). False
+            turns off Portal hoisting for every Html(...) that does not pass
+            auto_structure=True; the doctype is still written (default True)
+        html_doctype: Start Html(...) documents with <!doctype html>. False
+            drops it everywhere; Html(..., auto_structure=False) never writes
+            it (default True)
+        on_tag_rendered: Callable invoked as fn(tag) after each element is
+            built, innermost first, with the lowercase tag name - for usage
+            telemetry. Exceptions are passed to sys.unraisablehook and never
+            fail the render; tags rendered inside the callback don't call it
+            again. None (default) removes it; while unset the hook costs one
+            atomic load per tag
+        child_method_errors: What happens when a child's __html__(),
+            _repr_html_() or render() raises. "propagate" (default) re-raises
+            the exception with a note naming the child type and method;
+            "fallthrough" ignores it and tries the next method, then str()
+
+    Unknown option names raise ValueError.
+    """
+    ...
+
+def get_config() -> dict[str, Any]:
+    """Return the current rendering options"""
+    ...
+
+def reset_config() -> None:
+    """Restore every rendering option to its default"""
+    ...
+
+def render_profile() -> dict[str, dict[str, int]]:
+    """Per-tag timings collected while configure(profile=True) was active
+
+    Returns {tag: {"ns": total_nanoseconds, "calls": count}}. Rendering is eager,
+    so a tag's time covers its own attribute processing and assembly; its
+    children were rendered (and timed) before it was called.
+    """
+    ...
+
+def reset_profile() -> None:
+    """Discard collected render timings"""
+    ...
+
+# Introspection
+def debug_tree(element: Child) -> dict[str, Any]:
+    """Describe the rendered structure of an element as nested dicts
+
+    Every node has a "type" key:
+        {"type": "element", "tag": str, "attrs": dict[str, str], "children": list[node]}
+        {"type": "text", "text": str}
+        {"type": "fragment", "children": list[node]}  # zero or several root nodes
+
+    Whitespace-only text is skipped and entities are decoded, matching HtmlString.parse().
+    """
+    ...
+
+def map_attr(name: str) -> str:
+    """The attribute name a keyword argument renders as, for a plain (non-dict) value
+
+    Uses the same routing as tag functions, so Datastar shorthands are included:
+    map_attr("cls") -> "class", map_attr("data_x") -> "data-x",
+    map_attr("on_click") -> "data-on:click".
+    """
+    ...
+
+def normalize_tag(name: str) -> str:
+    """The element name a tag function or CustomTag name renders as
+
+    normalize_tag("OptionEl") -> "option", normalize_tag("Div") -> "div".
+    """
+    ...
+
+def count_nodes(element: Child, text: bool = False) -> int:
+    """Number of element nodes in the rendered output
+
+    Every start tag counts once, void and self-closing elements included; end
+    tags, comments and the doctype never count. With text=True, text runs that
+    are not whitespace-only (script/style content included) are counted too.
+    count_nodes(Ul(Li("a"), Li("b"))) -> 3, with text=True -> 5.
+    """
+    ...
+
+def pool_stats() -> dict[str, int | float]:
+    """String pool counters for the calling thread's render buffers
+
+    Keys: hits, misses, hit_ratio (hits / (hits + misses), 1.0 before any request),
+    bypasses (requests above max_pooled_capacity, allocated directly), returns,
+    discards (buffers too large or pool full), max_pooled_capacity, pooled
+    (buffers currently held by this thread).
+    """
+    ...
+
+def reset_pool_stats() -> None:
+    """Zero the pool counters; pooled buffers are kept."""
+    ...
+
+def is_known_tag(name: str) -> bool:
+    """True for standard HTML/SVG elements, hyphenated custom elements and names
+    registered with register_tag() - the names CustomTag accepts in strict mode."""
+    ...
+
+def register_tag(name: str) -> None:
+    """Accept an additional tag name in strict mode. Raises ValueError for invalid tag names."""
+    ...
+
+def is_void_tag(name: str) -> bool:
+    """True for void elements (br, img, input, ...) and any registered with register_void_tag()."""
+    ...
+
+def is_raw_text_tag(name: str) -> bool:
+    """True for elements whose content is raw text and never escaped (script,
+    style) and any registered with register_raw_text_tag()."""
+    ...
+
+def is_escapable_raw_text_tag(name: str) -> bool:
+    """True for escapable raw-text elements (textarea, title): their content is
+    not parsed as markup, but text children are escaped when rendered."""
+    ...
+
+def register_void_tag(name: str) -> None:
+    """Treat an additional tag as void. Raises ValueError for invalid tag names."""
+    ...
+
+def register_raw_text_tag(name: str) -> None:
+    """Treat an additional tag's content as raw text. Raises ValueError for invalid tag names."""
+    ...
+
+def is_boolean_attr(name: str) -> bool:
+    """True for boolean attributes (disabled, checked, inert, ...) and any
+    registered with register_boolean_attrs(). Their value "" or their own name
+    renders bare, like True: Button(disabled="disabled") -> <button disabled>."""
+    ...
+
+def register_boolean_attrs(names: Iterable[str]) -> None:
+    """Treat additional attributes as boolean. Raises ValueError for invalid attribute names."""
+    ...
+
+def unregister_boolean_attrs(names: Iterable[str]) -> bool:
+    """Remove names added with register_boolean_attrs(), returning whether any was
+    registered. Built-in boolean attributes cannot be removed."""
+    ...
+
+def render_pretty(element: Child, indent: int | str = 2) -> HtmlString:
+    """Render an element as indented markup, one element or text run per line
+
+    indent is the unit written per nesting level: an int means that many
+    spaces, a str (e.g. "\t") is used literally and may only contain spaces and
+    tabs (ValueError otherwise). <pre> and raw-text content is kept verbatim.
+    """
+    ...
+
+def render_variants(
+    element: Child, pretty: bool = True, minified: bool = True, indent: int | str = 2
+) -> dict[str, HtmlString]:
+    """Render once and return pretty and/or minified output from a single tokenization
+
+    Returns a dict with "pretty" and/or "minified" keys depending on the flags.
+    indent is the pretty-print unit, as in render_pretty().
+    Content of <pre>, <script>, <style>, <textarea> and <title> is kept verbatim.
+    Minified output drops comments except those strip_comments() keeps.
+    Raises ValueError if both flags are False.
+    """
+    ...
+
+def strip_comments(element: Child) -> HtmlString:
+    """Remove <!-- ... --> comments from rendered output
+
+    Kept: IE conditional comments (<!--[if IE]>...<![endif]-->, <!--<![endif]-->)
+    and comments marked with a leading "!" (<!--! license -->), for notes that
+    are meant to ship. Everything else is copied unchanged; "<!--" inside
+    script or style content is not a comment and is left alone.
+    """
+    ...
+
+class HtmlToken:
+    """One token produced by tokenize()
+
+    Joining every token's raw text reproduces the input exactly.
+    """
+    kind: Literal["doctype", "comment", "start_tag", "end_tag", "text", "raw_text"]
+    raw: str
+    name: str | None
+    """Lowercased tag name for start_tag/end_tag tokens, otherwise None"""
+    attrs: list[tuple[str, str | None]]
+    """(name, value) pairs for start_tag tokens; value is None for bare attributes
+    and is returned as written (quotes stripped, entities not decoded)"""
+    self_closing: bool
+
+def strip_tags(element: Child) -> str:
+    """Text content of rendered markup with the tags removed
+
+    For plaintext email alternatives and search indexes. Entities are decoded,
+    whitespace collapses to single spaces and the ends are trimmed. Tags other
+    than inline ones (span, a, strong, em, ...) separate words, so
+    <p>a</p><p>b</p> gives "a b". Script/style content and comments are dropped.
+    """
+    ...
+
+def tokenize(markup: str | HtmlString) -> list[HtmlToken]:
+    """Split markup into tokens using the same tokenizer as render_variants()
+
+    Non-validating and forgiving: stray "<" stays in text and unterminated
+    constructs run to the end of the input. Content of script, style, textarea,
+    title (and registered raw-text tags) is a single "raw_text" token.
+    """
+    ...
+
+def diff(old: Child, new: Child) -> list[dict[str, Any]]:
+    """Compute patch operations that turn the old render into the new one
+
+    Each patch is a dict applied in order:
+        {"op": "replace", "path": [1, 0], "html": "..."}
+        {"op": "insert", "path": [3], "html": "..."}
+        {"op": "remove", "path": [2]}
+    path indexes child nodes (text and comments included) from the top level
+    down. Elements whose start tag is unchanged are descended into; anything
+    else that differs is replaced. Identical renders give [].
+    """
+    ...
+
+def css(rules: dict[str, Any]) -> str:
+    """Serialize nested CSS rules into a compact stylesheet for Style(...)
+
+    Nested keys combine with the parent selector: ":hover"/"::before" attach
+    directly, "&" stands for the parent, other keys become descendant selectors,
+    and comma lists expand. "@media ..." keys wrap the rules inside them.
+    Property names use snake_case or kebab-case (font_size -> font-size);
+    values are str, int or float, and None skips a declaration.
+    "<" is escaped so the output can't close the <style> element; braces or
+    semicolons in names and values raise ValueError.
+
+    Example:
+        css({".btn": {"color": "red", ":hover": {"color": "blue"}}})
+        # .btn{color:red}.btn:hover{color:blue}
+    """
+    ...
+
+def render_svg(element: Child, standalone: bool = True) -> HtmlString:
+    """Render an <svg> element as a standalone SVG document
+
+    Adds xmlns="http://www.w3.org/2000/svg" to the root when missing, and
+    xmlns:xlink when xlink: attributes are used; declarations already present
+    are kept as written. standalone=True prepends '<?xml version="1.0"?>'
+    (an existing prolog is never duplicated). Raises ValueError if the root
+    element isn't <svg>.
+
+    Example:
+        render_to_file(render_svg(Svg(Circle(r="4"), viewBox="0 0 10 10")), "icon.svg")
+    """
+    ...
+
+def json_ld(data: Any, indent: int | None = None, **kwargs: AttributeValue) -> HtmlString:
+    """Structured data in a <script type="application/ld+json"> element
+
+    data (dicts, lists, str, numbers, bools, None - nested freely) is
+    serialized as JSON with keys sorted; indent pretty-prints with that many
+    spaces. "<", ">" and "&" are written as \\u escapes, so strings such as
+    "</script>" can't end the element early. Other keyword arguments become
+    script attributes (id, nonce, ...). Raises TypeError for data that is not
+    JSON-serializable.
+
+    Example:
+        json_ld({"@context": "https://schema.org", "@type": "Person", "name": "Ada"})
+    """
+    ...
+
+def render_email(element: Child, styles: dict[str, str | dict[str, str | int | float]] | None = None) -> HtmlString:
+    """Rendered markup adjusted for HTML email clients
+
+    Exactly three differences from normal output:
+      1. classes listed in styles are inlined into a style attribute, ahead of
+         any existing inline style (which wins on conflicts); values are CSS
+         strings or css()-style property dicts
+      2. <script> elements are removed with their content
+      3. doctype declarations are removed
+    Everything else - table structure, attributes, comments including
+    <!--[if mso]> blocks - is copied verbatim. Use Html(..., auto_structure=False)
+    or plain tags to avoid head hoisting in the first place.
+    """
+    ...
+
+def conditional_comment(condition: str, node: Child) -> HtmlString:
+    """Wrap rendered markup in <!--[if condition]>...<![endif]--> (legacy feature)
+
+    Only Outlook (mso) and Internet Explorer up to 9 act on these; every other
+    client sees a single comment. The node is rendered as markup, not escaped.
+    Raises ValueError for an empty condition, one containing ']', '>' or "--",
+    or content containing "-->".
+
+    Example:
+        conditional_comment("lt IE 9", Link(rel="stylesheet", href="/ie.css"))
+        # <!--[if lt IE 9]><link rel="stylesheet" href="/ie.css"></link><![endif]-->
+    """
+    ...
+
+def cache_bust(
+    element: Child,
+    version: str | None = None,
+    versions: dict[str, str] | None = None,
+    param: str = "v",
+) -> HtmlString:
+    """Append ?v=<version> to local <script src>, <link href> and <img src> URLs
+
+    <link> is rewritten only for asset rels (stylesheet, icon, apple-touch-icon,
+    preload, modulepreload, prefetch, manifest, ...); canonical, alternate and
+    other page links are left alone. Only root-relative ("/static/app.js") and relative ("img/logo.png") URLs
+    are rewritten; URLs with a scheme (https:, data:), protocol-relative
+    "//host/..." URLs and URLs whose query already has param are left alone.
+    versions maps URL paths (before "?" and "#", as written) to their own
+    version and wins over the global version; a path in neither is untouched.
+    An existing query gets "&v=..." and a #fragment stays last. Values are
+    percent-encoded. Raises ValueError without version/versions or for a
+    param that is not a plain name.
+
+    Example:
+        cache_bust(Script(src="/app.js"), versions={"/app.js": "3f2a9c"})
+        # <script src="/app.js?v=3f2a9c"></script>
+    """
+    ...
+
+def resource_hints(
+    preconnect: list[str | dict[str, Any]] | None = None,
+    dns_prefetch: list[str | dict[str, Any]] | None = None,
+    preload: list[dict[str, Any]] | None = None,
+) -> HtmlString:
+    """Render <link> resource hints for the <head> in the recommended order
+
+    Preconnect links come first, then dns-prefetch, then preload, each group in
+    the order given. preconnect and dns_prefetch entries are URLs or dicts of
+    attributes with "href"; preload entries are dicts with "href" and an "as"
+    destination (audio, document, embed, fetch, font, image, object, script,
+    style, track, video, worker). Font preloads get crossorigin unless the dict
+    sets it. Raises ValueError for an unknown "as", a missing "href" or a
+    "rel" key.
+
+    Example:
+        resource_hints(preconnect=["https://cdn.example.com"],
+                       preload=[{"href": "/app.css", "as": "style"}])
+        # <link rel="preconnect" href="https://cdn.example.com"></link><link rel="preload" href="/app.css" as="style"></link>
+    """
+    ...
+
+def block(name: str, *default: Child) -> HtmlString:
+    """Define a named, overridable block with default content
+
+    Rendered as <!--block:NAME-->default<!--/block:NAME--> so extends() can find it.
+    Names may contain letters, digits, '_' and '-'.
+    """
+    ...
+
+def extends(base: Child, blocks: dict[str, Child] | None = None, keep_blocks: bool = False) -> HtmlString:
+    """Render a base layout with some of its blocks overridden
+
+    Blocks that aren't overridden keep their default content. Naming a block the base
+    doesn't define raises ValueError. keep_blocks=True keeps the block markers so the
+    result can be extended again (multi-level inheritance).
+    """
+    ...
+
+class RequestContext:
+    """Context manager returned by request_context()"""
+    def __enter__(self) -> dict[str, Any]: ...
+    def __exit__(self, exc_type: Any = None, exc_value: Any = None, traceback: Any = None) -> bool: ...
+
+def request_context(**values: Any) -> RequestContext:
+    """Make request-scoped values (csrf=..., nonce=..., ...) available to helpers
+
+    Stored in a contextvars.ContextVar, so values are isolated per thread and per
+    asyncio task. Nested blocks layer over the enclosing values.
+    """
+    ...
+
+def get_request_context() -> dict[str, Any]:
+    """Copy of the active request-scoped values (empty outside request_context)"""
+    ...
+
+def csrf_input(name: str | None = None) -> HtmlString:
+    """Hidden input carrying the CSRF token from request_context(csrf=...)
+
+    name defaults to request_context(csrf_field=...), else "csrf_token".
+    Raises ValueError when no CSRF token is available. Form() inserts this
+    input by itself for non-GET forms, so it is rarely needed directly.
+    """
+    ...
+
+def csp_nonce() -> str | None:
+    """The CSP nonce from request_context(nonce=...), or None"""
+    ...
+
+class EscapeScope:
+    """Context manager returned by escape_with()"""
+    def __enter__(self) -> None: ...
+    def __exit__(self, exc_type: Any = None, exc_value: Any = None, traceback: Any = None) -> bool: ...
+
+def escape_with(func: Callable[[str], str] | None) -> EscapeScope:
+    """Escape plain str children with func for tags built inside the with block
+
+    Only plain strings are passed to func; HtmlString and __html__ objects are
+    trusted markup and left alone. func must return a str (TypeError otherwise).
+    Rendering is eager, so only elements created inside the block are affected.
+    Pass None to restore the default behavior inside an outer block. The active
+    function is held in a ContextVar (per thread and asyncio task).
+    """
+    ...
+
+class UnsafeAttr:
+    """An on* attribute value marked as intentional by unsafe_attr()"""
+    @property
+    def name(self) -> str: ...
+    @property
+    def value(self) -> str: ...
+
+def unsafe_attr(name: str, code: str) -> dict[str, UnsafeAttr]:
+    """Mark an inline event handler as intentional, for ** unpacking
+
+    Required for on* attributes under configure(guard_event_handlers=True), which
+    keeps every inline handler explicit and easy to audit.
+
+    Example:
+        Button("Save", **unsafe_attr("onclick", "save()"))
+    """
+    ...
+
+class IdScope:
+    """Context manager returned by id_scope()"""
+    def __enter__(self) -> str: ...
+    def __exit__(self, exc_type: Any = None, exc_value: Any = None, traceback: Any = None) -> bool: ...
+
+def id_scope(prefix: str) -> IdScope:
+    """Prefix ids and id references of tags built inside the with block
+
+    id="name" becomes id="<prefix>-name". Attributes that reference ids are
+    rewritten the same way so they stay consistent: single ids (for, form, list,
+    aria-activedescendant, aria-errormessage, popovertarget, commandfor, anchor)
+    and space-separated id lists (aria-labelledby, aria-describedby,
+    aria-controls, aria-owns, aria-flowto, aria-details, headers, itemref).
+    URLs like href="#name" are not rewritten - build them with scoped_id().
+
+    Nested scopes compose ("outer-inner-name"); `as` yields the full prefix.
+    Rendering is eager, so only elements created inside the block are affected.
+    The prefix is held in a ContextVar (per thread and asyncio task).
+    """
+    ...
+
+def scoped_id(name: str) -> str:
+    """The id name as written in the current id_scope (unchanged outside one)"""
+    ...
+
+def check_id_references(element: Child) -> list[str]:
+    """List for / aria-labelledby / aria-describedby references to missing ids
+
+    Scans the rendered element as a whole, so pass the complete page or form.
+    Returns one message per broken reference, or [] when all resolve. With
+    configure(strict=True), Html(...) runs this check on the finished document
+    and emits a UserWarning per broken reference.
+    """
+    ...
+
+class FrozenHtml:
+    """Immutable pre-rendered markup returned by freeze()
+
+    Embedded by tags with a straight byte copy. Compares and hashes like its str.
+    """
+    @property
+    def content(self) -> str: ...
+    def __html__(self) -> str: ...
+    def __len__(self) -> int: ...
+    def encode(self) -> bytes: ...
+
+def freeze(element: Child) -> FrozenHtml:
+    """Render an element once into immutable markup for static fragments
+
+    Use for content that never changes (logos, footers, icon sprites) and is
+    embedded in many pages: tags copy a FrozenHtml child straight into their
+    output without any per-child conversion. Freezing a FrozenHtml shares it.
+
+    Example:
+        FOOTER = freeze(Footer(P("(c) 2025 Example")))
+        Body(main_content, FOOTER)
+    """
+    ...
+
+def register_child_renderer(
+    predicate: type | tuple[type, ...] | Callable[[Any], bool],
+    renderer: Callable[[Any], str | HtmlString],
+) -> None:
+    """Teach tags how to render children of a third-party type
+
+    predicate is a type (or tuple of types, matched with isinstance) or a
+    callable returning True for children it handles. renderer gets the child
+    and returns a str (written as-is) or an HtmlString.
+
+    Precedence: None, HtmlString, FrozenHtml, str and bool children are always
+    handled natively. Registered renderers come next, most recent first, and
+    win over number formatting and the __html__ / _repr_html_ / render()
+    protocols; __str__ is the last resort.
+
+    Example:
+        register_child_renderer(Decimal, lambda d: f"{d:,.2f}")
+        Td(Decimal("1234.5"))  # <td>1,234.50</td>
+    """
+    ...
+
+def unregister_child_renderer(predicate: type | tuple[type, ...] | Callable[[Any], bool]) -> bool:
+    """Remove the renderers registered for predicate; returns whether any was removed"""
+    ...
+
 __version__: str
 __author__: str
 __description__: str
@@ -0,0 +1,14 @@
+"""
+Optional layout component kit for RustyTags.
+
+Small, opinionated helpers implemented in Rust that emit semantic scaffolding
+(cards, grids, navbars) with configurable class names, plus ARIA live regions
+(live_region, alert). They are built on the same rendering primitives as the
+core tags and are not imported by default:
+
+    from rusty_tags.components import card, grid, navbar, live_region, alert
+"""
+
+from .core import card, grid, navbar, live_region, alert
+
+__all__ = ["card", "grid", "navbar", "live_region", "alert"]
@@ -0,0 +1,40 @@
+"""
+Type stubs for the optional RustyTags component kit
+"""
+
+from typing import Any, Literal, Optional
+
+from . import HtmlString
+
+def card(title: Any, body: Any, footer: Optional[Any] = None, cls: str = "card") -> HtmlString:
+    """Card component - an <article> with header, body and optional footer
+
+    Sub-element classes are derived from `cls` (card-header, card-body, card-footer).
+    """
+    ...
+
+def grid(items: list[Any], cols: int = 3, cls: str = "grid", gap: Optional[str] = None) -> HtmlString:
+    """Grid component - wraps each item in a cell of an N-column CSS grid"""
+    ...
+
+def navbar(brand: Any, links: list[tuple[Any, str]] = ..., brand_href: str = "/", cls: str = "navbar") -> HtmlString:
+    """Navbar component - a <nav> with a brand link and a list of (label, href) links"""
+    ...
+
+def live_region(*children: Any, politeness: Literal["off", "polite", "assertive"] = "polite", **attrs: Any) -> HtmlString:
+    """A <div> whose later content changes are announced by screen readers
+
+    "polite" adds role="status" aria-live="polite" (announced when the user is
+    idle), "assertive" role="alert" aria-live="assertive" (interrupts), "off"
+    only aria-live="off". Other values raise ValueError. Keyword attributes
+    work as on Div and may override the defaults. Render the region before
+    its content changes - regions inserted already filled are often not read.
+    """
+    ...
+
+def alert(*children: Any, **attrs: Any) -> HtmlString:
+    """A <div role="alert"> for important, time-sensitive messages
+
+    Announced immediately, even when inserted with its content.
+    """
+    ...
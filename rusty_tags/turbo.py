@@ -0,0 +1,77 @@
+"""
+Optional Hotwire/Turbo helpers for RustyTags.
+
+<turbo-frame> and <turbo-stream> are custom elements, so they already work
+through CustomTag; these wrappers add the expected structure (stream content
+inside a <template>) and validate stream actions so typos fail loudly:
+
+    from rusty_tags.turbo import turbo_frame, turbo_stream
+"""
+
+from typing import Any, Optional
+
+from .core import CustomTag, HtmlString, Template
+
+TURBO_STREAM_ACTIONS = frozenset({
+    "append", "prepend", "replace", "update", "remove",
+    "before", "after", "morph", "refresh",
+})
+
+# Actions that carry no content and must not be given a <template>
+_CONTENTLESS_ACTIONS = frozenset({"remove", "refresh"})
+
+
+def turbo_frame(*children: Any, id: str, src: Optional[str] = None, **kwargs: Any) -> HtmlString:
+    """<turbo-frame id=...> wrapper
+
+    Args:
+        *children: Frame content
+        id: Frame id - required, Turbo matches frames across responses by id
+        src: URL to load the frame from (lazy frames)
+        **kwargs: Extra attributes (loading="lazy", target="_top", ...)
+    """
+    if not id:
+        raise ValueError("turbo_frame() requires a non-empty id")
+    attrs = {"id": id}
+    if src is not None:
+        attrs["src"] = src
+    return CustomTag("turbo-frame", *children, **attrs, **kwargs)
+
+
+def turbo_stream(
+    action: str,
+    *children: Any,
+    target: Optional[str] = None,
+    targets: Optional[str] = None,
+    **kwargs: Any,
+) -> HtmlString:
+    """<turbo-stream action=... target=...> with its content wrapped in <template>
+
+    Args:
+        action: One of append, prepend, replace, update, remove, before, after,
+            morph, refresh
+        *children: Content for the action (not allowed for remove/refresh)
+        target: DOM id of the element to act on
+        targets: CSS selector for several elements (instead of target)
+        **kwargs: Extra attributes (method="morph", request_id=..., ...)
+    """
+    if action not in TURBO_STREAM_ACTIONS:
+        raise ValueError(
+            f"Unknown turbo-stream action {action!r} "
+            f"(expected one of: {', '.join(sorted(TURBO_STREAM_ACTIONS))})"
+        )
+    if action != "refresh" and (target is None) == (targets is None):
+        raise ValueError("turbo_stream() needs exactly one of target= or targets=")
+    if action in _CONTENTLESS_ACTIONS and children:
+        raise ValueError(f"turbo-stream action {action!r} takes no content")
+
+    attrs = {"action": action}
+    if target is not None:
+        attrs["target"] = target
+    if targets is not None:
+        attrs["targets"] = targets
+    content = (Template(*children),) if action not in _CONTENTLESS_ACTIONS else ()
+    return CustomTag("turbo-stream", *content, **attrs, **kwargs)
+
+
+__all__ = ["turbo_frame", "turbo_stream", "TURBO_STREAM_ACTIONS"]
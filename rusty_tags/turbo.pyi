@@ -0,0 +1,32 @@
+"""
+Type stubs for the optional Hotwire/Turbo helpers
+"""
+
+from typing import Any, Literal, Optional
+
+from . import HtmlString
+
+TurboStreamAction = Literal[
+    "append", "prepend", "replace", "update", "remove",
+    "before", "after", "morph", "refresh",
+]
+
+TURBO_STREAM_ACTIONS: frozenset[str]
+
+def turbo_frame(*children: Any, id: str, src: Optional[str] = None, **kwargs: Any) -> HtmlString:
+    """<turbo-frame id=...> wrapper; id is required"""
+    ...
+
+def turbo_stream(
+    action: TurboStreamAction,
+    *children: Any,
+    target: Optional[str] = None,
+    targets: Optional[str] = None,
+    **kwargs: Any,
+) -> HtmlString:
+    """<turbo-stream> with content wrapped in <template>
+
+    Raises ValueError for unknown actions, when target/targets is missing or
+    both are given (except for refresh), or when remove/refresh get content.
+    """
+    ...
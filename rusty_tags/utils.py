@@ -1,6 +1,8 @@
-from .core import CustomTag, Html, Head, Title, Body, HtmlString, Script
+from .core import CustomTag, Html, Head, Title, Body, HtmlString, Script, Link, Img, Picture, Source, Progress, Meter, get_config
+import html
+import re
 from functools import partial, wraps
-from typing import Optional, Callable, TypeVar, ParamSpec
+from typing import Optional, Callable, TypeVar, ParamSpec, Iterable, Union
 from asyncio import iscoroutinefunction
 
 P = ParamSpec("P")
@@ -152,4 +154,229 @@ def unless(condition, element):
     from .core import Fragment
     if not condition:
         return element
-    return Fragment()
\ No newline at end of file
+    return Fragment()
+
+def testid(value: str) -> dict:
+    """Test hook attribute: `Button("Save", **testid("save"))` -> data-testid="save"
+
+    Removed from the output when `configure(strip_testids=True)` is set, so the
+    hooks can stay in the source while production HTML ships without them.
+    """
+    return {"data-testid": value}
+
+
+def script_src(url: str, integrity: Optional[str] = None, crossorigin: Optional[str] = "anonymous", **kwargs) -> HtmlString:
+    """External script tag with optional subresource integrity
+
+    Args:
+        url: Script URL
+        integrity: SRI hash (e.g. "sha384-..."), omitted when None
+        crossorigin: CORS mode, omitted when None
+        **kwargs: Extra attributes (defer, type="module", ...)
+
+    Returns:
+        <script src=... integrity=... crossorigin=...></script>
+    """
+    attrs = {"src": url}
+    if integrity is not None:
+        attrs["integrity"] = integrity
+    if crossorigin is not None:
+        attrs["crossorigin"] = crossorigin
+    return Script(**attrs, **kwargs)
+
+
+def stylesheet(url: str, integrity: Optional[str] = None, crossorigin: Optional[str] = "anonymous", **kwargs) -> HtmlString:
+    """Stylesheet link with optional subresource integrity
+
+    Args:
+        url: Stylesheet URL
+        integrity: SRI hash (e.g. "sha384-..."), omitted when None
+        crossorigin: CORS mode, omitted when None
+        **kwargs: Extra attributes (media, ...)
+
+    Returns:
+        <link rel="stylesheet" href=... integrity=... crossorigin=...>
+    """
+    attrs = {"rel": "stylesheet", "href": url}
+    if integrity is not None:
+        attrs["integrity"] = integrity
+    if crossorigin is not None:
+        attrs["crossorigin"] = crossorigin
+    return Link(**attrs, **kwargs)
+
+
+_SRCSET_DESCRIPTOR = re.compile(r"^\d+(\.\d+)?[wx]$")
+
+
+def _srcset_url(url: str) -> str:
+    """Percent-encode characters that would split a srcset candidate"""
+    return url.replace(" ", "%20").replace(",", "%2C")
+
+
+def _format_srcset(srcset: Iterable[Union[str, tuple[str, str]]]) -> str:
+    """Join srcset candidates - (url, descriptor) pairs or bare URLs"""
+    candidates = []
+    for candidate in srcset:
+        if isinstance(candidate, str):
+            candidates.append(_srcset_url(candidate))
+            continue
+        url, descriptor = candidate
+        descriptor = str(descriptor).strip()
+        if not _SRCSET_DESCRIPTOR.match(descriptor):
+            raise ValueError(f"Invalid srcset descriptor {descriptor!r} (expected e.g. '320w' or '2x')")
+        candidates.append(f"{_srcset_url(url)} {descriptor}")
+    return ", ".join(candidates)
+
+
+def responsive_img(
+    src: str,
+    srcset: Iterable[Union[str, tuple[str, str]]] = (),
+    sizes: Optional[str] = None,
+    alt: str = "",
+    **kwargs,
+) -> HtmlString:
+    """Image with a properly formatted srcset/sizes pair
+
+    Args:
+        src: Fallback image URL
+        srcset: Candidates as (url, descriptor) pairs - descriptors like "320w"
+            or "2x" - or bare URLs
+        sizes: Media conditions for width descriptors, e.g. "(max-width: 600px) 100vw, 50vw"
+        alt: Alternative text (always emitted, empty for decorative images)
+        **kwargs: Extra attributes (loading="lazy", width, height, ...)
+
+    Returns:
+        <img src=... srcset="a.jpg 320w, b.jpg 640w" sizes=... alt=...>
+
+    Commas and spaces in candidate URLs are percent-encoded so they can't split
+    the list; src, srcset, sizes and alt are HTML-escaped.
+    """
+    attrs = {"src": html.escape(src)}
+    candidates = _format_srcset(srcset)
+    if candidates:
+        attrs["srcset"] = html.escape(candidates)
+    if sizes is not None:
+        attrs["sizes"] = html.escape(sizes)
+    attrs["alt"] = html.escape(alt)
+    return Img(**attrs, **kwargs)
+
+
+def picture(
+    sources: Iterable[tuple],
+    img: Union[str, HtmlString],
+    alt: str = "",
+    **kwargs,
+) -> HtmlString:
+    """Art-directed <picture>: one <source> per media query, then the fallback <img>
+
+    Args:
+        sources: (media, srcset) or (media, srcset, type) tuples, in priority
+            order - the browser uses the first matching one. srcset is a ready
+            string or a list of candidates as accepted by responsive_img;
+            media may be None for type-only switching.
+        img: Fallback image - an Img(...)/responsive_img(...) element, or a URL
+        alt: Alternative text when img is a URL
+        **kwargs: Attributes for the <picture> element
+
+    Returns:
+        <picture><source media=... srcset=...>...<img src=... alt=...></picture>
+    """
+    children = []
+    for source in sources:
+        if len(source) not in (2, 3):
+            raise ValueError("picture() sources must be (media, srcset) or (media, srcset, type) tuples")
+        media, srcset = source[0], source[1]
+        attrs = {}
+        if media is not None:
+            attrs["media"] = html.escape(media)
+        srcset = srcset if isinstance(srcset, str) else _format_srcset(srcset)
+        attrs["srcset"] = html.escape(srcset)
+        if len(source) == 3 and source[2] is not None:
+            attrs["type"] = html.escape(source[2])
+        children.append(Source(**attrs))
+
+    # The fallback <img> must come last - sources after it are ignored
+    fallback = img if isinstance(img, HtmlString) else Img(src=html.escape(img), alt=html.escape(alt))
+    children.append(fallback)
+    return Picture(*children, **kwargs)
+
+
+def _format_number(value: float) -> str:
+    """Shortest attribute form: 50 rather than 50.0, 0.25 as is"""
+    if isinstance(value, float) and value.is_integer():
+        return str(int(value))
+    return str(value)
+
+
+def _check_number(name: str, value) -> float:
+    if isinstance(value, bool) or not isinstance(value, (int, float)):
+        raise TypeError(f"{name} must be a number, got {type(value).__name__}")
+    if value != value or value in (float("inf"), float("-inf")):
+        raise ValueError(f"{name} must be finite, got {value!r}")
+    return value
+
+
+def _clamp(tag: str, name: str, value, low, high):
+    """Clamp value into [low, high]; strict mode raises instead"""
+    value = _check_number(name, value)
+    if low <= value <= high:
+        return value
+    if get_config()["strict"]:
+        raise ValueError(f"<{tag}> {name}={value!r} is outside [{_format_number(low)}, {_format_number(high)}] (strict mode)")
+    return min(max(value, low), high)
+
+
+def progress_bar(value=None, max=1, *children, **kwargs) -> HtmlString:
+    """<progress> with value clamped to [0, max]
+
+    Args:
+        value: Completed amount, or None for an indeterminate bar (no value attribute)
+        max: Total amount, must be positive (default 1, as in HTML)
+        *children: Fallback content for browsers without <progress>
+        **kwargs: Extra attributes
+
+    Out-of-range values are clamped; with configure(strict=True) they raise ValueError.
+    """
+    max = _check_number("max", max)
+    if max <= 0:
+        raise ValueError(f"<progress> max must be positive, got {max!r}")
+    attrs = {}
+    if value is not None:
+        attrs["value"] = _format_number(_clamp("progress", "value", value, 0, max))
+    attrs["max"] = _format_number(max)
+    return Progress(*children, **attrs, **kwargs)
+
+
+def meter_bar(value, min=0, max=1, *children, low=None, high=None, optimum=None, **kwargs) -> HtmlString:
+    """<meter> with value, low, high and optimum clamped to [min, max]
+
+    Args:
+        value: Current measurement
+        min, max: Range bounds (default 0 and 1, as in HTML); min must not exceed max
+        *children: Fallback content for browsers without <meter>
+        low, high: Optional thresholds of the "low" and "high" regions; high
+            may not be below low
+        optimum: Optional optimal value
+        **kwargs: Extra attributes
+
+    Out-of-range values are clamped the way browsers interpret them; with
+    configure(strict=True) they raise ValueError.
+    """
+    min = _check_number("min", min)
+    max = _check_number("max", max)
+    if min > max:
+        raise ValueError(f"<meter> min ({_format_number(min)}) must not exceed max ({_format_number(max)})")
+
+    attrs = {"value": _format_number(_clamp("meter", "value", value, min, max))}
+    if min != 0:
+        attrs["min"] = _format_number(min)
+    attrs["max"] = _format_number(max)
+    if low is not None:
+        low = _clamp("meter", "low", low, min, max)
+        attrs["low"] = _format_number(low)
+    if high is not None:
+        high = _clamp("meter", "high", high, min if low is None else low, max)
+        attrs["high"] = _format_number(high)
+    if optimum is not None:
+        attrs["optimum"] = _format_number(_clamp("meter", "optimum", optimum, min, max))
+    return Meter(*children, **attrs, **kwargs)
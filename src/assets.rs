@@ -0,0 +1,291 @@
+// =============================================================================
+// ASSETS - Cache-busting query strings on local asset URLs, resource hints
+// =============================================================================
+//
+//   cache_bust(page, version="3f2a9c")
+//   <script src="/static/app.js">  ->  <script src="/static/app.js?v=3f2a9c">
+//
+// A pass over rendered markup, like render_email: start tags are rebuilt only
+// when a URL changes, everything else is copied verbatim. Rewritten:
+//   <script src>, <img src>, and <link href> when rel names a fetched asset
+//   (stylesheet, icon, preload, manifest, ...) - canonical, alternate and
+//   prev/next links are page URLs and keep their exact form
+// and only local URLs - root-relative ("/static/app.js") and relative
+// ("img/logo.png"). URLs with a scheme (https:, data:, ...), protocol-relative
+// URLs ("//cdn...") and fragment-only or empty values are left alone, as is a
+// URL whose query already has the parameter. `versions` maps a URL path (the
+// part before "?" and "#", as written) to its own version and wins over the
+// global `version`; paths in neither are not touched.
+//
+//   resource_hints(preconnect=["https://fonts.gstatic.com"],
+//                  preload=[{"href": "/app.css", "as": "style"}])
+//   -> <link rel="preconnect" href="https://fonts.gstatic.com"></link>
+//      <link rel="preload" href="/app.css" as="style"></link>
+//
+// Head hints in the order browsers act on them: connections first
+// (preconnect, then the cheaper dns-prefetch), then fetches of this page's
+// critical resources (preload). Font preloads are always fetched in CORS mode,
+// so they get `crossorigin` when the entry does not set it - without it the
+// browser downloads the font twice.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+use std::collections::HashMap;
+
+use crate::tokenizer::{parse_attributes_as_written, start_tag_name, tokenize, write_start_tag, Token};
+use crate::{build_html_tag_optimized, process_child_object, process_plain_kwargs, AttrMap, HtmlString};
+
+/// Destinations a `<link rel="preload">` may name in `as`
+const PRELOAD_DESTINATIONS: &[&str] = &[
+    "audio", "document", "embed", "fetch", "font", "image", "object", "script", "style", "track", "video", "worker",
+];
+
+/// `<link rel>` values whose `href` is an asset the page fetches
+const ASSET_LINK_RELS: &[&str] = &[
+    "apple-touch-icon", "apple-touch-icon-precomposed", "icon", "manifest", "mask-icon", "modulepreload",
+    "prefetch", "preload", "stylesheet",
+];
+
+/// The URL-valued attribute that gets a version on each element
+fn asset_attribute(tag: &str) -> Option<&'static str> {
+    match tag {
+        "script" | "img" => Some("src"),
+        "link" => Some("href"),
+        _ => None,
+    }
+}
+
+/// Whether a `<link>`'s rel list names an asset (e.g. "shortcut icon")
+fn is_asset_link(attrs: &[(String, Option<String>)]) -> bool {
+    attrs
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("rel"))
+        .and_then(|(_, value)| value.as_deref())
+        .is_some_and(|rel| {
+            rel.split_ascii_whitespace()
+                .any(|token| ASSET_LINK_RELS.iter().any(|asset| token.eq_ignore_ascii_case(asset)))
+        })
+}
+
+/// Whether the URL points at this site: no scheme, not protocol-relative
+fn is_local_url(url: &str) -> bool {
+    if url.is_empty() || url.starts_with('#') || url.starts_with("//") {
+        return false;
+    }
+    // A ':' before any '/', '?' or '#' starts a scheme (https:, data:, mailto:)
+    let head_end = url.find(['/', '?', '#']).unwrap_or(url.len());
+    !url[..head_end].contains(':')
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters
+fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// The URL with `param=version` added to its query, or None to leave it as is
+fn versioned_url(url: &str, param: &str, version: Option<&str>, versions: &HashMap<String, String>) -> Option<String> {
+    if !is_local_url(url) {
+        return None;
+    }
+    let (before_fragment, fragment) = url.split_at(url.find('#').unwrap_or(url.len()));
+    let (path, query) = match before_fragment.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (before_fragment, None),
+    };
+    if query.is_some_and(|query| query.split('&').any(|pair| pair.split('=').next() == Some(param))) {
+        return None;
+    }
+    let version = versions.get(path).map(String::as_str).or(version)?;
+
+    let separator = match query {
+        Some("") => "",
+        Some(_) => "&",
+        None => "?",
+    };
+    Some(format!("{}{}{}={}{}", before_fragment, separator, param, encode_query_value(version), fragment))
+}
+
+/// Rebuild an asset start tag with its URL versioned, or None when it is unchanged
+fn bust_start_tag(
+    raw: &str,
+    tag_name: &str,
+    attr: &str,
+    self_closing: bool,
+    param: &str,
+    version: Option<&str>,
+    versions: &HashMap<String, String>,
+) -> Option<String> {
+    let mut attrs = parse_attributes_as_written(raw);
+    if tag_name == "link" && !is_asset_link(&attrs) {
+        return None;
+    }
+    let url = attrs
+        .iter_mut()
+        .find(|(name, _)| name.eq_ignore_ascii_case(attr))
+        .and_then(|(_, value)| value.as_mut())?;
+    *url = versioned_url(url, param, version, versions)?;
+    Some(write_start_tag(start_tag_name(raw), &attrs, self_closing))
+}
+
+/// Append a cache-busting query parameter to local script, stylesheet and image URLs
+///
+/// `version` applies to every local asset; `versions` maps URL paths to their
+/// own version (e.g. content hashes from a build manifest) and takes
+/// precedence. Values are percent-encoded. Raises ValueError when neither is
+/// given or `param` is not a plain query parameter name.
+///
+/// Example:
+///   cache_bust(Script(src="/app.js"), versions={"/app.js": "3f2a9c"})
+///   Output: <script src="/app.js?v=3f2a9c"></script>
+#[pyfunction]
+#[pyo3(signature = (element, version = None, versions = None, param = "v"))]
+fn cache_bust(
+    element: Py<PyAny>,
+    version: Option<String>,
+    versions: Option<HashMap<String, String>>,
+    param: &str,
+    py: Python,
+) -> PyResult<HtmlString> {
+    if version.is_none() && versions.is_none() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "cache_bust() needs version= or versions=",
+        ));
+    }
+    if param.is_empty() || !param.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.')) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid cache_bust() param: {:?}",
+            param
+        )));
+    }
+    let versions = versions.unwrap_or_default();
+    let content = process_child_object(&element, py)?;
+
+    let mut out = String::with_capacity(content.len() + 64);
+    for token in tokenize(&content) {
+        if let Token::StartTag { name, raw, self_closing } = &token {
+            if let Some(attr) = asset_attribute(name) {
+                if let Some(tag) = bust_start_tag(raw, name, attr, *self_closing, param, version.as_deref(), &versions) {
+                    out.push_str(&tag);
+                    continue;
+                }
+            }
+        }
+        out.push_str(token.raw());
+    }
+    Ok(HtmlString::new(out))
+}
+
+/// Render one `<link rel=...>` hint from a URL or a dict of attributes with "href"
+fn hint_link(rel: &str, entry: &Bound<'_, PyAny>, py: Python) -> PyResult<String> {
+    let mut attrs = AttrMap::default();
+    attrs.insert("rel".to_string(), rel.to_string().into());
+
+    if let Ok(href) = entry.downcast::<PyString>() {
+        if rel == "preload" {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "preload hint for {:?} needs a dict with \"href\" and \"as\"",
+                href.to_str()?
+            )));
+        }
+        if href.is_empty()? {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "{} hint needs a non-empty \"href\"",
+                rel
+            )));
+        }
+        attrs.insert("href".to_string(), href.to_str()?.to_string().into());
+    } else if let Ok(entry) = entry.downcast::<PyDict>() {
+        match entry.get_item("href")? {
+            Some(href) if href.downcast::<PyString>().is_ok_and(|href| !href.is_empty().unwrap_or(true)) => {}
+            _ => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "{} hint needs a non-empty \"href\"",
+                    rel
+                )))
+            }
+        }
+        if entry.contains("rel")? {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "{} hint cannot set \"rel\"",
+                rel
+            )));
+        }
+        // Keep href first, then the entry's own order
+        let ordered = PyDict::new(py);
+        ordered.set_item("href", entry.get_item("href")?)?;
+        ordered.update(entry.as_mapping())?;
+        if rel == "preload" {
+            let destination = entry.get_item("as")?.map(|value| value.extract::<String>()).transpose()?;
+            match destination.as_deref() {
+                Some(destination) if PRELOAD_DESTINATIONS.contains(&destination) => {
+                    if destination == "font" && !entry.contains("crossorigin")? {
+                        ordered.set_item("crossorigin", true)?;
+                    }
+                }
+                Some(destination) => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Invalid preload \"as\": {:?} (expected one of: {})",
+                        destination,
+                        PRELOAD_DESTINATIONS.join(", ")
+                    )))
+                }
+                None => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "preload hint needs an \"as\" destination",
+                    ))
+                }
+            }
+        }
+        process_plain_kwargs(&ordered, &mut attrs, py)?;
+    } else {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "{} hint must be a URL string or a dict, got {}",
+            rel,
+            entry.get_type().name()?
+        )));
+    }
+    Ok(build_html_tag_optimized("link", Vec::new(), attrs, py)?.content)
+}
+
+/// Render preconnect, dns-prefetch and preload `<link>` hints in the recommended order
+///
+/// `preconnect` and `dns_prefetch` take URL strings or dicts of attributes
+/// with "href" (e.g. {"href": url, "crossorigin": True}). `preload` takes
+/// dicts with "href" and an "as" destination (script, style, font, image,
+/// ...); font preloads get `crossorigin` unless the dict sets it. Raises
+/// ValueError for an unknown "as" or a missing "href".
+///
+/// Example:
+///   resource_hints(preconnect=["https://cdn.example.com"], preload=[{"href": "/app.js", "as": "script"}])
+///   Output: <link rel="preconnect" href="https://cdn.example.com"></link><link rel="preload" href="/app.js" as="script"></link>
+#[pyfunction]
+#[pyo3(signature = (preconnect = None, dns_prefetch = None, preload = None))]
+fn resource_hints(
+    preconnect: Option<Vec<Bound<'_, PyAny>>>,
+    dns_prefetch: Option<Vec<Bound<'_, PyAny>>>,
+    preload: Option<Vec<Bound<'_, PyAny>>>,
+    py: Python,
+) -> PyResult<HtmlString> {
+    let mut out = String::new();
+    for (rel, entries) in [("preconnect", preconnect), ("dns-prefetch", dns_prefetch), ("preload", preload)] {
+        for entry in entries.iter().flatten() {
+            out.push_str(&hint_link(rel, entry, py)?);
+        }
+    }
+    Ok(HtmlString::new(out))
+}
+
+/// Register the asset URL helpers on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(cache_bust, m)?)?;
+    m.add_function(wrap_pyfunction!(resource_hints, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,149 @@
+// =============================================================================
+// TEMPLATE BLOCKS - Jinja-style block/extends over rendered layouts
+// =============================================================================
+//
+// Rendering is eager, so a block is a pair of comment markers around its
+// default content:  <!--block:NAME-->default<!--/block:NAME-->
+// `extends` walks a rendered base layout and substitutes overridden blocks,
+// keeping the default content of the rest. Markers are dropped from the result
+// unless `keep_blocks=True`, which allows multi-level inheritance.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use ahash::AHashMap as HashMap;
+
+use crate::{process_children_optimized, process_child_object, HtmlString};
+
+const BLOCK_OPEN: &str = "<!--block:";
+const BLOCK_CLOSE: &str = "<!--/block:";
+const MARKER_END: &str = "-->";
+
+fn validate_block_name(name: &str) -> PyResult<()> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Invalid block name: {:?} (use letters, digits, '_' or '-')", name)
+        ));
+    }
+    Ok(())
+}
+
+#[inline]
+fn push_open_marker(out: &mut String, name: &str) {
+    out.push_str(BLOCK_OPEN);
+    out.push_str(name);
+    out.push_str(MARKER_END);
+}
+
+#[inline]
+fn push_close_marker(out: &mut String, name: &str) {
+    out.push_str(BLOCK_CLOSE);
+    out.push_str(name);
+    out.push_str(MARKER_END);
+}
+
+/// Copy `content` into `out`, replacing overridden blocks
+fn merge_blocks(
+    content: &str,
+    overrides: &HashMap<String, String>,
+    keep_blocks: bool,
+    out: &mut String,
+) -> PyResult<()> {
+    let mut rest = content;
+    while let Some(start) = rest.find(BLOCK_OPEN) {
+        out.push_str(&rest[..start]);
+
+        let after_open = &rest[start + BLOCK_OPEN.len()..];
+        let Some(name_end) = after_open.find(MARKER_END) else {
+            // Not a well-formed marker - leave the text alone
+            out.push_str(&rest[start..]);
+            return Ok(());
+        };
+        let name = &after_open[..name_end];
+        let body = &after_open[name_end + MARKER_END.len()..];
+
+        let mut close_marker = String::with_capacity(BLOCK_CLOSE.len() + name.len() + MARKER_END.len());
+        push_close_marker(&mut close_marker, name);
+        let Some(body_end) = body.find(&close_marker) else {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Unclosed block '{}' in base layout", name)
+            ));
+        };
+        let default_content = &body[..body_end];
+
+        if keep_blocks {
+            push_open_marker(out, name);
+        }
+        match overrides.get(name) {
+            Some(replacement) => out.push_str(replacement),
+            None => merge_blocks(default_content, overrides, keep_blocks, out)?,
+        }
+        if keep_blocks {
+            out.push_str(&close_marker);
+        }
+
+        rest = &body[body_end + close_marker.len()..];
+    }
+    out.push_str(rest);
+    Ok(())
+}
+
+/// Define a named, overridable block with default content
+///
+/// Example:
+///   base = Html(Body(block("content", P("Nothing here yet"))))
+///   extends(base, {"content": Main("Hello")})
+#[pyfunction]
+#[pyo3(signature = (name, *default))]
+fn block(name: &str, default: Vec<PyObject>, py: Python) -> PyResult<HtmlString> {
+    validate_block_name(name)?;
+    let default_content = process_children_optimized(&default, py)?;
+
+    let mut result = String::with_capacity(default_content.len() + name.len() * 2 + 32);
+    push_open_marker(&mut result, name);
+    result.push_str(&default_content);
+    push_close_marker(&mut result, name);
+    Ok(HtmlString::new(result))
+}
+
+/// Render a base layout with some of its blocks overridden
+///
+/// Blocks that aren't overridden keep their default content. Naming a block the
+/// base doesn't define raises ValueError. Pass `keep_blocks=True` to keep the block
+/// markers so the result can itself be extended.
+#[pyfunction]
+#[pyo3(signature = (base, blocks = None, keep_blocks = false))]
+fn extends(base: PyObject, blocks: Option<&Bound<'_, PyDict>>, keep_blocks: bool, py: Python) -> PyResult<HtmlString> {
+    let base_content = process_child_object(&base, py)?;
+
+    let mut overrides = HashMap::new();
+    if let Some(blocks) = blocks {
+        for (key, value) in blocks.iter() {
+            let name = key.extract::<String>()?;
+            validate_block_name(&name)?;
+
+            // Catch typos early - every override must name a block in the base
+            let mut open_marker = String::with_capacity(BLOCK_OPEN.len() + name.len() + MARKER_END.len());
+            push_open_marker(&mut open_marker, &name);
+            if !base_content.contains(&open_marker) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Base layout has no block named '{}'", name)
+                ));
+            }
+
+            overrides.insert(name, process_child_object(&value.unbind(), py)?);
+        }
+    }
+
+    let mut result = String::with_capacity(base_content.len());
+    merge_blocks(&base_content, &overrides, keep_blocks, &mut result)?;
+    Ok(HtmlString::new(result))
+}
+
+/// Register the block/extends functions on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(block, m)?)?;
+    m.add_function(wrap_pyfunction!(extends, m)?)?;
+    Ok(())
+}
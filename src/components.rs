@@ -0,0 +1,181 @@
+// =============================================================================
+// COMPONENT KIT - Optional layout helpers built on the core tag primitives
+// =============================================================================
+//
+// These helpers emit opinionated, semantic scaffolding (cards, grids, navbars)
+// with configurable class names, plus ARIA live regions for content updated
+// after page load. They are exposed through the optional
+// `rusty_tags.components` Python module and are not part of the core exports.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::{
+    build_html_tag_optimized, build_html_tag_with_datastar, process_kwargs, AttrMap, DatastarProcessor, HtmlString,
+};
+
+/// Build `<tag ...>children</tag>` through the regular tag path, so components
+/// get the same attribute handling, config options and hooks as `Div(...)`.
+/// An empty class is left out.
+fn element(tag: &str, attrs: &[(&str, &str)], children: Vec<Py<PyAny>>, py: Python) -> PyResult<HtmlString> {
+    let mut attr_map = AttrMap::default();
+    for (name, value) in attrs {
+        if *name == "class" && value.is_empty() {
+            continue;
+        }
+        attr_map.insert(name.to_string(), value.to_string().into());
+    }
+    build_html_tag_optimized(tag, children, attr_map, py)
+}
+
+/// An already-built element as a child of the next one
+fn child(html: HtmlString, py: Python) -> PyResult<Py<PyAny>> {
+    Ok(Py::new(py, html)?.into_any())
+}
+
+/// Card component - an `<article>` with header, body and optional footer
+///
+/// Sub-element classes are derived from the base class:
+///   card("Title", "Body", cls="card")
+///   Output: <article class="card"><header class="card-header">Title</header>
+///           <div class="card-body">Body</div></article>
+#[pyfunction]
+#[pyo3(signature = (title, body, footer = None, cls = "card"))]
+fn card(title: Py<PyAny>, body: Py<PyAny>, footer: Option<Py<PyAny>>, cls: &str, py: Python) -> PyResult<HtmlString> {
+    let mut parts = vec![
+        child(element("header", &[("class", &format!("{}-header", cls))], vec![title], py)?, py)?,
+        child(element("div", &[("class", &format!("{}-body", cls))], vec![body], py)?, py)?,
+    ];
+    if let Some(footer) = footer {
+        parts.push(child(element("footer", &[("class", &format!("{}-footer", cls))], vec![footer], py)?, py)?);
+    }
+    element("article", &[("class", cls)], parts, py)
+}
+
+/// Grid component - wraps each item in a cell of an N-column CSS grid
+///
+/// Example:
+///   grid([A, B, C], cols=3)
+///   Output: <div class="grid" style="display:grid;grid-template-columns:repeat(3,minmax(0,1fr))">
+///           <div class="grid-item">A</div>...</div>
+#[pyfunction]
+#[pyo3(signature = (items, cols = 3, cls = "grid", gap = None))]
+fn grid(items: Vec<Py<PyAny>>, cols: usize, cls: &str, gap: Option<&str>, py: Python) -> PyResult<HtmlString> {
+    if cols == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "grid() requires cols >= 1"
+        ));
+    }
+
+    let mut style = format!("display:grid;grid-template-columns:repeat({},minmax(0,1fr))", cols);
+    if let Some(gap) = gap {
+        style.push_str(";gap:");
+        style.push_str(gap);
+    }
+
+    let item_cls = format!("{}-item", cls);
+    let mut cells = Vec::with_capacity(items.len());
+    for item in items {
+        cells.push(child(element("div", &[("class", &item_cls)], vec![item], py)?, py)?);
+    }
+    element("div", &[("class", cls), ("style", &style)], cells, py)
+}
+
+/// Navbar component - a `<nav>` with a brand link and a list of (label, href) links
+///
+/// Example:
+///   navbar("Acme", [("Home", "/"), ("Docs", "/docs")])
+///   Output: <nav class="navbar"><a class="navbar-brand" href="/">Acme</a>
+///           <ul class="navbar-links"><li><a href="/">Home</a></li>...</ul></nav>
+#[pyfunction]
+#[pyo3(signature = (brand, links = Vec::new(), brand_href = "/", cls = "navbar"))]
+fn navbar(brand: Py<PyAny>, links: Vec<(Py<PyAny>, String)>, brand_href: &str, cls: &str, py: Python) -> PyResult<HtmlString> {
+    let brand_cls = format!("{}-brand", cls);
+    let mut parts = vec![child(element("a", &[("class", &brand_cls), ("href", brand_href)], vec![brand], py)?, py)?];
+
+    if !links.is_empty() {
+        let mut items = Vec::with_capacity(links.len());
+        for (label, href) in links {
+            let link = child(element("a", &[("href", &href)], vec![label], py)?, py)?;
+            items.push(child(element("li", &[], vec![link], py)?, py)?);
+        }
+        parts.push(child(element("ul", &[("class", &format!("{}-links", cls))], items, py)?, py)?);
+    }
+
+    element("nav", &[("class", cls)], parts, py)
+}
+
+/// `<div>` with the given ARIA attributes first, then the caller's attributes
+/// (which may override them)
+fn aria_div(
+    aria: &[(&str, &str)],
+    children: Vec<Py<PyAny>>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+    py: Python,
+) -> PyResult<HtmlString> {
+    let mut attrs = AttrMap::default();
+    let mut datastar_attrs = AttrMap::default();
+    for (name, value) in aria {
+        attrs.insert(name.to_string(), value.to_string().into());
+    }
+    if let Some(kwargs) = kwargs {
+        process_kwargs(kwargs, &DatastarProcessor::new(), &mut attrs, &mut datastar_attrs, py)?;
+    }
+    build_html_tag_with_datastar("div", children, &attrs, &datastar_attrs, py)
+}
+
+/// Live region - a `<div>` whose later content changes screen readers announce
+///
+/// `politeness` is "polite" (role="status", announced when the user is idle),
+/// "assertive" (role="alert", interrupts) or "off" (no role, not announced
+/// until focused). The region must already be on the page before its content
+/// changes, so render it empty and swap the content in later.
+///
+/// Example:
+///   live_region("3 results", id="search-status")
+///   Output: <div role="status" aria-live="polite" id="search-status">3 results</div>
+#[pyfunction]
+#[pyo3(signature = (*children, politeness = "polite", **attrs))]
+fn live_region(
+    children: Vec<Py<PyAny>>,
+    politeness: &str,
+    attrs: Option<&Bound<'_, PyDict>>,
+    py: Python,
+) -> PyResult<HtmlString> {
+    let aria: &[(&str, &str)] = match politeness {
+        "polite" => &[("role", "status"), ("aria-live", "polite")],
+        "assertive" => &[("role", "alert"), ("aria-live", "assertive")],
+        "off" => &[("aria-live", "off")],
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown politeness: {:?} (expected \"off\", \"polite\" or \"assertive\")",
+                politeness
+            )))
+        }
+    };
+    aria_div(aria, children, attrs, py)
+}
+
+/// Alert - a `<div role="alert">` for important, time-sensitive messages
+///
+/// role="alert" is an assertive live region: screen readers interrupt to
+/// announce it, including when it is inserted already filled in.
+///
+/// Example:
+///   alert("Payment failed", cls="error")
+///   Output: <div role="alert" class="error">Payment failed</div>
+#[pyfunction]
+#[pyo3(signature = (*children, **attrs))]
+fn alert(children: Vec<Py<PyAny>>, attrs: Option<&Bound<'_, PyDict>>, py: Python) -> PyResult<HtmlString> {
+    aria_div(&[("role", "alert")], children, attrs, py)
+}
+
+/// Register the component kit functions on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(card, m)?)?;
+    m.add_function(wrap_pyfunction!(grid, m)?)?;
+    m.add_function(wrap_pyfunction!(navbar, m)?)?;
+    m.add_function(wrap_pyfunction!(live_region, m)?)?;
+    m.add_function(wrap_pyfunction!(alert, m)?)?;
+    Ok(())
+}
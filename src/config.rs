@@ -0,0 +1,377 @@
+// =============================================================================
+// RENDER CONFIGURATION - Process-wide rendering options
+// =============================================================================
+//
+// Options are read on the hot path, so each thread keeps its own copy of the
+// config and only goes back to the shared lock after `configure`/`reset_config`
+// bump the generation - a read is one atomic load. Hot paths take one snapshot
+// per element and read every option from it. New options need a field, a
+// default, a `configure` arm and a `get_config` entry.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use once_cell::sync::Lazy;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+use crate::profile::{set_tag_callback, tag_callback};
+
+/// Output dialect - decides which serialization shortcuts are legal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum RenderMode {
+    /// HTML syntax: only void elements may omit their end tag
+    #[default]
+    Html,
+    /// XML/polyglot syntax: any element may be written as `<tag/>`
+    Xml,
+    /// Strict XHTML: every childless element is written as `<tag />`
+    Xhtml,
+}
+
+impl RenderMode {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value {
+            "html" => Ok(RenderMode::Html),
+            "xml" => Ok(RenderMode::Xml),
+            "xhtml" => Ok(RenderMode::Xhtml),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Unknown render mode: {:?} (expected \"html\", \"xml\" or \"xhtml\")", value)
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            RenderMode::Html => "html",
+            RenderMode::Xml => "xml",
+            RenderMode::Xhtml => "xhtml",
+        }
+    }
+}
+
+/// What a `None` child renders as
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum NoneChildren {
+    /// Leave it out - `Div("a", None)` is `<div>a</div>`, and a None item
+    /// has no entry in `render_each`/`render_fragment_list` results
+    #[default]
+    Skip,
+    /// Raise TypeError, to catch values that unexpectedly became None
+    Error,
+    /// Render it as an empty string - same markup inside elements, but a None
+    /// item keeps its `""` entry in per-item results so indexes line up
+    Empty,
+}
+
+impl NoneChildren {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value {
+            "skip" => Ok(NoneChildren::Skip),
+            "error" => Ok(NoneChildren::Error),
+            "empty" => Ok(NoneChildren::Empty),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Unknown none_children value: {:?} (expected \"skip\", \"error\" or \"empty\")", value)
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            NoneChildren::Skip => "skip",
+            NoneChildren::Error => "error",
+            NoneChildren::Empty => "empty",
+        }
+    }
+}
+
+/// What happens to attribute names containing characters HTML does not allow
+/// in a name (whitespace, quotes, `<`, `>`, `/`, `=`, control characters)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum AttrNamePolicy {
+    /// Emit the name as given
+    #[default]
+    Passthrough,
+    /// Remove the offending characters; a name left empty drops the attribute
+    Strip,
+    /// Raise ValueError when the element is built
+    Error,
+}
+
+impl AttrNamePolicy {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value {
+            "passthrough" => Ok(AttrNamePolicy::Passthrough),
+            "strip" => Ok(AttrNamePolicy::Strip),
+            "error" => Ok(AttrNamePolicy::Error),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Unknown attr_name_policy: {:?} (expected \"passthrough\", \"strip\" or \"error\")", value)
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            AttrNamePolicy::Passthrough => "passthrough",
+            AttrNamePolicy::Strip => "strip",
+            AttrNamePolicy::Error => "error",
+        }
+    }
+}
+
+/// What happens when a child's `__html__`, `_repr_html_` or `render` raises
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum ChildMethodErrors {
+    /// Re-raise the exception, noting which child method failed
+    #[default]
+    Propagate,
+    /// Ignore it and try the next method, down to `str()`
+    Fallthrough,
+}
+
+impl ChildMethodErrors {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value {
+            "propagate" => Ok(ChildMethodErrors::Propagate),
+            "fallthrough" => Ok(ChildMethodErrors::Fallthrough),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Unknown child_method_errors value: {:?} (expected \"propagate\" or \"fallthrough\")", value)
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            ChildMethodErrors::Propagate => "propagate",
+            ChildMethodErrors::Fallthrough => "fallthrough",
+        }
+    }
+}
+
+/// Rendering options shared by every tag function
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RenderConfig {
+    /// Output dialect, "html" (default), "xml" or "xhtml"
+    pub mode: RenderMode,
+
+    /// In XML mode, emit every childless element as `<tag/>` (ignored in HTML mode)
+    pub self_close_empty: bool,
+
+    /// Emit childless SVG leaf elements (circle, rect, path, ...) as `<circle .../>`
+    pub svg_self_closing: bool,
+
+    /// Emit DeprecationWarning for nonstandard attribute aliases (htmlClass, klass, fr, htmlFor)
+    pub warn_deprecated_aliases: bool,
+
+    /// Validate markup rules (Td/Th spans, CustomTag names, ...) and raise ValueError
+    pub strict: bool,
+
+    /// Record per-tag render timings, read back with render_profile()
+    pub profile: bool,
+
+    /// End documents from `Html(...)` and `render_to_file` with a newline
+    pub trailing_newline: bool,
+
+    /// Require `on*` attributes to go through unsafe_attr() and raise ValueError otherwise
+    pub guard_event_handlers: bool,
+
+    /// Allocate buffers directly and skip the name caches, for render-once processes
+    pub no_pool: bool,
+
+    /// How `None` children are handled: "skip" (default), "error" or "empty"
+    pub none_children: NoneChildren,
+
+    /// Drop `data-testid` attributes from the output, for production builds
+    pub strip_testids: bool,
+
+    /// In strict mode, longest attribute value in bytes before ValueError (None = no limit)
+    pub max_attr_value_len: Option<usize>,
+
+    /// Levels of nested lists/tuples/iterators flattened into the parent (None = all, 0 = none)
+    pub flatten_depth: Option<usize>,
+
+    /// Emit attribute names in lowercase, keeping SVG camelCase names (viewBox, ...)
+    pub lowercase_attrs: bool,
+
+    /// Largest output in bytes any element or Fragment may reach before ValueError (None = no limit)
+    pub max_document_size: Option<usize>,
+
+    /// Handling of illegal characters in attribute names: "passthrough" (default), "strip" or "error"
+    pub attr_name_policy: AttrNamePolicy,
+
+    /// Set by `html_auto_structure=False`: `Html(...)` defaults to a plain wrapper, as with
+    /// `auto_structure=False` (stored inverted so the all-false default means "on")
+    pub html_plain_wrapper: bool,
+
+    /// Set by `html_doctype=False`: `Html(...)` documents start at `<html>` without
+    /// `<!doctype html>` (stored inverted like `html_plain_wrapper`)
+    pub html_omit_doctype: bool,
+
+    /// A raising `__html__`/`_repr_html_`/`render` on a child: "propagate" (default) or "fallthrough"
+    pub child_method_errors: ChildMethodErrors,
+}
+
+static RENDER_CONFIG: Lazy<RwLock<RenderConfig>> = Lazy::new(|| RwLock::new(RenderConfig::default()));
+
+/// Bumped after every change to RENDER_CONFIG
+static CONFIG_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    // This thread's copy of the config and the generation it was taken at
+    static CONFIG_SNAPSHOT: RefCell<(u64, Rc<RenderConfig>)> =
+        RefCell::new((u64::MAX, Rc::new(RenderConfig::default())));
+}
+
+/// Snapshot of the current configuration
+#[inline(always)]
+pub(crate) fn render_config() -> Rc<RenderConfig> {
+    let generation = CONFIG_GENERATION.load(Ordering::Acquire);
+    CONFIG_SNAPSHOT.with(|snapshot| {
+        let mut snapshot = snapshot.borrow_mut();
+        if snapshot.0 != generation {
+            // A poisoned lock still holds a valid config - options are plain values
+            let config = RENDER_CONFIG.read().unwrap_or_else(|e| e.into_inner()).clone();
+            *snapshot = (generation, Rc::new(config));
+        }
+        Rc::clone(&snapshot.1)
+    })
+}
+
+/// Replace the configuration and invalidate every thread's snapshot
+fn store_config(config: RenderConfig) {
+    *RENDER_CONFIG.write().unwrap_or_else(|e| e.into_inner()) = config;
+    CONFIG_GENERATION.fetch_add(1, Ordering::Release);
+}
+
+/// A positive size limit, or None to disable it
+fn parse_limit(name: &str, value: &Bound<'_, PyAny>) -> PyResult<Option<usize>> {
+    if value.is_none() {
+        return Ok(None);
+    }
+    match value.extract::<usize>() {
+        Ok(limit) if limit > 0 => Ok(Some(limit)),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("{} must be a positive integer or None", name)
+        )),
+    }
+}
+
+/// A depth of zero or more, or None for unlimited
+fn parse_depth(name: &str, value: &Bound<'_, PyAny>) -> PyResult<Option<usize>> {
+    if value.is_none() {
+        return Ok(None);
+    }
+    value.extract::<usize>().map(Some).map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+        format!("{} must be a non-negative integer or None", name)
+    ))
+}
+
+/// A callable, or None to remove the callback
+fn parse_callback(name: &str, value: &Bound<'_, PyAny>) -> PyResult<Option<Py<PyAny>>> {
+    if value.is_none() {
+        return Ok(None);
+    }
+    if !value.is_callable() {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            format!("{} must be callable or None", name)
+        ));
+    }
+    Ok(Some(value.clone().unbind()))
+}
+
+/// Update rendering options, e.g. `configure(svg_self_closing=True)`
+///
+/// Unknown option names raise `ValueError` so typos don't go unnoticed.
+#[pyfunction]
+#[pyo3(signature = (**options))]
+pub(crate) fn configure(options: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+    let Some(options) = options else {
+        return Ok(());
+    };
+
+    // Validate everything against a copy so a bad option leaves the config untouched
+    let mut updated = RenderConfig::clone(&render_config());
+    // Python callables live outside RenderConfig, which holds plain values only
+    let mut tag_callback_update = None;
+    for (key, value) in options.iter() {
+        let key_str = key.extract::<String>()?;
+        match key_str.as_str() {
+            "mode" => updated.mode = RenderMode::parse(&value.extract::<String>()?)?,
+            "self_close_empty" => updated.self_close_empty = value.extract()?,
+            "svg_self_closing" => updated.svg_self_closing = value.extract()?,
+            "warn_deprecated_aliases" => updated.warn_deprecated_aliases = value.extract()?,
+            "strict" => updated.strict = value.extract()?,
+            "profile" => updated.profile = value.extract()?,
+            "trailing_newline" => updated.trailing_newline = value.extract()?,
+            "guard_event_handlers" => updated.guard_event_handlers = value.extract()?,
+            "no_pool" => updated.no_pool = value.extract()?,
+            "none_children" => updated.none_children = NoneChildren::parse(&value.extract::<String>()?)?,
+            "strip_testids" => updated.strip_testids = value.extract()?,
+            "max_attr_value_len" => updated.max_attr_value_len = parse_limit("max_attr_value_len", &value)?,
+            "flatten_depth" => updated.flatten_depth = parse_depth("flatten_depth", &value)?,
+            "lowercase_attrs" => updated.lowercase_attrs = value.extract()?,
+            "max_document_size" => updated.max_document_size = parse_limit("max_document_size", &value)?,
+            "attr_name_policy" => updated.attr_name_policy = AttrNamePolicy::parse(&value.extract::<String>()?)?,
+            "html_auto_structure" => updated.html_plain_wrapper = !value.extract::<bool>()?,
+            "html_doctype" => updated.html_omit_doctype = !value.extract::<bool>()?,
+            "child_method_errors" => updated.child_method_errors = ChildMethodErrors::parse(&value.extract::<String>()?)?,
+            "on_tag_rendered" => tag_callback_update = Some(parse_callback("on_tag_rendered", &value)?),
+            _ => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Unknown render option: {}", key_str)
+                ));
+            }
+        }
+    }
+
+    if let Some(callback) = tag_callback_update {
+        set_tag_callback(callback);
+    }
+    store_config(updated);
+    Ok(())
+}
+
+/// Return the current rendering options as a dict
+#[pyfunction]
+pub(crate) fn get_config(py: Python) -> PyResult<Py<PyDict>> {
+    let config = render_config();
+    let dict = PyDict::new(py);
+    dict.set_item("mode", config.mode.as_str())?;
+    dict.set_item("self_close_empty", config.self_close_empty)?;
+    dict.set_item("svg_self_closing", config.svg_self_closing)?;
+    dict.set_item("warn_deprecated_aliases", config.warn_deprecated_aliases)?;
+    dict.set_item("strict", config.strict)?;
+    dict.set_item("profile", config.profile)?;
+    dict.set_item("trailing_newline", config.trailing_newline)?;
+    dict.set_item("guard_event_handlers", config.guard_event_handlers)?;
+    dict.set_item("no_pool", config.no_pool)?;
+    dict.set_item("none_children", config.none_children.as_str())?;
+    dict.set_item("strip_testids", config.strip_testids)?;
+    dict.set_item("max_attr_value_len", config.max_attr_value_len)?;
+    dict.set_item("flatten_depth", config.flatten_depth)?;
+    dict.set_item("lowercase_attrs", config.lowercase_attrs)?;
+    dict.set_item("max_document_size", config.max_document_size)?;
+    dict.set_item("attr_name_policy", config.attr_name_policy.as_str())?;
+    dict.set_item("html_auto_structure", !config.html_plain_wrapper)?;
+    dict.set_item("html_doctype", !config.html_omit_doctype)?;
+    dict.set_item("child_method_errors", config.child_method_errors.as_str())?;
+    dict.set_item("on_tag_rendered", tag_callback(py))?;
+    Ok(dict.unbind())
+}
+
+/// Restore every rendering option to its default
+#[pyfunction]
+pub(crate) fn reset_config() {
+    set_tag_callback(None);
+    store_config(RenderConfig::default());
+}
+
+/// Register the configuration functions on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(configure, m)?)?;
+    m.add_function(wrap_pyfunction!(get_config, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_config, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,230 @@
+// =============================================================================
+// REQUEST CONTEXT - Request-scoped values (CSRF token, CSP nonce, ...)
+// =============================================================================
+//
+// Values live in a Python `contextvars.ContextVar`, so they follow the current
+// thread *and* the current asyncio task - concurrent requests served by one
+// event loop never see each other's tokens. Each `request_context()` block
+// installs a fresh dict layered over the enclosing one and restores the outer
+// dict on exit.
+
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyBool, PyDict};
+
+use crate::{build_html_tag_optimized, AttrMap, AttrValue, HtmlString};
+
+static REQUEST_CONTEXT: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+/// The module-wide ContextVar holding the active values dict
+fn context_var(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    REQUEST_CONTEXT
+        .get_or_try_init(py, || -> PyResult<Py<PyAny>> {
+            let contextvars = py.import("contextvars")?;
+            Ok(contextvars.getattr("ContextVar")?.call1(("rusty_tags_request_context",))?.unbind())
+        })
+        .map(|var| var.bind(py))
+}
+
+/// Active values dict, or None outside any request_context block
+fn current_values(py: Python<'_>) -> PyResult<Option<Bound<'_, PyDict>>> {
+    let values = context_var(py)?.call_method1("get", (py.None(),))?;
+    if values.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(values.downcast_into::<PyDict>()?))
+}
+
+/// Look up a single request-scoped value
+pub(crate) fn context_value<'py>(py: Python<'py>, key: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+    match current_values(py)? {
+        Some(values) => Ok(values.get_item(key)?.filter(|value| !value.is_none())),
+        None => Ok(None),
+    }
+}
+
+/// Context manager returned by `request_context()`
+#[pyclass(module = "rusty_tags.core")]
+pub struct RequestContext {
+    values: Py<PyDict>,
+    token: Option<PyObject>,
+}
+
+#[pymethods]
+impl RequestContext {
+    fn __enter__(&mut self, py: Python) -> PyResult<Py<PyDict>> {
+        if self.token.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "request_context is already active"
+            ));
+        }
+
+        // Layer over the enclosing context so nested blocks only override what they set
+        let merged = match current_values(py)? {
+            Some(outer) => outer.copy()?,
+            None => PyDict::new(py),
+        };
+        merged.update(self.values.bind(py).as_mapping())?;
+
+        let token = context_var(py)?.call_method1("set", (&merged,))?;
+        self.token = Some(token.unbind());
+        Ok(merged.unbind())
+    }
+
+    #[pyo3(signature = (_exc_type = None, _exc_value = None, _traceback = None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<PyObject>,
+        _exc_value: Option<PyObject>,
+        _traceback: Option<PyObject>,
+        py: Python,
+    ) -> PyResult<bool> {
+        if let Some(token) = self.token.take() {
+            context_var(py)?.call_method1("reset", (token,))?;
+        }
+        Ok(false)
+    }
+}
+
+/// Make request-scoped values available to helpers for the duration of a `with` block
+///
+/// Example:
+///   with request_context(csrf=token, nonce=nonce):
+///       Form(Input(name="email"), method="post")  # hidden csrf_token input first
+#[pyfunction]
+#[pyo3(signature = (**values))]
+fn request_context(values: Option<&Bound<'_, PyDict>>, py: Python) -> PyResult<RequestContext> {
+    let values = match values {
+        Some(values) => values.copy()?,
+        None => PyDict::new(py),
+    };
+    Ok(RequestContext { values: values.unbind(), token: None })
+}
+
+/// Copy of the active request-scoped values (empty outside request_context)
+#[pyfunction]
+fn get_request_context(py: Python) -> PyResult<Py<PyDict>> {
+    match current_values(py)? {
+        Some(values) => Ok(values.copy()?.unbind()),
+        None => Ok(PyDict::new(py).unbind()),
+    }
+}
+
+/// Name of the hidden CSRF field: request_context(csrf_field=...) or "csrf_token"
+fn csrf_field_name(py: Python) -> PyResult<String> {
+    match context_value(py, "csrf_field")? {
+        Some(field) => Ok(field.str()?.to_string()),
+        None => Ok("csrf_token".to_string()),
+    }
+}
+
+fn hidden_csrf_input(name: String, token: &Bound<'_, PyAny>, py: Python) -> PyResult<HtmlString> {
+    let mut attrs = AttrMap::default();
+    attrs.insert("type".to_string(), "hidden".to_string().into());
+    attrs.insert("name".to_string(), name.into());
+    attrs.insert("value".to_string(), token.str()?.to_string().into());
+    build_html_tag_optimized("input", Vec::new(), attrs, py)
+}
+
+/// Hidden input carrying the request's CSRF token
+///
+/// Example:
+///   with request_context(csrf="abc123"):
+///       csrf_input()
+///   Output: <input type="hidden" name="csrf_token" value="abc123"></input>
+#[pyfunction]
+#[pyo3(signature = (name = None))]
+fn csrf_input(name: Option<String>, py: Python) -> PyResult<HtmlString> {
+    let Some(token) = context_value(py, "csrf")? else {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "csrf_input() needs an active request_context(csrf=...)"
+        ));
+    };
+    let name = match name {
+        Some(name) => name,
+        None => csrf_field_name(py)?,
+    };
+    hidden_csrf_input(name, &token, py)
+}
+
+/// Remove Form's `csrf=` option from the kwargs before they become attributes
+pub(crate) fn take_csrf_option<'py>(kwargs: Option<&Bound<'py, PyDict>>) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let Some(kwargs) = kwargs else {
+        return Ok(None);
+    };
+    let option = kwargs.get_item("csrf")?;
+    if option.is_some() {
+        kwargs.del_item("csrf")?;
+    }
+    Ok(option)
+}
+
+/// Prepend the hidden CSRF input to a form's children
+///
+/// `csrf="token"` always inserts that token, `csrf=True` requires one from
+/// request_context, and `csrf=False`/`None` opts out. Without the option, the
+/// request_context token is inserted into forms whose method is not GET or
+/// dialog - those submit through the URL, which would leak the token. A
+/// csrf_input() already passed as a direct child is not duplicated.
+pub(crate) fn prepend_form_csrf(
+    option: Option<Bound<'_, PyAny>>,
+    attrs: &AttrMap<AttrValue>,
+    children: &mut Vec<PyObject>,
+    py: Python,
+) -> PyResult<()> {
+    let token = match option {
+        Some(option) if option.is_none() => return Ok(()),
+        Some(option) if option.is_instance_of::<PyBool>() => {
+            if !option.is_truthy()? {
+                return Ok(());
+            }
+            context_value(py, "csrf")?.ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Form(csrf=True) needs an active request_context(csrf=...)"
+            ))?
+        }
+        Some(token) => token,
+        None => {
+            let method = attrs.get("method").map(|method| method.trim().to_ascii_lowercase());
+            match method.as_deref() {
+                None | Some("") | Some("get") | Some("dialog") => return Ok(()),
+                Some(_) => match context_value(py, "csrf")? {
+                    Some(token) => token,
+                    None => return Ok(()),
+                },
+            }
+        }
+    };
+
+    let input = hidden_csrf_input(csrf_field_name(py)?, &token, py)?;
+    let already_present = children.iter().any(|child| {
+        child
+            .extract::<PyRef<HtmlString>>(py)
+            .is_ok_and(|html| html.trusted && html.content == input.content)
+    });
+    if !already_present {
+        children.insert(0, Py::new(py, input)?.into_any());
+    }
+    Ok(())
+}
+
+/// The request's CSP nonce, or None outside request_context(nonce=...)
+///
+/// Example:
+///   Script(code, nonce=csp_nonce())
+#[pyfunction]
+fn csp_nonce(py: Python) -> PyResult<Option<String>> {
+    match context_value(py, "nonce")? {
+        Some(nonce) => Ok(Some(nonce.str()?.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Register the request context API on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<RequestContext>()?;
+    m.add_function(wrap_pyfunction!(request_context, m)?)?;
+    m.add_function(wrap_pyfunction!(get_request_context, m)?)?;
+    m.add_function(wrap_pyfunction!(csrf_input, m)?)?;
+    m.add_function(wrap_pyfunction!(csp_nonce, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,161 @@
+// =============================================================================
+// CSS - Serialize nested rule dicts into a stylesheet string
+// =============================================================================
+//
+//   css({".btn": {"color": "red", ":hover": {"color": "blue"}, ".icon": {"width": 16}}})
+//   -> .btn{color:red}.btn:hover{color:blue}.btn .icon{width:16}
+//
+// Nested keys combine with their parent selector: ":hover"/"::before" attach
+// directly, "&" is replaced by the parent, anything else becomes a descendant
+// selector. Comma lists expand to every combination. "@media ..." and other
+// at-rules wrap the rules nested inside them. Output is compact and safe to
+// place in a <style> element: "<" is written as the CSS escape "\3c ", and
+// braces or semicolons inside values are rejected.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Property name from a Python key: font_size -> font-size, custom properties kept
+pub(crate) fn property_name(key: &str) -> String {
+    if key.starts_with("--") {
+        key.to_string()
+    } else {
+        key.replace('_', "-")
+    }
+}
+
+/// Keep `</style>` from ever appearing in the output
+#[inline]
+fn escape_css(text: &str) -> String {
+    if text.contains('<') {
+        text.replace('<', "\\3c ")
+    } else {
+        text.to_string()
+    }
+}
+
+pub(crate) fn reject_structural(kind: &str, text: &str) -> PyResult<()> {
+    if text.contains(['{', '}', ';']) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("CSS {} may not contain '{{', '}}' or ';': {:?}", kind, text)
+        ));
+    }
+    Ok(())
+}
+
+/// Serialize a declaration value (str, int or float)
+pub(crate) fn declaration_value(value: &Bound<'_, PyAny>) -> PyResult<String> {
+    if let Ok(s) = value.extract::<String>() {
+        return Ok(s);
+    }
+    if value.is_instance_of::<pyo3::types::PyBool>() {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            "CSS values must be str, int or float, got bool"
+        ));
+    }
+    if let Ok(i) = value.extract::<i64>() {
+        let mut buffer = itoa::Buffer::new();
+        return Ok(buffer.format(i).to_string());
+    }
+    if let Ok(f) = value.extract::<f64>() {
+        let mut buffer = ryu::Buffer::new();
+        return Ok(buffer.format(f).trim_end_matches(".0").to_string());
+    }
+    Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+        "CSS values must be str, int or float, got {}",
+        value.get_type().name()?
+    )))
+}
+
+/// Combine parent selectors with a nested key
+fn combine_selectors(parents: &[String], key: &str) -> Vec<String> {
+    let parts: Vec<&str> = key.split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+    if parents.is_empty() {
+        return parts.iter().map(|part| part.replace('&', "")).collect();
+    }
+
+    let mut combined = Vec::with_capacity(parents.len() * parts.len());
+    for parent in parents {
+        for part in &parts {
+            combined.push(if part.contains('&') {
+                part.replace('&', parent)
+            } else if part.starts_with(':') {
+                format!("{}{}", parent, part)
+            } else {
+                format!("{} {}", parent, part)
+            });
+        }
+    }
+    combined
+}
+
+/// Emit the declarations of `rules` for `selectors`, then every nested block
+fn emit_rules(selectors: &[String], rules: &Bound<'_, PyDict>, out: &mut String) -> PyResult<()> {
+    let mut declarations = String::new();
+    let mut nested = Vec::new();
+
+    for (key, value) in rules.iter() {
+        let key = key.extract::<String>()?;
+        if let Ok(block) = value.downcast::<PyDict>() {
+            nested.push((key, block.clone()));
+            continue;
+        }
+        if value.is_none() {
+            continue;
+        }
+        if selectors.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Top-level CSS declaration {:?} needs a selector", key)
+            ));
+        }
+
+        let value = declaration_value(&value)?;
+        reject_structural("property", &key)?;
+        reject_structural("value", &value)?;
+        if !declarations.is_empty() {
+            declarations.push(';');
+        }
+        declarations.push_str(&escape_css(&property_name(&key)));
+        declarations.push(':');
+        declarations.push_str(&escape_css(&value));
+    }
+
+    if !declarations.is_empty() {
+        out.push_str(&escape_css(&selectors.join(",")));
+        out.push('{');
+        out.push_str(&declarations);
+        out.push('}');
+    }
+
+    for (key, block) in nested {
+        reject_structural("selector", &key)?;
+        if key.starts_with('@') {
+            // At-rules keep the current selectors for the rules inside them
+            out.push_str(&escape_css(&key));
+            out.push('{');
+            emit_rules(selectors, &block, out)?;
+            out.push('}');
+        } else {
+            emit_rules(&combine_selectors(selectors, &key), &block, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Serialize nested CSS rules into a stylesheet string for a Style element
+///
+/// Example:
+///   Style(css({".btn": {"color": "red", ":hover": {"color": "blue"}}}))
+///   Output: <style>.btn{color:red}.btn:hover{color:blue}</style>
+#[pyfunction]
+fn css(rules: &Bound<'_, PyDict>) -> PyResult<String> {
+    let mut out = String::new();
+    emit_rules(&[], rules, &mut out)?;
+    Ok(out)
+}
+
+/// Register the css function on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(css, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,208 @@
+// =============================================================================
+// DECORATE - Add or override attributes on an already rendered element
+// =============================================================================
+//
+//   with_attrs(Button("Save", cls="btn"), cls="primary", disabled=True)
+//   -> <button class="btn primary" disabled>Save</button>
+//
+// Elements are rendered eagerly, so a wrapper component that receives a child
+// can only decorate its markup: the root start tag is parsed, the new
+// attributes are merged in and the tag is rebuilt. Everything after the start
+// tag is copied verbatim. Merge rules:
+//   - class tokens are appended, skipping ones already present
+//   - any other attribute replaces the existing value, keeping its position
+//   - None / False removes the attribute
+// The markup must hold exactly one root element (leading doctype, comments and
+// whitespace are allowed); a Fragment with several roots raises ValueError
+// rather than guessing which one was meant.
+//
+// combine() applies the same class rule before rendering, layering attribute
+// dicts for a component (defaults, variant, caller overrides):
+//   Button("Save", **combine({"cls": "btn", "type": "button"}, {"cls": "btn-primary"}, attrs))
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict};
+
+use crate::elements::is_void_element;
+use crate::tokenizer::{parse_attributes_as_written, start_tag_name, tokenize, write_start_tag, Token};
+use crate::{
+    attrmap_optimized, build_attributes_with_datastar, process_child_object, process_kwargs, validate_attr_names,
+    AttrMap, DatastarProcessor, HtmlString,
+};
+
+/// Append the class names in `added` that `existing` does not already list
+fn append_classes(existing: &mut String, added: &str) {
+    let added: Vec<&str> = added
+        .split_whitespace()
+        .filter(|class| !existing.split_whitespace().any(|present| present == *class))
+        .collect();
+    if !added.is_empty() {
+        if !existing.trim().is_empty() {
+            existing.push(' ');
+        }
+        existing.push_str(&added.join(" "));
+    }
+}
+
+/// Byte range of the root element's start tag, or an error describing why
+/// the markup has no single root
+fn root_start_tag(markup: &str) -> PyResult<(usize, usize)> {
+    let mut root = None;
+    let mut depth = 0usize;
+    let mut offset = 0;
+    for token in tokenize(markup) {
+        let start = offset;
+        offset += token.raw().len();
+        match &token {
+            Token::StartTag { name, self_closing, .. } => {
+                if depth == 0 {
+                    if root.is_some() {
+                        return Err(single_root_error("several top-level elements"));
+                    }
+                    root = Some((start, offset));
+                }
+                if !self_closing && !is_void_element(name) {
+                    depth += 1;
+                }
+            }
+            Token::EndTag { name, .. } if !is_void_element(name) => depth = depth.saturating_sub(1),
+            Token::Text(text) if depth == 0 && !text.trim().is_empty() => {
+                return Err(single_root_error("top-level text"));
+            }
+            _ => {}
+        }
+    }
+    root.ok_or_else(|| single_root_error("no element"))
+}
+
+fn single_root_error(found: &str) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+        "with_attrs() needs markup with a single root element, found {}",
+        found
+    ))
+}
+
+/// Attributes one keyword renders to, using the same mapping as the tag functions
+fn rendered_attributes(key: &Bound<'_, PyAny>, value: &Bound<'_, PyAny>, py: Python) -> PyResult<Vec<(String, Option<String>)>> {
+    let single = PyDict::new(py);
+    single.set_item(key, value)?;
+    let mut attrs = AttrMap::default();
+    let mut datastar_attrs = AttrMap::default();
+    process_kwargs(&single, &DatastarProcessor::new(), &mut attrs, &mut datastar_attrs, py)?;
+    validate_attr_names(&attrs, &datastar_attrs)?;
+    Ok(parse_attributes_as_written(&format!("<x{}>", build_attributes_with_datastar(&attrs, &datastar_attrs))))
+}
+
+/// Whether a keyword value asks for the attribute to be removed
+fn is_removal(value: &Bound<'_, PyAny>) -> bool {
+    value.is_none() || value.downcast::<PyBool>().is_ok_and(|flag| !flag.is_true())
+}
+
+/// Merge `kwargs` into parsed start tag attributes following the module rules
+fn merge_attributes(
+    attrs: &mut Vec<(String, Option<String>)>,
+    kwargs: &Bound<'_, PyDict>,
+    py: Python,
+) -> PyResult<()> {
+    for (key, value) in kwargs.iter() {
+        if is_removal(&value) {
+            // Render as True only to learn the emitted name(s)
+            for (name, _) in rendered_attributes(&key, PyBool::new(py, true).as_any(), py)? {
+                attrs.retain(|(existing, _)| !existing.eq_ignore_ascii_case(&name));
+            }
+            continue;
+        }
+
+        for (name, value) in rendered_attributes(&key, &value, py)? {
+            match attrs.iter_mut().find(|(existing, _)| existing.eq_ignore_ascii_case(&name)) {
+                Some((_, Some(existing))) if name.eq_ignore_ascii_case("class") => {
+                    append_classes(existing, value.as_deref().unwrap_or(""));
+                }
+                Some(entry) => entry.1 = value,
+                None => attrs.push((name, value)),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Add or override attributes on the root element of rendered markup
+///
+/// Keywords use the same aliases as the tag functions (cls, for_, ds_*, ...).
+/// Class names are appended to the existing class list, other attributes are
+/// replaced, and None/False removes an attribute. Raises ValueError unless the
+/// markup has exactly one root element.
+///
+/// Example:
+///   with_attrs(Div("x", cls="card"), cls="active", id="main")
+///   Output: <div class="card active" id="main">x</div>
+#[pyfunction]
+#[pyo3(signature = (element, **attrs))]
+fn with_attrs(element: PyObject, attrs: Option<&Bound<'_, PyDict>>, py: Python) -> PyResult<HtmlString> {
+    let markup = process_child_object(&element, py)?;
+    let (start, end) = root_start_tag(&markup)?;
+    let Some(kwargs) = attrs.filter(|kwargs| !kwargs.is_empty()) else {
+        return Ok(HtmlString::new(markup));
+    };
+
+    let raw = &markup[start..end];
+    let self_closing = raw.ends_with("/>");
+    let mut parsed = parse_attributes_as_written(raw);
+    merge_attributes(&mut parsed, kwargs, py)?;
+
+    let mut result = String::with_capacity(markup.len() + 64);
+    result.push_str(&markup[..start]);
+    result.push_str(&write_start_tag(start_tag_name(raw), &parsed, self_closing));
+    result.push_str(&markup[end..]);
+    Ok(HtmlString::new(result))
+}
+
+/// Merge attribute dicts left to right into one dict for `**` expansion
+///
+/// Keys are matched by the attribute they render as, so "cls" and "class_"
+/// are the same key; the first spelling is kept. Class strings concatenate
+/// (duplicates skipped), every other key takes the last value - including
+/// None/False, which then drop the attribute at render time.
+///
+/// Example:
+///   combine({"cls": "btn", "type": "button"}, {"cls": "btn-lg", "type": "submit"})
+///   Output: {"cls": "btn btn-lg", "type": "submit"}
+#[pyfunction]
+#[pyo3(signature = (base, *overrides))]
+fn combine<'py>(
+    base: &Bound<'py, PyDict>,
+    overrides: Vec<Bound<'py, PyDict>>,
+    py: Python<'py>,
+) -> PyResult<Bound<'py, PyDict>> {
+    // (emitted name, key as first written, value)
+    let mut merged: Vec<(String, Bound<'py, PyAny>, Bound<'py, PyAny>)> = Vec::new();
+    for layer in std::iter::once(base).chain(overrides.iter()) {
+        for (key, value) in layer.iter() {
+            let name = attrmap_optimized(&key.extract::<String>()?);
+            let Some(entry) = merged.iter_mut().find(|(existing, _, _)| *existing == name) else {
+                merged.push((name, key, value));
+                continue;
+            };
+            entry.2 = match (name.as_str(), entry.2.extract::<String>(), value.extract::<String>()) {
+                ("class", Ok(mut classes), Ok(added)) => {
+                    append_classes(&mut classes, &added);
+                    classes.into_pyobject(py)?.into_any()
+                }
+                _ => value,
+            };
+        }
+    }
+
+    let result = PyDict::new(py);
+    for (_, key, value) in merged {
+        result.set_item(key, value)?;
+    }
+    Ok(result)
+}
+
+/// Register the attribute decorator and combine() on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(with_attrs, m)?)?;
+    m.add_function(wrap_pyfunction!(combine, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,177 @@
+// =============================================================================
+// DIFF - Structural patches between two renders
+// =============================================================================
+//
+// Both renders are tokenized and folded into a light node tree (elements, text,
+// comments). Siblings are compared by position: identical markup is skipped,
+// an element whose start tag is unchanged is descended into, anything else is
+// replaced wholesale. Extra siblings become inserts or removes at the end.
+//
+// Patch format - a list of dicts applied in order:
+//   {"op": "replace", "path": [1, 0], "html": "<li>new</li>"}
+//   {"op": "insert",  "path": [3],    "html": "<li>added</li>"}
+//   {"op": "remove",  "path": [2]}
+// `path` indexes child nodes (text and comments included) from the top level
+// of the fragment down. Removes run from the last index back, so each path is
+// valid at the moment its op is applied.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::elements::is_void_element;
+use crate::process_child_object;
+use crate::tokenizer::{tokenize, Token};
+
+/// Span of one node in its source markup
+struct Node {
+    start: usize,
+    end: usize,
+    /// End of the start tag (equals `start` for non-elements)
+    open_end: usize,
+    name: Option<String>,
+    children: Vec<Node>,
+}
+
+#[inline]
+fn attach(stack: &mut [Node], roots: &mut Vec<Node>, node: Node) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(node),
+        None => roots.push(node),
+    }
+}
+
+/// Fold the token stream of `markup` into sibling trees
+fn build_tree(markup: &str) -> Vec<Node> {
+    let mut roots = Vec::new();
+    let mut stack: Vec<Node> = Vec::new();
+    let mut offset = 0;
+
+    for token in tokenize(markup) {
+        let start = offset;
+        offset += token.raw().len();
+
+        match token {
+            Token::StartTag { name, self_closing, .. } if !self_closing && !is_void_element(&name) => {
+                stack.push(Node { start, end: offset, open_end: offset, name: Some(name), children: Vec::new() });
+            }
+            Token::StartTag { name, .. } => {
+                let node = Node { start, end: offset, open_end: offset, name: Some(name), children: Vec::new() };
+                attach(&mut stack, &mut roots, node);
+            }
+            Token::EndTag { name, .. } if stack.iter().any(|open| open.name.as_deref() == Some(&name)) => {
+                // Close implicitly-ended children, then the matching element
+                while let Some(mut open) = stack.pop() {
+                    let matched = open.name.as_deref() == Some(&name);
+                    open.end = if matched { offset } else { start };
+                    attach(&mut stack, &mut roots, open);
+                    if matched {
+                        break;
+                    }
+                }
+            }
+            _ => {
+                // Text, comments, doctypes and stray end tags are leaves
+                let node = Node { start, end: offset, open_end: start, name: None, children: Vec::new() };
+                attach(&mut stack, &mut roots, node);
+            }
+        }
+    }
+
+    // Unclosed elements run to the end of the input
+    while let Some(mut open) = stack.pop() {
+        open.end = markup.len();
+        attach(&mut stack, &mut roots, open);
+    }
+    roots
+}
+
+/// A single patch operation
+enum Patch {
+    Replace(Vec<usize>, String),
+    Insert(Vec<usize>, String),
+    Remove(Vec<usize>),
+}
+
+fn diff_nodes(old_src: &str, old: &[Node], new_src: &str, new: &[Node], path: &mut Vec<usize>, patches: &mut Vec<Patch>) {
+    let common = old.len().min(new.len());
+
+    for (index, (o, n)) in old.iter().zip(new).enumerate() {
+        let old_html = &old_src[o.start..o.end];
+        let new_html = &new_src[n.start..n.end];
+        if old_html == new_html {
+            continue;
+        }
+
+        path.push(index);
+        let same_element = o.name.is_some()
+            && o.name == n.name
+            && o.open_end > o.start
+            && old_src[o.start..o.open_end] == new_src[n.start..n.open_end];
+        if same_element {
+            diff_nodes(old_src, &o.children, new_src, &n.children, path, patches);
+        } else {
+            patches.push(Patch::Replace(path.clone(), new_html.to_string()));
+        }
+        path.pop();
+    }
+
+    for (index, n) in new.iter().enumerate().skip(common) {
+        path.push(index);
+        patches.push(Patch::Insert(path.clone(), new_src[n.start..n.end].to_string()));
+        path.pop();
+    }
+
+    for index in (common..old.len()).rev() {
+        path.push(index);
+        patches.push(Patch::Remove(path.clone()));
+        path.pop();
+    }
+}
+
+/// Compute DOM patch operations turning one render into another
+///
+/// Example:
+///   diff(Ul(Li("a"), Li("b")), Ul(Li("a"), Li("c"), Li("d")))
+///   Output: [{"op": "replace", "path": [0, 1, 0], "html": "c"},
+///            {"op": "insert", "path": [0, 2], "html": "<li>d</li>"}]
+#[pyfunction]
+fn diff(old: PyObject, new: PyObject, py: Python) -> PyResult<Vec<Py<PyDict>>> {
+    let old_src = process_child_object(&old, py)?;
+    let new_src = process_child_object(&new, py)?;
+    if old_src == new_src {
+        return Ok(Vec::new());
+    }
+
+    let mut patches = Vec::new();
+    diff_nodes(&old_src, &build_tree(&old_src), &new_src, &build_tree(&new_src), &mut Vec::new(), &mut patches);
+
+    patches
+        .into_iter()
+        .map(|patch| {
+            let dict = PyDict::new(py);
+            match patch {
+                Patch::Replace(path, html) => {
+                    dict.set_item("op", "replace")?;
+                    dict.set_item("path", path)?;
+                    dict.set_item("html", html)?;
+                }
+                Patch::Insert(path, html) => {
+                    dict.set_item("op", "insert")?;
+                    dict.set_item("path", path)?;
+                    dict.set_item("html", html)?;
+                }
+                Patch::Remove(path) => {
+                    dict.set_item("op", "remove")?;
+                    dict.set_item("path", path)?;
+                }
+            }
+            Ok(dict.unbind())
+        })
+        .collect()
+}
+
+/// Register the diff function on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(diff, m)?)?;
+    Ok(())
+}
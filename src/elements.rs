@@ -0,0 +1,223 @@
+// =============================================================================
+// ELEMENT CATEGORIES - Known, void and (escapable) raw-text element sets, boolean attributes
+// =============================================================================
+//
+// Built-in sets follow the HTML spec; tooling can register extra names (e.g.
+// custom elements that behave like <br> or <script>). The tokenizer and the
+// formatting passes consult these sets, and the same answers are exposed to
+// Python so external serializers can make identical decisions. Boolean
+// attributes are consulted at render time: "" or the attribute's own name
+// (disabled="disabled") is written in the bare form, like True.
+
+use ahash::AHashSet;
+use once_cell::sync::Lazy;
+use phf::phf_set;
+use pyo3::prelude::*;
+use std::sync::RwLock;
+
+/// Every standard HTML and SVG element name (SVG names lowercased)
+static KNOWN_TAGS: phf::Set<&'static str> = phf_set! {
+    // HTML
+    "a", "abbr", "address", "area", "article", "aside", "audio", "b", "base",
+    "bdi", "bdo", "blockquote", "body", "br", "button", "canvas", "caption",
+    "cite", "code", "col", "colgroup", "data", "datalist", "dd", "del",
+    "details", "dfn", "dialog", "div", "dl", "dt", "em", "embed", "fieldset",
+    "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5",
+    "h6", "head", "header", "hgroup", "hr", "html", "i", "iframe", "img",
+    "input", "ins", "kbd", "label", "legend", "li", "link", "main", "map",
+    "mark", "math", "menu", "meta", "meter", "nav", "noscript", "object", "ol",
+    "optgroup", "option", "output", "p", "param", "picture", "pre", "progress",
+    "q", "rp", "rt", "ruby", "s", "samp", "script", "search", "section",
+    "select", "slot", "small", "source", "span", "strong", "style", "sub",
+    "summary", "sup", "table", "tbody", "td", "template", "textarea", "tfoot",
+    "th", "thead", "time", "title", "tr", "track", "u", "ul", "var", "video",
+    "wbr",
+
+    // SVG
+    "svg", "animate", "animatemotion", "animatetransform", "circle", "clippath",
+    "defs", "desc", "ellipse", "feblend", "fecolormatrix", "fecomponenttransfer",
+    "fecomposite", "feconvolvematrix", "fediffuselighting", "fedisplacementmap",
+    "fedistantlight", "fedropshadow", "feflood", "fefunca", "fefuncb", "fefuncg",
+    "fefuncr", "fegaussianblur", "feimage", "femerge", "femergenode",
+    "femorphology", "feoffset", "fepointlight", "fespecularlighting",
+    "fespotlight", "fetile", "feturbulence", "filter", "foreignobject", "g",
+    "image", "line", "lineargradient", "marker", "mask", "metadata", "mpath",
+    "path", "pattern", "polygon", "polyline", "radialgradient", "rect", "set",
+    "stop", "switch", "symbol", "text", "textpath", "tspan", "use", "view",
+};
+
+/// Elements that never have content or a closing tag
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input",
+    "link", "meta", "source", "track", "wbr",
+];
+
+/// Raw-text elements: content is never parsed as markup and never escaped
+const RAW_TEXT_TAGS: &[&str] = &["script", "style"];
+
+/// Escapable raw-text elements: content is not parsed as markup, but character
+/// references are decoded, so the renderer escapes text children
+const ESCAPABLE_RAW_TEXT_TAGS: &[&str] = &["textarea", "title"];
+
+/// Attributes whose presence alone means "on" (popover and hidden="until-found"
+/// take keyword values, but hidden="" and hidden="hidden" are still bare)
+const BOOLEAN_ATTRS: &[&str] = &[
+    "allowfullscreen", "alpha", "async", "autofocus", "autoplay", "checked",
+    "controls", "default", "defer", "disabled", "disablepictureinpicture",
+    "disableremoteplayback", "formnovalidate", "hidden", "inert", "ismap",
+    "itemscope", "loop", "multiple", "muted", "nomodule", "novalidate", "open",
+    "playsinline", "readonly", "required", "reversed", "selected",
+    "shadowrootclonable", "shadowrootdelegatesfocus", "shadowrootserializable",
+];
+
+static USER_TAGS: Lazy<RwLock<AHashSet<String>>> = Lazy::new(|| RwLock::new(AHashSet::new()));
+static USER_VOID_TAGS: Lazy<RwLock<AHashSet<String>>> = Lazy::new(|| RwLock::new(AHashSet::new()));
+static USER_RAW_TEXT_TAGS: Lazy<RwLock<AHashSet<String>>> = Lazy::new(|| RwLock::new(AHashSet::new()));
+static USER_BOOLEAN_ATTRS: Lazy<RwLock<AHashSet<String>>> = Lazy::new(|| RwLock::new(AHashSet::new()));
+
+#[inline]
+fn is_registered(set: &RwLock<AHashSet<String>>, name: &str) -> bool {
+    let set = set.read().unwrap_or_else(|e| e.into_inner());
+    !set.is_empty() && set.contains(name)
+}
+
+/// Whether `name` (lowercase) is a standard element, a custom element (contains
+/// a hyphen, per the web-components spec) or registered with `register_tag`
+#[inline]
+pub(crate) fn is_known_element(name: &str) -> bool {
+    KNOWN_TAGS.contains(name) || name.contains('-') || is_registered(&USER_TAGS, name)
+}
+
+/// Whether `name` (lowercase) is a void element, built-in or registered
+#[inline]
+pub(crate) fn is_void_element(name: &str) -> bool {
+    VOID_TAGS.contains(&name) || is_registered(&USER_VOID_TAGS, name)
+}
+
+/// Whether `name` (lowercase) is a raw-text element, built-in or registered
+#[inline]
+pub(crate) fn is_raw_text_element(name: &str) -> bool {
+    RAW_TEXT_TAGS.contains(&name) || is_registered(&USER_RAW_TEXT_TAGS, name)
+}
+
+/// Whether `name` (lowercase) is an escapable raw-text element (textarea, title)
+#[inline]
+pub(crate) fn is_escapable_raw_text_element(name: &str) -> bool {
+    ESCAPABLE_RAW_TEXT_TAGS.contains(&name)
+}
+
+/// Whether `name` (lowercase) is a boolean attribute, built-in or registered
+#[inline]
+pub(crate) fn is_boolean_attribute(name: &str) -> bool {
+    BOOLEAN_ATTRS.contains(&name) || is_registered(&USER_BOOLEAN_ATTRS, name)
+}
+
+/// Lowercase and validate a tag name supplied from Python
+fn normalize_element_name(name: &str) -> PyResult<String> {
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Invalid tag name: {:?}", name)
+        ));
+    }
+    Ok(name.to_ascii_lowercase())
+}
+
+/// Check whether a tag name passes strict-mode validation (`is_known_tag("divv")` -> False)
+#[pyfunction]
+fn is_known_tag(name: &str) -> bool {
+    is_known_element(&name.to_ascii_lowercase())
+}
+
+/// Check whether a tag is a void element (`is_void_tag("br")` -> True)
+#[pyfunction]
+fn is_void_tag(name: &str) -> bool {
+    is_void_element(&name.to_ascii_lowercase())
+}
+
+/// Check whether a tag's content is raw text (`is_raw_text_tag("script")` -> True)
+#[pyfunction]
+fn is_raw_text_tag(name: &str) -> bool {
+    is_raw_text_element(&name.to_ascii_lowercase())
+}
+
+/// Check whether a tag's content is escapable raw text (`is_escapable_raw_text_tag("textarea")` -> True)
+#[pyfunction]
+fn is_escapable_raw_text_tag(name: &str) -> bool {
+    is_escapable_raw_text_element(&name.to_ascii_lowercase())
+}
+
+/// Accept an additional tag name in strict mode
+#[pyfunction]
+fn register_tag(name: &str) -> PyResult<()> {
+    let name = normalize_element_name(name)?;
+    USER_TAGS.write().unwrap_or_else(|e| e.into_inner()).insert(name);
+    Ok(())
+}
+
+/// Treat an additional tag as a void element
+#[pyfunction]
+fn register_void_tag(name: &str) -> PyResult<()> {
+    let name = normalize_element_name(name)?;
+    USER_VOID_TAGS.write().unwrap_or_else(|e| e.into_inner()).insert(name);
+    Ok(())
+}
+
+/// Treat an additional tag's content as raw text
+#[pyfunction]
+fn register_raw_text_tag(name: &str) -> PyResult<()> {
+    let name = normalize_element_name(name)?;
+    USER_RAW_TEXT_TAGS.write().unwrap_or_else(|e| e.into_inner()).insert(name);
+    Ok(())
+}
+
+/// Check whether an attribute is boolean (`is_boolean_attr("inert")` -> True)
+#[pyfunction]
+fn is_boolean_attr(name: &str) -> bool {
+    is_boolean_attribute(&name.to_ascii_lowercase())
+}
+
+/// Treat additional attributes as boolean, e.g. ones added by a newer HTML spec
+#[pyfunction]
+fn register_boolean_attrs(names: Vec<String>) -> PyResult<()> {
+    let mut normalized = Vec::with_capacity(names.len());
+    for name in names {
+        if name.is_empty() || name.chars().any(crate::is_illegal_attr_name_char) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Invalid attribute name: {:?}", name)
+            ));
+        }
+        normalized.push(name.to_ascii_lowercase());
+    }
+    USER_BOOLEAN_ATTRS.write().unwrap_or_else(|e| e.into_inner()).extend(normalized);
+    Ok(())
+}
+
+/// Remove names added with `register_boolean_attrs`; returns whether any was removed
+///
+/// Built-in boolean attributes (disabled, inert, ...) stay boolean.
+#[pyfunction]
+fn unregister_boolean_attrs(names: Vec<String>) -> bool {
+    let mut registered = USER_BOOLEAN_ATTRS.write().unwrap_or_else(|e| e.into_inner());
+    let mut removed = false;
+    for name in names {
+        removed |= registered.remove(&name.to_ascii_lowercase());
+    }
+    removed
+}
+
+/// Register the element category functions on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(is_known_tag, m)?)?;
+    m.add_function(wrap_pyfunction!(register_tag, m)?)?;
+    m.add_function(wrap_pyfunction!(is_void_tag, m)?)?;
+    m.add_function(wrap_pyfunction!(is_raw_text_tag, m)?)?;
+    m.add_function(wrap_pyfunction!(is_escapable_raw_text_tag, m)?)?;
+    m.add_function(wrap_pyfunction!(register_void_tag, m)?)?;
+    m.add_function(wrap_pyfunction!(register_raw_text_tag, m)?)?;
+    m.add_function(wrap_pyfunction!(is_boolean_attr, m)?)?;
+    m.add_function(wrap_pyfunction!(register_boolean_attrs, m)?)?;
+    m.add_function(wrap_pyfunction!(unregister_boolean_attrs, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,152 @@
+// =============================================================================
+// EMAIL - Post-process rendered markup for HTML email clients
+// =============================================================================
+//
+//   render_email(Table(Tr(Td(A("Confirm", href=url, cls="btn")))),
+//                styles={"btn": {"background": "#0a7", "color": "#fff"}})
+//   -> <table><tr><td><a href="..." class="btn" style="background:#0a7;color:#fff">Confirm</a></td></tr></table>
+//
+// Differences from normal output, and nothing else:
+//   1. classes listed in `styles` are inlined into a style attribute, ahead of
+//      any existing inline style (which therefore wins on conflicts)
+//   2. <script> elements are removed with their content - clients strip them
+//   3. doctype declarations are removed - email builders and ESPs supply
+//      their own wrapper
+// Structure (tables, attributes, comments including <!--[if mso]> blocks) is
+// copied verbatim; build the message with plain tags or
+// Html(..., auto_structure=False) so no head hoisting happens in the first place.
+//
+// conditional_comment() writes those blocks - a legacy feature for Outlook
+// (mso) and old Internet Explorer; every other client sees one comment:
+//   conditional_comment("mso", Table(...))  ->  <!--[if mso]><table>...</table><![endif]-->
+
+use ahash::AHashMap;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::css::{declaration_value, property_name, reject_structural};
+use crate::tokenizer::{parse_attributes, tokenize, write_start_tag, Token};
+use crate::{process_child_object, HtmlString};
+
+/// Declarations per class: a str is used as written, a dict like css() properties
+fn class_declarations(styles: &Bound<'_, PyDict>) -> PyResult<AHashMap<String, String>> {
+    let mut declarations = AHashMap::with_capacity(styles.len());
+    for (class, rules) in styles.iter() {
+        let class = class.extract::<String>()?;
+        let text = if let Ok(properties) = rules.downcast::<PyDict>() {
+            let mut parts = Vec::with_capacity(properties.len());
+            for (property, value) in properties.iter() {
+                let value = declaration_value(&value)?;
+                reject_structural("value", &value)?;
+                parts.push(format!("{}:{}", property_name(&property.extract::<String>()?), value));
+            }
+            parts.join(";")
+        } else {
+            rules.extract::<String>()?.trim().trim_end_matches(';').trim_end().to_string()
+        };
+        if !text.is_empty() {
+            declarations.insert(class, text);
+        }
+    }
+    Ok(declarations)
+}
+
+/// Rebuild a start tag with its class styles inlined, or None when no class matches
+fn inline_class_styles(
+    name: &str,
+    raw: &str,
+    self_closing: bool,
+    declarations: &AHashMap<String, String>,
+) -> Option<String> {
+    let attrs = parse_attributes(raw);
+    let classes = attrs.iter().find(|(attr, _)| attr == "class")?.1.as_deref()?;
+    let inlined: Vec<&str> = classes
+        .split_whitespace()
+        .filter_map(|class| declarations.get(class).map(String::as_str))
+        .collect();
+    if inlined.is_empty() {
+        return None;
+    }
+    let inlined = inlined.join(";");
+
+    let style = match attrs.iter().find(|(attr, _)| attr == "style") {
+        Some((_, Some(existing))) if !existing.trim().is_empty() => format!("{};{}", inlined, existing),
+        _ => inlined,
+    };
+
+    let mut attrs: Vec<_> = attrs.into_iter().filter(|(attr, _)| attr != "style").collect();
+    attrs.push(("style".to_string(), Some(style)));
+    Some(write_start_tag(name, &attrs, self_closing))
+}
+
+/// Rendered markup adjusted for HTML email: class styles inlined, scripts and
+/// doctype removed, everything else unchanged
+///
+/// `styles` maps class names to declarations, either a CSS string or a dict
+/// of properties in css() form.
+///
+/// Example:
+///   render_email(P("Hi", cls="lead"), styles={"lead": "font-size:18px"})
+///   Output: <p class="lead" style="font-size:18px">Hi</p>
+#[pyfunction]
+#[pyo3(signature = (element, styles = None))]
+fn render_email(element: PyObject, styles: Option<&Bound<'_, PyDict>>, py: Python) -> PyResult<HtmlString> {
+    let declarations = match styles {
+        Some(styles) => class_declarations(styles)?,
+        None => AHashMap::new(),
+    };
+    let content = process_child_object(&element, py)?;
+
+    let mut out = String::with_capacity(content.len());
+    let mut in_script = false;
+    for token in tokenize(&content) {
+        match &token {
+            Token::Doctype(_) => {}
+            Token::StartTag { name, self_closing, .. } if name == "script" => in_script = !self_closing,
+            Token::EndTag { name, .. } if name == "script" => in_script = false,
+            _ if in_script => {}
+            Token::StartTag { name, raw, self_closing } if !declarations.is_empty() => {
+                match inline_class_styles(name, raw, *self_closing, &declarations) {
+                    Some(tag) => out.push_str(&tag),
+                    None => out.push_str(raw),
+                }
+            }
+            token => out.push_str(token.raw()),
+        }
+    }
+    Ok(HtmlString::new(out))
+}
+
+/// Wrap rendered markup in a legacy `<!--[if condition]>...<![endif]-->` block
+///
+/// The node is rendered as markup, not escaped. Raises ValueError for an empty
+/// condition, one containing ']', '>' or "--", or content containing "-->"
+/// (it would end the comment early for every other client).
+///
+/// Example:
+///   conditional_comment("lt IE 9", Link(rel="stylesheet", href="/ie.css"))
+///   Output: <!--[if lt IE 9]><link rel="stylesheet" href="/ie.css"></link><![endif]-->
+#[pyfunction]
+fn conditional_comment(condition: &str, node: PyObject, py: Python) -> PyResult<HtmlString> {
+    let condition = condition.trim();
+    if condition.is_empty() || condition.contains([']', '>']) || condition.contains("--") {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid conditional comment condition: {:?}",
+            condition
+        )));
+    }
+    let content = process_child_object(&node, py)?;
+    if content.contains("-->") {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "conditional_comment() content cannot contain \"-->\"",
+        ));
+    }
+    Ok(HtmlString::new(format!("<!--[if {}]>{}<![endif]-->", condition, content)))
+}
+
+/// Register the email renderer and conditional comments on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(render_email, m)?)?;
+    m.add_function(wrap_pyfunction!(conditional_comment, m)?)?;
+    Ok(())
+}
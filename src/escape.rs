@@ -0,0 +1,114 @@
+// =============================================================================
+// CUSTOM ESCAPING - Scoped override of how plain text children are written
+// =============================================================================
+//
+// Plain `str` children are normally written as-is (textarea content gets RCDATA
+// escaping). Inside `with escape_with(func):` every plain string child of the
+// tags built in the block goes through `func` instead - for example to escape
+// for a template language embedded in the output. HtmlString children and
+// __html__ objects are already markup and are never passed to `func`.
+//
+// The active function lives in a ContextVar so it follows threads and asyncio
+// tasks; a global scope counter keeps the common case (no override anywhere)
+// down to one atomic load per string child.
+
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ESCAPE_FUNCTION: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+/// Number of escape_with blocks currently entered, in any thread or task
+static ACTIVE_SCOPES: AtomicUsize = AtomicUsize::new(0);
+
+fn escape_var(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    ESCAPE_FUNCTION
+        .get_or_try_init(py, || -> PyResult<Py<PyAny>> {
+            let contextvars = py.import("contextvars")?;
+            Ok(contextvars.getattr("ContextVar")?.call1(("rusty_tags_escape",))?.unbind())
+        })
+        .map(|var| var.bind(py))
+}
+
+/// Escape `text` with the active custom function, or None when no override applies
+#[inline]
+pub(crate) fn custom_escape(text: &str, py: Python) -> PyResult<Option<String>> {
+    if ACTIVE_SCOPES.load(Ordering::Relaxed) == 0 {
+        return Ok(None);
+    }
+
+    let function = escape_var(py)?.call_method1("get", (py.None(),))?;
+    if function.is_none() {
+        return Ok(None);
+    }
+    let escaped = function.call1((text,))?;
+    escaped.extract::<String>().map(Some).map_err(|_| {
+        PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "escape function must return str, got {}",
+            escaped.get_type().name().map(|n| n.to_string()).unwrap_or_else(|_| "unknown".to_string())
+        ))
+    })
+}
+
+/// Context manager returned by `escape_with()`
+#[pyclass(module = "rusty_tags.core")]
+pub struct EscapeScope {
+    function: PyObject,
+    token: Option<PyObject>,
+}
+
+#[pymethods]
+impl EscapeScope {
+    fn __enter__(&mut self, py: Python) -> PyResult<()> {
+        if self.token.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "escape_with is already active"
+            ));
+        }
+        let token = escape_var(py)?.call_method1("set", (&self.function,))?;
+        self.token = Some(token.unbind());
+        ACTIVE_SCOPES.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    #[pyo3(signature = (_exc_type = None, _exc_value = None, _traceback = None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<PyObject>,
+        _exc_value: Option<PyObject>,
+        _traceback: Option<PyObject>,
+        py: Python,
+    ) -> PyResult<bool> {
+        if let Some(token) = self.token.take() {
+            ACTIVE_SCOPES.fetch_sub(1, Ordering::Relaxed);
+            escape_var(py)?.call_method1("reset", (token,))?;
+        }
+        Ok(false)
+    }
+}
+
+/// Escape plain text children with `func` for tags built inside a `with` block
+///
+/// `func` receives each plain str child and must return a str. Pass None to
+/// restore the default (unescaped) behavior inside an outer block.
+///
+/// Example:
+///   with escape_with(lambda s: s.replace("{{", "{{ '{{' }}")):
+///       Div("{{ user }}")
+#[pyfunction]
+fn escape_with(func: PyObject, py: Python) -> PyResult<EscapeScope> {
+    let bound = func.bind(py);
+    if !bound.is_none() && !bound.is_callable() {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            "escape_with() expects a callable or None"
+        ));
+    }
+    Ok(EscapeScope { function: func, token: None })
+}
+
+/// Register the custom escaping API on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<EscapeScope>()?;
+    m.add_function(wrap_pyfunction!(escape_with, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,40 @@
+// =============================================================================
+// ETAGS - Stable content hashes for HTTP caching
+// =============================================================================
+//
+//   etag = page.etag()                      # "5d0c1a7e..." (32 hex digits)
+//   response.headers["ETag"] = f'"{etag}"'
+//
+// The hash is SipHash-2-4 with a 128-bit output and a fixed all-zero key over
+// the UTF-8 bytes of the markup. It depends only on the content - not on the
+// process, platform or Python's hash seed - so every worker and every restart
+// computes the same value, as conditional requests require. It is a cache
+// validator, not a cryptographic digest: don't use it to authenticate content.
+
+use pyo3::prelude::*;
+use siphasher::sip128::{Hasher128, SipHasher24};
+use std::hash::Hasher;
+
+use crate::process_child_object;
+
+/// 32 lowercase hex digits of the SipHash-2-4-128 (zero key) of `content`
+pub(crate) fn content_etag(content: &str) -> String {
+    let mut hasher = SipHasher24::new_with_keys(0, 0);
+    hasher.write(content.as_bytes());
+    format!("{:032x}", hasher.finish128().as_u128())
+}
+
+/// Content hash of a rendered element, the same value as `HtmlString.etag()`
+///
+/// Example:
+///   render_cache_key(Div("x")) == Div("x").etag()
+#[pyfunction]
+fn render_cache_key(element: PyObject, py: Python) -> PyResult<String> {
+    Ok(content_etag(&process_child_object(&element, py)?))
+}
+
+/// Register the cache key helper on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(render_cache_key, m)?)?;
+    Ok(())
+}
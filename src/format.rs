@@ -0,0 +1,463 @@
+// =============================================================================
+// FORMATTING - Pretty-print, minify and strip rendered markup
+// =============================================================================
+//
+// Both formatters consume the same token stream, so callers that need several
+// variants tokenize once and feed every token to each writer. Content of
+// `<pre>` and raw-text elements (script, style, textarea, title) is always
+// copied verbatim.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict};
+
+use crate::elements::is_void_element;
+use crate::tokenizer::{tokenize, Token};
+use crate::{process_child_object, HtmlString};
+
+#[inline]
+fn is_end_of(token: Option<&Token>, tag: &str) -> bool {
+    matches!(token, Some(Token::EndTag { name, .. }) if name == tag)
+}
+
+/// Indent unit when none is given: two spaces
+pub(crate) const DEFAULT_INDENT: &str = "  ";
+
+/// Indented output - one element or text run per line
+pub(crate) struct PrettyWriter {
+    out: String,
+    indent: String,
+    depth: usize,
+    /// Open `<pre>` elements - their content is copied untouched
+    pre_depth: usize,
+    /// Tokens already written by a lookahead
+    skip: usize,
+}
+
+impl PrettyWriter {
+    pub(crate) fn new(indent: &str, capacity: usize) -> Self {
+        PrettyWriter {
+            out: String::with_capacity(capacity + capacity / 2),
+            indent: indent.to_string(),
+            depth: 0,
+            pre_depth: 0,
+            skip: 0,
+        }
+    }
+
+    #[inline]
+    fn start_line(&mut self) {
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        for _ in 0..self.depth {
+            self.out.push_str(&self.indent);
+        }
+    }
+
+    #[inline]
+    fn line(&mut self, content: &str) {
+        self.start_line();
+        self.out.push_str(content);
+    }
+
+    /// Write the token at `index`, looking ahead to keep short elements on one line
+    pub(crate) fn feed(&mut self, tokens: &[Token], index: usize) {
+        if self.skip > 0 {
+            self.skip -= 1;
+            return;
+        }
+
+        let token = &tokens[index];
+        if self.pre_depth > 0 {
+            match token {
+                Token::StartTag { name, raw, .. } => {
+                    if name == "pre" {
+                        self.pre_depth += 1;
+                    }
+                    self.out.push_str(raw);
+                }
+                Token::EndTag { name, raw } => {
+                    if name == "pre" {
+                        self.pre_depth -= 1;
+                    }
+                    self.out.push_str(raw);
+                }
+                Token::Doctype(raw) | Token::Comment(raw) | Token::Text(raw) | Token::RawText(raw) => {
+                    self.out.push_str(raw);
+                }
+            }
+            return;
+        }
+
+        match token {
+            Token::Doctype(raw) | Token::Comment(raw) => self.line(raw),
+            Token::StartTag { name, raw, self_closing } => {
+                if name == "pre" {
+                    self.line(raw);
+                    self.pre_depth = 1;
+                    return;
+                }
+
+                let next = tokens.get(index + 1);
+                if is_end_of(next, name) {
+                    // Empty element: <div></div>
+                    self.line(raw);
+                    if let Some(Token::EndTag { raw: end, .. }) = next {
+                        self.out.push_str(end);
+                    }
+                    self.skip = 1;
+                    return;
+                }
+                if *self_closing || is_void_element(name) {
+                    self.line(raw);
+                    return;
+                }
+
+                // Single text child stays inline: <p>Hello</p>
+                let inline_text = match next {
+                    Some(Token::RawText(text)) => Some(*text),
+                    Some(Token::Text(text)) if !text.contains('\n') => Some(*text),
+                    _ => None,
+                };
+                if let Some(text) = inline_text {
+                    if let Some(Token::EndTag { name: end_name, raw: end }) = tokens.get(index + 2) {
+                        if end_name == name {
+                            self.line(raw);
+                            self.out.push_str(text);
+                            self.out.push_str(end);
+                            self.skip = 2;
+                            return;
+                        }
+                    }
+                }
+
+                self.line(raw);
+                self.depth += 1;
+            }
+            Token::EndTag { raw, .. } => {
+                self.depth = self.depth.saturating_sub(1);
+                self.line(raw);
+            }
+            Token::Text(text) => {
+                let trimmed = text.trim();
+                if !trimmed.is_empty() {
+                    self.line(trimmed);
+                }
+            }
+            Token::RawText(text) => self.out.push_str(text),
+        }
+    }
+
+    pub(crate) fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Comments that survive minify and strip_comments
+///
+/// Conditional comments (`<!--[if IE]>...`, `<!--<![endif]-->`) carry meaning
+/// for old IE, and `<!--! ... -->` marks a comment as intentionally shipped
+/// (licenses, build stamps).
+#[inline]
+pub(crate) fn is_kept_comment(raw: &str) -> bool {
+    raw.starts_with("<!--[if") || raw.starts_with("<!--<![endif]") || raw.starts_with("<!--!")
+}
+
+/// Compact output - collapses whitespace in text and drops comments
+pub(crate) struct MinifyWriter {
+    out: String,
+    pre_depth: usize,
+}
+
+impl MinifyWriter {
+    pub(crate) fn new(capacity: usize) -> Self {
+        MinifyWriter {
+            out: String::with_capacity(capacity),
+            pre_depth: 0,
+        }
+    }
+
+    pub(crate) fn feed(&mut self, token: &Token) {
+        match token {
+            Token::Comment(raw) => {
+                if self.pre_depth > 0 || is_kept_comment(raw) {
+                    self.out.push_str(raw);
+                }
+            }
+            Token::StartTag { name, raw, .. } => {
+                if name == "pre" {
+                    self.pre_depth += 1;
+                }
+                self.out.push_str(raw);
+            }
+            Token::EndTag { name, raw } => {
+                if name == "pre" {
+                    self.pre_depth = self.pre_depth.saturating_sub(1);
+                }
+                self.out.push_str(raw);
+            }
+            Token::Text(text) if self.pre_depth == 0 => self.push_collapsed(text),
+            Token::Doctype(raw) | Token::Text(raw) | Token::RawText(raw) => self.out.push_str(raw),
+        }
+    }
+
+    /// Collapse whitespace runs to one space; indentation-only runs vanish
+    #[inline]
+    fn push_collapsed(&mut self, text: &str) {
+        if text.trim().is_empty() && text.contains('\n') {
+            return;
+        }
+        let mut in_space = false;
+        for c in text.chars() {
+            if c.is_ascii_whitespace() {
+                if !in_space {
+                    self.out.push(' ');
+                    in_space = true;
+                }
+            } else {
+                self.out.push(c);
+                in_space = false;
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Indent unit from an `indent` argument: an int is that many spaces, a str is
+/// used literally and may only contain spaces and tabs
+fn indent_unit(indent: &Bound<'_, PyAny>) -> PyResult<String> {
+    if let Ok(unit) = indent.extract::<String>() {
+        if !unit.chars().all(|c| c == ' ' || c == '\t') {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "indent must contain only spaces and tabs, got {:?}", unit
+            )));
+        }
+        return Ok(unit);
+    }
+    if indent.is_instance_of::<PyBool>() {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>("indent must be an int or a str, not bool"));
+    }
+    match indent.extract::<usize>() {
+        Ok(width) => Ok(" ".repeat(width)),
+        Err(_) if indent.extract::<isize>().is_ok() => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "indent must be a non-negative number of spaces"
+        )),
+        Err(_) => Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>("indent must be an int or a str")),
+    }
+}
+
+/// Pretty-print rendered markup with `unit` per nesting level
+pub(crate) fn pretty_markup(content: &str, unit: &str) -> String {
+    let tokens = tokenize(content);
+    let mut writer = PrettyWriter::new(unit, content.len());
+    for index in 0..tokens.len() {
+        writer.feed(&tokens, index);
+    }
+    writer.finish()
+}
+
+/// Render an element as indented markup, one element or text run per line
+///
+/// `indent` is the unit written per nesting level: an int for that many spaces
+/// or a str of spaces/tabs such as "\t".
+///
+/// Example:
+///   render_pretty(Div(P("Hi")), indent="\t")
+///   Output: "<div>\n\t<p>Hi</p>\n</div>"
+#[pyfunction]
+#[pyo3(signature = (element, indent = None))]
+fn render_pretty(element: PyObject, indent: Option<&Bound<'_, PyAny>>, py: Python) -> PyResult<HtmlString> {
+    let unit = match indent {
+        Some(indent) => indent_unit(indent)?,
+        None => DEFAULT_INDENT.to_string(),
+    };
+    let content = process_child_object(&element, py)?;
+    Ok(HtmlString::new(pretty_markup(&content, &unit)))
+}
+
+/// Render an element once and derive pretty and/or minified output from one tokenization
+///
+/// Returns a dict with a "pretty" and/or "minified" HtmlString, depending on the flags.
+/// `indent` is the pretty-print unit, as in render_pretty().
+///
+/// Example:
+///   render_variants(Div(P("Hi")))
+///   {"pretty": "<div>\n  <p>Hi</p>\n</div>", "minified": "<div><p>Hi</p></div>"}
+#[pyfunction]
+#[pyo3(signature = (element, pretty = true, minified = true, indent = None))]
+fn render_variants(
+    element: PyObject,
+    pretty: bool,
+    minified: bool,
+    indent: Option<&Bound<'_, PyAny>>,
+    py: Python,
+) -> PyResult<Py<PyDict>> {
+    if !pretty && !minified {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "render_variants() requires pretty=True or minified=True"
+        ));
+    }
+    let unit = match indent {
+        Some(indent) => indent_unit(indent)?,
+        None => DEFAULT_INDENT.to_string(),
+    };
+
+    let content = process_child_object(&element, py)?;
+    let tokens = tokenize(&content);
+
+    let mut pretty_writer = pretty.then(|| PrettyWriter::new(&unit, content.len()));
+    let mut minify_writer = minified.then(|| MinifyWriter::new(content.len()));
+    for index in 0..tokens.len() {
+        if let Some(writer) = pretty_writer.as_mut() {
+            writer.feed(&tokens, index);
+        }
+        if let Some(writer) = minify_writer.as_mut() {
+            writer.feed(&tokens[index]);
+        }
+    }
+
+    let result = PyDict::new(py);
+    if let Some(writer) = pretty_writer {
+        result.set_item("pretty", HtmlString::new(writer.finish()))?;
+    }
+    if let Some(writer) = minify_writer {
+        result.set_item("minified", HtmlString::new(writer.finish()))?;
+    }
+    Ok(result.unbind())
+}
+
+/// Remove `<!-- ... -->` comments, keeping conditional and `<!--! ... -->` ones
+///
+/// Everything else, including whitespace, is copied unchanged; comment-like
+/// text inside script and style content is not a comment and is left alone.
+///
+/// Example:
+///   strip_comments(HtmlString("<!-- TODO --><p>Hi</p><!--! v1.2 -->"))
+///   Output: <p>Hi</p><!--! v1.2 -->
+#[pyfunction]
+fn strip_comments(element: PyObject, py: Python) -> PyResult<HtmlString> {
+    let content = process_child_object(&element, py)?;
+    if !content.contains("<!--") {
+        return Ok(HtmlString::new(content));
+    }
+
+    let mut out = String::with_capacity(content.len());
+    for token in tokenize(&content) {
+        match token {
+            Token::Comment(raw) if !is_kept_comment(raw) => {}
+            token => out.push_str(token.raw()),
+        }
+    }
+    Ok(HtmlString::new(out))
+}
+
+/// Phrasing elements whose tags don't separate words in extracted text
+const INLINE_TAGS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "cite", "code", "data", "del", "dfn", "em",
+    "i", "ins", "kbd", "label", "mark", "q", "s", "samp", "small", "span",
+    "strong", "sub", "sup", "time", "u", "var", "wbr",
+];
+
+/// Decode the basic named entities and numeric character references
+///
+/// Unknown or malformed references are left as they are.
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|end| *end <= 12).and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                reference => reference
+                    .strip_prefix("#x")
+                    .or_else(|| reference.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| reference.strip_prefix('#').map(str::parse::<u32>))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Append `text`, collapsing whitespace runs into a single pending space
+fn push_words(out: &mut String, text: &str, pending_space: &mut bool) {
+    for c in text.chars() {
+        if c.is_whitespace() {
+            *pending_space = true;
+        } else {
+            if *pending_space && !out.is_empty() {
+                out.push(' ');
+            }
+            *pending_space = false;
+            out.push(c);
+        }
+    }
+}
+
+/// Text content of an element with the tags removed - for plaintext email
+/// alternatives and search indexes
+///
+/// Entities are decoded, whitespace runs collapse to one space and the ends are
+/// trimmed. Tags other than inline ones (span, a, strong, ...) separate words,
+/// so `<p>a</p><p>b</p>` gives "a b". Script and style content and comments are dropped.
+///
+/// Example:
+///   strip_tags(Div(H1("Hi &amp; welcome"), P("Read ", A("more", href="/"))))
+///   Output: "Hi & welcome Read more"
+#[pyfunction]
+fn strip_tags(element: PyObject, py: Python) -> PyResult<String> {
+    let content = process_child_object(&element, py)?;
+    let tokens = tokenize(&content);
+
+    let mut out = String::with_capacity(content.len() / 2);
+    let mut pending_space = false;
+    let mut raw_parent = "";
+    for token in &tokens {
+        match token {
+            Token::Text(text) => push_words(&mut out, &decode_entities(text), &mut pending_space),
+            Token::RawText(text) if raw_parent != "script" && raw_parent != "style" => {
+                push_words(&mut out, &decode_entities(text), &mut pending_space)
+            }
+            Token::StartTag { name, .. } => {
+                raw_parent = name;
+                pending_space |= !INLINE_TAGS.contains(&name.as_str());
+            }
+            Token::EndTag { name, .. } => pending_space |= !INLINE_TAGS.contains(&name.as_str()),
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+/// Register the formatting functions on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(render_pretty, m)?)?;
+    m.add_function(wrap_pyfunction!(render_variants, m)?)?;
+    m.add_function(wrap_pyfunction!(strip_comments, m)?)?;
+    m.add_function(wrap_pyfunction!(strip_tags, m)?)?;
+    Ok(())
+}
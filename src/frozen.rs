@@ -0,0 +1,94 @@
+// =============================================================================
+// FROZEN HTML - Precompiled markup for static fragments
+// =============================================================================
+//
+// Logos, footers and icon sprites render to the same markup on every request.
+// `freeze(element)` renders once into an immutable, shared `Arc<str>`:
+//
+//   FOOTER = freeze(Footer(P("(c) 2025 Example"), Nav(...)))
+//   Page(..., FOOTER)
+//
+// Tags recognize a FrozenHtml child before any other conversion and copy its
+// bytes straight into their buffer - no __html__ lookup, no intermediate String.
+// Re-freezing a FrozenHtml only bumps the reference count, and the class is a
+// `frozen` pyclass, so reading it never takes a borrow.
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::sync::Arc;
+
+use crate::process_child_object;
+
+/// Immutable pre-rendered markup returned by `freeze()`
+#[pyclass(module = "rusty_tags.core", frozen)]
+pub struct FrozenHtml {
+    content: Arc<str>,
+}
+
+impl FrozenHtml {
+    #[inline(always)]
+    pub(crate) fn as_str(&self) -> &str {
+        &self.content
+    }
+}
+
+#[pymethods]
+impl FrozenHtml {
+    #[getter]
+    fn content(&self) -> &str {
+        &self.content
+    }
+
+    fn __str__(&self) -> &str {
+        &self.content
+    }
+
+    fn __html__(&self) -> &str {
+        &self.content
+    }
+
+    fn __repr__(&self) -> String {
+        format!("FrozenHtml({:?})", &*self.content)
+    }
+
+    fn __len__(&self) -> usize {
+        self.content.len()
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        match other.downcast::<FrozenHtml>() {
+            Ok(other) => self.content == other.get().content,
+            Err(_) => other.extract::<&str>().is_ok_and(|s| s == &*self.content),
+        }
+    }
+
+    fn __hash__(&self, py: Python) -> PyResult<isize> {
+        // Hash like the equal str so frozen fragments and plain strings mix in sets
+        self.content.into_pyobject(py)?.hash()
+    }
+
+    fn encode<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, self.content.as_bytes())
+    }
+}
+
+/// Render an element once into immutable markup that embeds with no processing
+///
+/// Example:
+///   LOGO = freeze(Svg(Path(d="M0 0h24v24H0z"), viewBox="0 0 24 24"))
+///   Header(LOGO, Nav(...))
+#[pyfunction]
+fn freeze(element: &Bound<'_, PyAny>, py: Python) -> PyResult<FrozenHtml> {
+    if let Ok(frozen) = element.downcast::<FrozenHtml>() {
+        return Ok(FrozenHtml { content: Arc::clone(&frozen.get().content) });
+    }
+    let rendered = process_child_object(&element.clone().unbind(), py)?;
+    Ok(FrozenHtml { content: Arc::from(rendered) })
+}
+
+/// Register the frozen markup API on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<FrozenHtml>()?;
+    m.add_function(wrap_pyfunction!(freeze, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+// =============================================================================
+// INLINE EVENT HANDLERS - Auditable on* attributes
+// =============================================================================
+//
+// Inline handlers (`onclick="..."`) run arbitrary script and are a common XSS
+// vector. With `configure(guard_event_handlers=True)` every `on*` attribute
+// must be written through `unsafe_attr()`, so each use is explicit and easy to
+// grep for:
+//
+//   Button("Save", **unsafe_attr("onclick", "save()"))
+//
+// A plain string for an `on*` attribute raises ValueError. The check is off by
+// default; Datastar's `on_click` / `ds_on_click` attributes are not affected.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::config::render_config;
+
+/// Attribute value marked as a deliberate inline handler
+#[pyclass(module = "rusty_tags.core", frozen, get_all)]
+pub struct UnsafeAttr {
+    name: String,
+    value: String,
+}
+
+#[pymethods]
+impl UnsafeAttr {
+    fn __str__(&self) -> &str {
+        &self.value
+    }
+
+    fn __repr__(&self) -> String {
+        format!("UnsafeAttr({:?}, {:?})", self.name, self.value)
+    }
+}
+
+/// `onclick`, `onload`, ... - but not Datastar's `on_click` shorthand
+#[inline(always)]
+fn is_event_handler(key: &str) -> bool {
+    let bytes = key.as_bytes();
+    bytes.len() > 2
+        && bytes[..2].eq_ignore_ascii_case(b"on")
+        && bytes[2].is_ascii_alphabetic()
+}
+
+/// Reject plain `on*` attribute values while `guard_event_handlers` is on
+#[inline(always)]
+pub(crate) fn check_event_handler(key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+    if !is_event_handler(key) || !render_config().guard_event_handlers {
+        return Ok(());
+    }
+
+    match value.downcast::<UnsafeAttr>() {
+        Ok(marked) if marked.get().name.eq_ignore_ascii_case(key) => Ok(()),
+        Ok(marked) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "unsafe_attr({:?}, ...) was passed as '{}'", marked.get().name, key
+        ))),
+        Err(_) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Inline event handler '{}' must be wrapped with unsafe_attr() (guard_event_handlers)", key
+        ))),
+    }
+}
+
+/// Mark an inline event handler as intentional, returning `{name: value}` for `**` unpacking
+///
+/// Example:
+///   Button("Save", **unsafe_attr("onclick", "save()"))
+///   Output: <button onclick="save()">Save</button>
+#[pyfunction(name = "unsafe_attr")]
+fn mark_event_handler(name: &str, code: &str, py: Python) -> PyResult<Py<PyDict>> {
+    if name.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "unsafe_attr() needs an attribute name"
+        ));
+    }
+    let dict = PyDict::new(py);
+    let marked = UnsafeAttr { name: name.to_string(), value: code.to_string() };
+    dict.set_item(name, Py::new(py, marked)?)?;
+    Ok(dict.unbind())
+}
+
+/// Register the inline handler API on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<UnsafeAttr>()?;
+    m.add_function(wrap_pyfunction!(mark_event_handler, m)?)?;
+    Ok(())
+}
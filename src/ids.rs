@@ -0,0 +1,181 @@
+// =============================================================================
+// ID SCOPES - Prefix element ids so component instances don't collide
+// =============================================================================
+//
+// Inside `with id_scope("card1"):` every `id` attribute of the tags built in
+// the block is written as "card1-<id>", and so is every id *reference*, so
+// labels, ARIA relationships and form associations keep pointing at the right
+// element:
+//
+//   with id_scope("card1"):
+//       Label("Name", fr="name"), Input(id="name")
+//   -> <label for="card1-name">Name</label><input id="card1-name"></input>
+//
+// References are detected by attribute name: single-id attributes (for, form,
+// list, aria-activedescendant, popovertarget, ...) and space-separated id lists
+// (aria-labelledby, aria-describedby, headers, ...) have each token prefixed.
+// URLs such as href="#name" are left alone; use scoped_id() to build those.
+// Nested scopes compose ("outer-inner-name"). Like escape_with(), the prefix
+// lives in a ContextVar and only affects elements created inside the block.
+
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{attrmap_optimized, AttrMap, AttrValue};
+
+static ID_PREFIX: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+/// Number of id_scope blocks currently entered, in any thread or task
+static ACTIVE_SCOPES: AtomicUsize = AtomicUsize::new(0);
+
+fn prefix_var(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    ID_PREFIX
+        .get_or_try_init(py, || -> PyResult<Py<PyAny>> {
+            let contextvars = py.import("contextvars")?;
+            Ok(contextvars.getattr("ContextVar")?.call1(("rusty_tags_id_prefix",))?.unbind())
+        })
+        .map(|var| var.bind(py))
+}
+
+/// The active prefix, or None outside any id_scope block
+fn current_prefix(py: Python) -> PyResult<Option<String>> {
+    if ACTIVE_SCOPES.load(Ordering::Relaxed) == 0 {
+        return Ok(None);
+    }
+    let prefix = prefix_var(py)?.call_method1("get", (py.None(),))?;
+    if prefix.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(prefix.extract()?))
+}
+
+/// How an attribute refers to element ids
+enum IdRole {
+    /// A single id (id, for, form, list, ...)
+    Single,
+    /// A space-separated list of ids (aria-labelledby, headers, ...)
+    List,
+}
+
+fn id_role(name: &str) -> Option<IdRole> {
+    match name {
+        "id" | "for" | "form" | "list" | "aria-activedescendant" | "aria-errormessage"
+        | "popovertarget" | "commandfor" | "anchor" => Some(IdRole::Single),
+        "aria-labelledby" | "aria-describedby" | "aria-controls" | "aria-owns"
+        | "aria-flowto" | "aria-details" | "headers" | "itemref" => Some(IdRole::List),
+        _ => None,
+    }
+}
+
+#[inline]
+fn prefixed(prefix: &str, id: &str) -> String {
+    format!("{}-{}", prefix, id)
+}
+
+/// Copy of `attrs` with ids and id references prefixed, or None when nothing changes
+pub(crate) fn scope_ids(attrs: &AttrMap<AttrValue>, py: Python) -> PyResult<Option<AttrMap<AttrValue>>> {
+    if attrs.is_empty() {
+        return Ok(None);
+    }
+    let Some(prefix) = current_prefix(py)? else {
+        return Ok(None);
+    };
+
+    let mut scoped = AttrMap::default();
+    let mut changed = false;
+    for (key, value) in attrs {
+        let rewritten = match id_role(&attrmap_optimized(key)) {
+            Some(_) if value.is_empty() => None,
+            Some(IdRole::Single) => Some(prefixed(&prefix, value)),
+            Some(IdRole::List) => Some(
+                value.split_ascii_whitespace().map(|id| prefixed(&prefix, id)).collect::<Vec<_>>().join(" ")
+            ),
+            None => None,
+        };
+        changed |= rewritten.is_some();
+        scoped.push_duplicate(key.clone(), rewritten.map_or_else(|| value.clone(), AttrValue::Text));
+    }
+    Ok(changed.then_some(scoped))
+}
+
+/// Context manager returned by `id_scope()`
+#[pyclass(module = "rusty_tags.core")]
+pub struct IdScope {
+    prefix: String,
+    token: Option<PyObject>,
+}
+
+#[pymethods]
+impl IdScope {
+    fn __enter__(&mut self, py: Python) -> PyResult<String> {
+        if self.token.is_some() {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "id_scope is already active"
+            ));
+        }
+
+        // Nested scopes compose with the enclosing prefix
+        let prefix = match current_prefix(py)? {
+            Some(outer) => prefixed(&outer, &self.prefix),
+            None => self.prefix.clone(),
+        };
+        let token = prefix_var(py)?.call_method1("set", (&prefix,))?;
+        self.token = Some(token.unbind());
+        ACTIVE_SCOPES.fetch_add(1, Ordering::Relaxed);
+        Ok(prefix)
+    }
+
+    #[pyo3(signature = (_exc_type = None, _exc_value = None, _traceback = None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<PyObject>,
+        _exc_value: Option<PyObject>,
+        _traceback: Option<PyObject>,
+        py: Python,
+    ) -> PyResult<bool> {
+        if let Some(token) = self.token.take() {
+            ACTIVE_SCOPES.fetch_sub(1, Ordering::Relaxed);
+            prefix_var(py)?.call_method1("reset", (token,))?;
+        }
+        Ok(false)
+    }
+}
+
+/// Prefix ids and id references of tags built inside a `with` block
+///
+/// Example:
+///   with id_scope("card1"):
+///       Label("Name", fr="name"), Input(id="name")
+///   Output: <label for="card1-name">Name</label><input id="card1-name"></input>
+#[pyfunction]
+fn id_scope(prefix: String) -> PyResult<IdScope> {
+    if prefix.is_empty() || prefix.contains(|c: char| c.is_ascii_whitespace()) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("id_scope() prefix must be non-empty and contain no whitespace: {:?}", prefix)
+        ));
+    }
+    Ok(IdScope { prefix, token: None })
+}
+
+/// The id `name` as it will be written in the current id_scope
+///
+/// Example:
+///   with id_scope("card1"):
+///       A("Jump", href="#" + scoped_id("details"))
+///   Output: <a href="#card1-details">Jump</a>
+#[pyfunction]
+fn scoped_id(name: &str, py: Python) -> PyResult<String> {
+    Ok(match current_prefix(py)? {
+        Some(prefix) => prefixed(&prefix, name),
+        None => name.to_string(),
+    })
+}
+
+/// Register the id scoping API on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<IdScope>()?;
+    m.add_function(wrap_pyfunction!(id_scope, m)?)?;
+    m.add_function(wrap_pyfunction!(scoped_id, m)?)?;
+    Ok(())
+}
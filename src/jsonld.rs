@@ -0,0 +1,87 @@
+// =============================================================================
+// JSON-LD - Structured data in <script type="application/ld+json">
+// =============================================================================
+//
+//   json_ld({"@context": "https://schema.org", "@type": "Article", "headline": title})
+//   -> <script type="application/ld+json">{"@context":"https://schema.org",...}</script>
+//
+// The data is serialized with serde_json and written into the script as raw
+// text. A script element ends at the first "</script" and treats "<!--" as
+// special, so every "<", ">" and "&" is written as its JSON \u escape - these
+// characters can only occur inside JSON strings, where the escape decodes to
+// the same value. Object keys come out sorted.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::Serialize;
+
+use crate::{build_html_tag_optimized, process_plain_kwargs, AttrMap, HtmlString};
+
+/// Serialize `value`, compact or with `indent` spaces per level
+fn to_json(value: &serde_json::Value, indent: Option<usize>) -> Result<String, serde_json::Error> {
+    let Some(indent) = indent else {
+        return serde_json::to_string(value);
+    };
+    let indent = " ".repeat(indent);
+    let mut buffer = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+    value.serialize(&mut serializer)?;
+    // serde_json only writes valid UTF-8
+    Ok(String::from_utf8(buffer).unwrap_or_default())
+}
+
+/// Escape the characters that could end or confuse the surrounding <script>
+fn escape_script_json(json: &str) -> String {
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            // Valid in JSON but line terminators in older JavaScript parsers
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Structured data as a `<script type="application/ld+json">` element
+///
+/// `indent` pretty-prints with that many spaces per level; extra keyword
+/// arguments become attributes of the script tag (id, nonce, ...).
+///
+/// Example:
+///   json_ld({"@context": "https://schema.org", "@type": "Person", "name": "Ada"})
+///   Output: <script type="application/ld+json">{"@context":"https://schema.org","@type":"Person","name":"Ada"}</script>
+#[pyfunction]
+#[pyo3(signature = (data, indent = None, **kwargs))]
+fn json_ld(
+    data: &Bound<'_, PyAny>,
+    indent: Option<usize>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+    py: Python,
+) -> PyResult<HtmlString> {
+    let value: serde_json::Value = pythonize::depythonize(data).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!("json_ld() data is not JSON-serializable: {}", e))
+    })?;
+    let json = to_json(&value, indent).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("json_ld() could not serialize data: {}", e))
+    })?;
+
+    let mut attrs = AttrMap::default();
+    attrs.insert("type".to_string(), "application/ld+json".to_string().into());
+    if let Some(kwargs) = kwargs {
+        process_plain_kwargs(kwargs, &mut attrs, py)?;
+    }
+    let body = Py::new(py, HtmlString::new(escape_script_json(&json)))?.into_any();
+    build_html_tag_optimized("script", vec![body], attrs, py)
+}
+
+/// Register the JSON-LD helper on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(json_ld, m)?)?;
+    Ok(())
+}
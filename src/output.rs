@@ -0,0 +1,100 @@
+// =============================================================================
+// FILE OUTPUT - Render straight to disk, optionally pre-compressed
+// =============================================================================
+//
+// Static-site builds usually ship `page.html.gz` / `page.html.br` next to (or
+// instead of) the plain file so the CDN can serve them as-is. Compression and
+// the file write both run in Rust with the GIL released. The gzip header carries
+// no timestamp, so the same page always compresses to the same bytes.
+
+use flate2::write::GzEncoder;
+use flate2::{Compression, GzBuilder};
+use pyo3::prelude::*;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::config::render_config;
+use crate::process_child_object;
+
+/// Supported compression methods
+#[derive(Clone, Copy)]
+enum Method {
+    Gzip,
+    Brotli,
+}
+
+impl Method {
+    fn parse(method: &str) -> PyResult<Self> {
+        match method {
+            "gzip" => Ok(Method::Gzip),
+            "brotli" => Ok(Method::Brotli),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Unknown compression: {:?} (expected \"gzip\", \"brotli\" or None)", method)
+            )),
+        }
+    }
+}
+
+/// Compress `content` at the highest level (gzip mtime 0, brotli quality 11)
+fn compress(content: &[u8], method: Method) -> std::io::Result<Vec<u8>> {
+    match method {
+        Method::Gzip => {
+            let mut encoder: GzEncoder<Vec<u8>> = GzBuilder::new().mtime(0).write(Vec::new(), Compression::best());
+            encoder.write_all(content)?;
+            encoder.finish()
+        }
+        Method::Brotli => {
+            let mut compressed = Vec::new();
+            {
+                let mut encoder = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+                encoder.write_all(content)?;
+            }
+            Ok(compressed)
+        }
+    }
+}
+
+/// Write `content` to `path`, compressed when `method` is given; returns bytes written
+pub(crate) fn write_output(content: &str, path: PathBuf, method: Option<&str>, py: Python) -> PyResult<usize> {
+    let method = method.map(Method::parse).transpose()?;
+
+    let written = py.detach(|| -> std::io::Result<usize> {
+        let compressed = method.map(|method| compress(content.as_bytes(), method)).transpose()?;
+        let bytes = compressed.as_deref().unwrap_or(content.as_bytes());
+        std::fs::write(&path, bytes)?;
+        Ok(bytes.len())
+    })?;
+    Ok(written)
+}
+
+/// Render an element and write it to a file, optionally gzip- or brotli-compressed
+///
+/// Returns the number of bytes written (the compressed size when compressing).
+/// `trailing_newline` defaults to the `configure(trailing_newline=...)` setting;
+/// content that already ends with a newline is left as is.
+///
+/// Example:
+///   render_to_file(page, "dist/index.html.gz", compress="gzip")
+///   Output: 1532
+#[pyfunction]
+#[pyo3(signature = (element, path, compress = None, trailing_newline = None))]
+fn render_to_file(
+    element: PyObject,
+    path: PathBuf,
+    compress: Option<&str>,
+    trailing_newline: Option<bool>,
+    py: Python,
+) -> PyResult<usize> {
+    let mut content = process_child_object(&element, py)?;
+    let trailing_newline = trailing_newline.unwrap_or_else(|| render_config().trailing_newline);
+    if trailing_newline && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    write_output(&content, path, compress, py)
+}
+
+/// Register the file output functions on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(render_to_file, m)?)?;
+    Ok(())
+}
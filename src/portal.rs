@@ -0,0 +1,172 @@
+// =============================================================================
+// PORTALS - Deferred placement of markup into <head> or the end of <body>
+// =============================================================================
+//
+// A component deep in the body can carry its own asset requirements:
+//   Portal("head", Link(rel="stylesheet", href="/widget.css"))
+// renders as  <!--portal:head-->...<!--/portal:head-->  and `Html` moves the
+// content to its target during document assembly. Identical contents sent to
+// the same target are emitted once, so a component used ten times still adds
+// one stylesheet. Any <base> in the document goes first in <head>, ahead of
+// any link or script whose URL it resolves.
+
+use pyo3::prelude::*;
+
+use crate::tokenizer::{tokenize, Token};
+use crate::{process_children_optimized, HtmlString};
+
+const PORTAL_OPEN: &str = "<!--portal:";
+const PORTAL_CLOSE: &str = "<!--/portal:";
+const MARKER_END: &str = "-->";
+
+/// Supported portal destinations
+const PORTAL_TARGETS: &[&str] = &["head", "body"];
+
+/// Pull every portal out of `content`
+///
+/// Returns the content with portals removed plus the collected markup for the
+/// head and body targets, or None when the content has no portals (fast path).
+fn collect_portals(content: &str) -> Option<(String, String, String)> {
+    if !content.contains(PORTAL_OPEN) {
+        return None;
+    }
+
+    let mut remaining = String::with_capacity(content.len());
+    let mut head: Vec<&str> = Vec::new();
+    let mut body: Vec<&str> = Vec::new();
+
+    let mut rest = content;
+    while let Some(start) = rest.find(PORTAL_OPEN) {
+        remaining.push_str(&rest[..start]);
+        let after_open = &rest[start + PORTAL_OPEN.len()..];
+
+        let parsed = after_open.find(MARKER_END).and_then(|name_end| {
+            let target = &after_open[..name_end];
+            let body_start = &after_open[name_end + MARKER_END.len()..];
+            let close = format!("{}{}{}", PORTAL_CLOSE, target, MARKER_END);
+            body_start.find(&close).map(|end| (target, &body_start[..end], &body_start[end + close.len()..]))
+        });
+
+        match parsed {
+            Some((target, portal_content, after)) => {
+                let collected = if target == "head" { &mut head } else { &mut body };
+                if !collected.contains(&portal_content) {
+                    collected.push(portal_content);
+                }
+                rest = after;
+            }
+            None => {
+                // Malformed marker - leave it in place
+                remaining.push_str(PORTAL_OPEN);
+                rest = after_open;
+            }
+        }
+    }
+    remaining.push_str(rest);
+
+    Some((remaining, head.concat(), body.concat()))
+}
+
+/// Separate `<base>` elements from the rest of the markup
+fn split_base(markup: &str) -> (String, String) {
+    if !markup.contains("<base") {
+        return (String::new(), markup.to_string());
+    }
+
+    let mut base = String::new();
+    let mut rest = String::with_capacity(markup.len());
+    for token in tokenize(markup) {
+        match &token {
+            Token::StartTag { name, raw, .. } | Token::EndTag { name, raw } if name == "base" => base.push_str(raw),
+            token => rest.push_str(token.raw()),
+        }
+    }
+    (base, rest)
+}
+
+/// Position just past the `<head ...>` start tag, if there is one
+fn head_content_start(content: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(offset) = content[from..].find("<head") {
+        let start = from + offset;
+        let after = &content[start + 5..];
+        if after.starts_with('>') || after.starts_with(|c: char| c.is_ascii_whitespace()) {
+            return after.find('>').map(|end| start + 5 + end + 1);
+        }
+        from = start + 5;
+    }
+    None
+}
+
+/// Move portal contents to their targets inside an `<html>` element's children
+///
+/// Head portals go before `</head>` (a `<head>` is created when missing) and body
+/// portals before `</body>`, or at the end when there is no body. Every `<base>`
+/// in the document - from a portal, the body, or later in the head - is moved to
+/// the top of `<head>`. Returns None when there is nothing to move (fast path).
+pub(crate) fn hoist_portals(children: &str) -> Option<String> {
+    let (mut content, head, body) = match collect_portals(children) {
+        Some(collected) => collected,
+        None if children.contains("<base") => (children.to_string(), String::new(), String::new()),
+        None => return None,
+    };
+
+    let (mut base, rest) = split_base(&content);
+    content = rest;
+    let (portal_base, head) = split_base(&head);
+    base.push_str(&portal_base);
+
+    if !head.is_empty() || !base.is_empty() {
+        match (content.find("</head>"), head_content_start(&content)) {
+            (Some(close), Some(open)) => {
+                content.insert_str(close, &head);
+                content.insert_str(open, &base);
+            }
+            _ => content.insert_str(0, &format!("<head>{}{}</head>", base, head)),
+        }
+    }
+    if !body.is_empty() {
+        match content.rfind("</body>") {
+            Some(pos) => content.insert_str(pos, &body),
+            None => content.push_str(&body),
+        }
+    }
+    Some(content)
+}
+
+/// Portal - render children into another part of the document
+///
+/// Target is "head" or "body" (end of body). Contents are moved when the
+/// enclosing `Html(...)` document is assembled; outside a document the markers
+/// stay in place as comments.
+///
+/// Example:
+///   Html(Head(Title("App")), Body(Div(Portal("head", Link(rel="stylesheet", href="/w.css")), "Widget")))
+///   Output: <!doctype html><html><head><title>App</title><link rel="stylesheet" href="/w.css"></link></head>
+///           <body><div>Widget</div></body></html>
+#[pyfunction]
+#[pyo3(signature = (target, *children))]
+fn Portal(target: &str, children: Vec<PyObject>, py: Python) -> PyResult<HtmlString> {
+    if !PORTAL_TARGETS.contains(&target) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Unknown portal target: {:?} (expected \"head\" or \"body\")", target)
+        ));
+    }
+
+    let content = process_children_optimized(&children, py)?;
+    let mut result = String::with_capacity(content.len() + target.len() * 2 + 32);
+    result.push_str(PORTAL_OPEN);
+    result.push_str(target);
+    result.push_str(MARKER_END);
+    result.push_str(&content);
+    result.push_str(PORTAL_CLOSE);
+    result.push_str(target);
+    result.push_str(MARKER_END);
+    Ok(HtmlString::new(result))
+}
+
+/// Register the portal function on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(Portal, m)?)?;
+    Ok(())
+}
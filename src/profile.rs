@@ -0,0 +1,123 @@
+// =============================================================================
+// RENDER PROFILING - Opt-in per-tag timing
+// =============================================================================
+//
+// Enabled with `configure(profile=True)`. When off, a tag function pays for one
+// relaxed atomic load. Rendering is eager - children are rendered before their
+// parent is called - so the time recorded for a tag covers its own attribute
+// processing and assembly, not the time spent building its children.
+//
+// `configure(on_tag_rendered=fn)` is the usage-counting counterpart: fn(tag) is
+// called after each element is assembled, innermost first. It is telemetry, so
+// an exception in it is reported through sys.unraisablehook and never fails
+// the render, and tags rendered inside the callback do not call it again.
+
+use ahash::AHashMap as HashMap;
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Set while an on_tag_rendered callback is registered, so tags skip the lock otherwise
+static TAG_CALLBACK_SET: AtomicBool = AtomicBool::new(false);
+static TAG_CALLBACK: Mutex<Option<Py<PyAny>>> = Mutex::new(None);
+
+thread_local! {
+    // True while this thread runs the callback, to stop it re-entering itself
+    static IN_TAG_CALLBACK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Cumulative (nanoseconds, calls) per normalized tag name
+static PROFILE: Lazy<Mutex<HashMap<String, (u128, u64)>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Replace the on_tag_rendered callback, kept in sync by `configure`/`reset_config`
+pub(crate) fn set_tag_callback(callback: Option<Py<PyAny>>) {
+    let mut slot = TAG_CALLBACK.lock().unwrap_or_else(|e| e.into_inner());
+    TAG_CALLBACK_SET.store(callback.is_some(), Ordering::Relaxed);
+    *slot = callback;
+}
+
+/// The registered on_tag_rendered callback, for `get_config()`
+pub(crate) fn tag_callback(py: Python) -> Option<Py<PyAny>> {
+    let slot = TAG_CALLBACK.lock().unwrap_or_else(|e| e.into_inner());
+    slot.as_ref().map(|callback| callback.clone_ref(py))
+}
+
+/// Call on_tag_rendered for a finished element; one atomic load when unset
+#[inline(always)]
+pub(crate) fn notify_tag_rendered(tag: &str, py: Python) {
+    if !TAG_CALLBACK_SET.load(Ordering::Relaxed) || IN_TAG_CALLBACK.with(Cell::get) {
+        return;
+    }
+    // Clone out of the lock so the callback may call configure() itself
+    let Some(callback) = tag_callback(py) else {
+        return;
+    };
+    IN_TAG_CALLBACK.with(|flag| flag.set(true));
+    let outcome = callback.call1(py, (tag,));
+    IN_TAG_CALLBACK.with(|flag| flag.set(false));
+    if let Err(err) = outcome {
+        err.write_unraisable(py, Some(callback.bind(py)));
+    }
+}
+
+/// Records the time between `start` and drop against a tag name
+pub(crate) struct TagTimer {
+    tag: String,
+    started: Instant,
+}
+
+impl TagTimer {
+    /// Start timing `tag`, or return None (doing nothing) when profiling is off
+    #[inline(always)]
+    pub(crate) fn start(tag: &str) -> Option<TagTimer> {
+        if !crate::config::render_config().profile {
+            return None;
+        }
+        Some(TagTimer { tag: crate::normalize_tag_name(tag), started: Instant::now() })
+    }
+}
+
+impl Drop for TagTimer {
+    fn drop(&mut self) {
+        let elapsed = self.started.elapsed().as_nanos();
+        let mut profile = PROFILE.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = profile.entry(std::mem::take(&mut self.tag)).or_insert((0, 0));
+        entry.0 += elapsed;
+        entry.1 += 1;
+    }
+}
+
+/// Per-tag render timings collected while `configure(profile=True)` was active
+///
+/// Example:
+///   render_profile()
+///   {"div": {"ns": 48210, "calls": 120}, "li": {"ns": 9120, "calls": 300}}
+#[pyfunction]
+fn render_profile(py: Python) -> PyResult<Py<PyDict>> {
+    let profile = PROFILE.lock().unwrap_or_else(|e| e.into_inner());
+    let result = PyDict::new(py);
+    for (tag, (nanos, calls)) in profile.iter() {
+        let entry = PyDict::new(py);
+        entry.set_item("ns", *nanos)?;
+        entry.set_item("calls", *calls)?;
+        result.set_item(tag, entry)?;
+    }
+    Ok(result.unbind())
+}
+
+/// Discard collected render timings
+#[pyfunction]
+fn reset_profile() {
+    PROFILE.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Register the profiling functions on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(render_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_profile, m)?)?;
+    Ok(())
+}
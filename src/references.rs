@@ -0,0 +1,82 @@
+// =============================================================================
+// ID REFERENCES - Find for / aria-* attributes that point at missing ids
+// =============================================================================
+//
+//   check_id_references(Form(Label("Email", for_="email"), Input(id="mail")))
+//   -> ['<label for="email">: no element with id="email"']
+//
+// Rendering is eager, so references can only be checked once the whole
+// document exists: this is a scan over rendered markup, collecting every id
+// and every referencing attribute. With configure(strict=True), Html(...)
+// runs it on the finished document and emits a UserWarning per broken
+// reference - a label wired to a renamed input still renders, but screen
+// readers lose the association.
+
+use pyo3::prelude::*;
+use std::ffi::CString;
+
+use ahash::AHashSet;
+
+use crate::process_child_object;
+use crate::tokenizer::{parse_attributes, tokenize, Token};
+
+/// Attributes holding id references; all of them accept a space-separated list
+const REFERENCE_ATTRS: &[&str] = &["for", "aria-labelledby", "aria-describedby"];
+
+/// One message per reference to an id that does not occur in `markup`
+pub(crate) fn broken_references(markup: &str) -> Vec<String> {
+    let mut ids = AHashSet::new();
+    let mut references = Vec::new();
+    for token in tokenize(markup) {
+        let Token::StartTag { name, raw, .. } = token else {
+            continue;
+        };
+        for (attr, value) in parse_attributes(raw) {
+            let Some(value) = value else { continue };
+            if attr == "id" {
+                ids.insert(value);
+            } else if REFERENCE_ATTRS.contains(&attr.as_str()) {
+                for target in value.split_whitespace() {
+                    references.push((name.clone(), attr.clone(), value.clone(), target.to_string()));
+                }
+            }
+        }
+    }
+
+    references
+        .into_iter()
+        .filter(|(_, _, _, target)| !ids.contains(target))
+        .map(|(tag, attr, value, target)| {
+            format!("<{} {}=\"{}\">: no element with id=\"{}\"", tag, attr, value, target)
+        })
+        .collect()
+}
+
+/// Emit a UserWarning for every broken reference in a finished document
+pub(crate) fn warn_broken_references(markup: &str, py: Python) -> PyResult<()> {
+    let category = py.get_type::<pyo3::exceptions::PyUserWarning>();
+    for message in broken_references(markup) {
+        PyErr::warn(py, &category, &CString::new(message)?, 1)?;
+    }
+    Ok(())
+}
+
+/// List `for` / `aria-labelledby` / `aria-describedby` references to missing ids
+///
+/// Checks the rendered element as a whole, so pass the complete page or form.
+/// Returns an empty list when every reference resolves.
+///
+/// Example:
+///   check_id_references(Div(Label("Name", for_="name"), Input(id="name")))
+///   Output: []
+#[pyfunction]
+fn check_id_references(element: PyObject, py: Python) -> PyResult<Vec<String>> {
+    let markup = process_child_object(&element, py)?;
+    Ok(broken_references(&markup))
+}
+
+/// Register the id reference check on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(check_id_references, m)?)?;
+    Ok(())
+}
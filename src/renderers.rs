@@ -0,0 +1,128 @@
+// =============================================================================
+// CHILD RENDERERS - Teach RustyTags how to render third-party types
+// =============================================================================
+//
+//   register_child_renderer(Decimal, lambda d: f"{d:,.2f}")
+//   register_child_renderer(lambda obj: hasattr(obj, "to_markdown"), render_markdown)
+//
+// `predicate` is a type (or tuple of types, checked with isinstance) or any
+// callable taking the child and returning a truthy value. `renderer` receives
+// the child and returns a str (written as-is), an HtmlString or a FrozenHtml.
+//
+// Precedence in process_child_object:
+//   1. built-in fast paths - None, HtmlString, FrozenHtml, str, bool
+//   2. registered renderers, most recently registered first
+//   3. numbers (int, float, Decimal, numpy scalars, ...)
+//   4. __html__, _repr_html_, render() protocols
+//   5. __str__
+// A registered renderer therefore overrides number formatting and a type's own
+// __html__, but str, bool and markup children can't be redirected. A global counter keeps the
+// common case (nothing registered) to one atomic load per non-fast-path child.
+
+use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyList, PyTuple, PyType};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::frozen::FrozenHtml;
+use crate::HtmlString;
+
+/// Registered (predicate, renderer) pairs in registration order
+static RENDERERS: PyOnceLock<Py<PyList>> = PyOnceLock::new();
+
+/// Number of registered renderers, so lookups can be skipped when there are none
+static RENDERER_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+fn registry(py: Python<'_>) -> &Bound<'_, PyList> {
+    RENDERERS.get_or_init(py, || PyList::empty(py).unbind()).bind(py)
+}
+
+/// Whether `predicate` is a type or a tuple of types (isinstance) rather than a callable
+fn is_type_predicate(predicate: &Bound<'_, PyAny>) -> bool {
+    predicate.is_instance_of::<PyType>()
+        || predicate
+            .downcast::<PyTuple>()
+            .is_ok_and(|types| types.iter().all(|t| t.is_instance_of::<PyType>()))
+}
+
+/// Render `child` with the first matching registered renderer, or None when none applies
+pub(crate) fn render_registered(child: &Bound<'_, PyAny>, py: Python) -> PyResult<Option<String>> {
+    if RENDERER_COUNT.load(Ordering::Relaxed) == 0 {
+        return Ok(None);
+    }
+
+    // Snapshot so a renderer may (un)register others without invalidating the walk
+    let entries: Vec<_> = registry(py).iter().collect();
+    for entry in entries.iter().rev() {
+        let (predicate, renderer) = entry.extract::<(Bound<'_, PyAny>, Bound<'_, PyAny>)>()?;
+        let matches = if is_type_predicate(&predicate) {
+            child.is_instance(&predicate)?
+        } else {
+            predicate.call1((child,))?.is_truthy()?
+        };
+        if !matches {
+            continue;
+        }
+
+        let rendered = renderer.call1((child,))?;
+        if let Ok(html) = rendered.extract::<PyRef<HtmlString>>() {
+            return Ok(Some(html.markup().into_owned()));
+        }
+        if let Ok(frozen) = rendered.downcast::<FrozenHtml>() {
+            return Ok(Some(frozen.get().as_str().to_string()));
+        }
+        if let Ok(text) = rendered.extract::<String>() {
+            return Ok(Some(text));
+        }
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "child renderer for {} must return str or HtmlString, got {}",
+            child.get_type().name()?,
+            rendered.get_type().name()?
+        )));
+    }
+    Ok(None)
+}
+
+/// Register how children matching `predicate` are rendered
+///
+/// Example:
+///   register_child_renderer(Decimal, lambda d: f"{d:.2f}")
+///   Td(Decimal("3.5"))
+///   Output: <td>3.50</td>
+#[pyfunction]
+fn register_child_renderer(predicate: &Bound<'_, PyAny>, renderer: &Bound<'_, PyAny>, py: Python) -> PyResult<()> {
+    if !is_type_predicate(predicate) && !predicate.is_callable() {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            "predicate must be a type, a tuple of types or a callable"
+        ));
+    }
+    if !renderer.is_callable() {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>("renderer must be callable"));
+    }
+    registry(py).append((predicate, renderer))?;
+    RENDERER_COUNT.store(registry(py).len(), Ordering::Relaxed);
+    Ok(())
+}
+
+/// Remove every renderer registered for `predicate`; returns whether any was removed
+#[pyfunction]
+fn unregister_child_renderer(predicate: &Bound<'_, PyAny>, py: Python) -> PyResult<bool> {
+    let registry = registry(py);
+    let mut removed = false;
+    for index in (0..registry.len()).rev() {
+        let (registered, _) = registry.get_item(index)?.extract::<(Bound<'_, PyAny>, Bound<'_, PyAny>)>()?;
+        if registered.is(predicate) || registered.eq(predicate)? {
+            registry.del_item(index)?;
+            removed = true;
+        }
+    }
+    RENDERER_COUNT.store(registry.len(), Ordering::Relaxed);
+    Ok(removed)
+}
+
+/// Register the child renderer API on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(register_child_renderer, m)?)?;
+    m.add_function(wrap_pyfunction!(unregister_child_renderer, m)?)?;
+    Ok(())
+}
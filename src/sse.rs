@@ -0,0 +1,72 @@
+// =============================================================================
+// SERVER-SENT EVENTS - Rendered elements as text/event-stream frames
+// =============================================================================
+//
+//   render_chunks_for_sse([Li("a"), Div(P("x"), "\n", P("y"))])
+//   -> ["data: <li>a</li>\n\n", "data: <div><p>x</p>\ndata: <p>y</p></div>\n\n"]
+//
+// One frame per element. The event-stream format ends a field at any line
+// break, so every line of the markup (split on \r\n, \r or \n) becomes its own
+// `data: ` line; the browser joins them back with "\n". The frames are plain
+// str, ready to write to a streaming response. For Datastar's patch events use
+// its SSE generator instead - this is the generic wire format.
+
+use pyo3::prelude::*;
+
+use crate::process_child_object;
+
+/// Format one rendered element as an event-stream frame
+fn sse_frame(markup: &str, event: Option<&str>) -> String {
+    let mut frame = String::with_capacity(markup.len() + 16);
+    if let Some(event) = event {
+        frame.push_str("event: ");
+        frame.push_str(event);
+        frame.push('\n');
+    }
+    let mut rest = markup;
+    loop {
+        let end = rest.find(['\r', '\n']).unwrap_or(rest.len());
+        frame.push_str("data: ");
+        frame.push_str(&rest[..end]);
+        frame.push('\n');
+        if end == rest.len() {
+            break;
+        }
+        let skip = if rest[end..].starts_with("\r\n") { 2 } else { 1 };
+        rest = &rest[end + skip..];
+    }
+    frame.push('\n');
+    frame
+}
+
+/// Render each element as a server-sent event frame (`data: ...\n\n`)
+///
+/// Multi-line markup gets one `data: ` line per line. `event` adds an
+/// `event: <name>` line to every frame so clients can listen with
+/// addEventListener(name, ...); it may not contain line breaks.
+///
+/// Example:
+///   render_chunks_for_sse([Li("a"), Li("b")], event="item")
+///   Output: ["event: item\ndata: <li>a</li>\n\n", "event: item\ndata: <li>b</li>\n\n"]
+#[pyfunction]
+#[pyo3(signature = (elements, event = None))]
+fn render_chunks_for_sse(elements: &Bound<'_, PyAny>, event: Option<&str>, py: Python) -> PyResult<Vec<String>> {
+    if event.is_some_and(|event| event.contains(['\r', '\n'])) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "SSE event names cannot contain line breaks",
+        ));
+    }
+
+    let mut frames = Vec::with_capacity(elements.len().unwrap_or(0));
+    for element in elements.try_iter()? {
+        let markup = process_child_object(&element?.unbind(), py)?;
+        frames.push(sse_frame(&markup, event));
+    }
+    Ok(frames)
+}
+
+/// Register the SSE helper on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(render_chunks_for_sse, m)?)?;
+    Ok(())
+}
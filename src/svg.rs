@@ -0,0 +1,97 @@
+// =============================================================================
+// STANDALONE SVG - Namespace and XML prolog for .svg files
+// =============================================================================
+//
+// Inline SVG inside HTML gets its namespace from the parser; a standalone .svg
+// file does not, so its root needs `xmlns="http://www.w3.org/2000/svg"` (and
+// `xmlns:xlink` when xlink:href is used). `render_svg()` adds whichever
+// declarations are missing to the root start tag - existing ones are left
+// untouched - and prepends the XML prolog for standalone files.
+
+use pyo3::prelude::*;
+
+use crate::process_child_object;
+use crate::tokenizer::{parse_attributes, tokenize, Token};
+use crate::HtmlString;
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+const XML_PROLOG: &str = "<?xml version=\"1.0\"?>";
+
+/// Split a leading `<?xml ...?>` prolog off `markup`
+fn split_prolog(markup: &str) -> (Option<&str>, &str) {
+    let trimmed = markup.trim_start();
+    if trimmed.starts_with("<?xml") {
+        if let Some(end) = trimmed.find("?>") {
+            return (Some(&trimmed[..end + 2]), &trimmed[end + 2..]);
+        }
+    }
+    (None, markup)
+}
+
+/// Render an `<svg>` element as a standalone SVG document
+///
+/// Example:
+///   render_svg(Svg(Circle(cx="5", cy="5", r="4"), viewBox="0 0 10 10"))
+///   Output: <?xml version="1.0"?>
+///           <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10">...</svg>
+#[pyfunction]
+#[pyo3(signature = (element, standalone = true))]
+fn render_svg(element: PyObject, standalone: bool, py: Python) -> PyResult<HtmlString> {
+    let markup = process_child_object(&element, py)?;
+    let (prolog, body) = split_prolog(&markup);
+
+    // Locate the root start tag, skipping whitespace and comments before it
+    let mut offset = 0;
+    let mut root = None;
+    for token in tokenize(body) {
+        match &token {
+            Token::Text(text) if text.trim().is_empty() => {}
+            Token::Comment(_) => {}
+            Token::StartTag { name, raw, .. } if name == "svg" => {
+                root = Some(*raw);
+                break;
+            }
+            _ => break,
+        }
+        offset += token.raw().len();
+    }
+    let Some(root) = root else {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "render_svg() expects an <svg> root element"
+        ));
+    };
+
+    let attrs = parse_attributes(root);
+    let has_attr = |name: &str| attrs.iter().any(|(attr, _)| attr.eq_ignore_ascii_case(name));
+    let mut declarations = String::new();
+    if !has_attr("xmlns") {
+        declarations.push_str(&format!(" xmlns=\"{}\"", SVG_NAMESPACE));
+    }
+    if body.contains("xlink:") && !has_attr("xmlns:xlink") {
+        declarations.push_str(&format!(" xmlns:xlink=\"{}\"", XLINK_NAMESPACE));
+    }
+
+    let insert_at = offset + "<svg".len();
+    let mut result = String::with_capacity(markup.len() + declarations.len() + XML_PROLOG.len() + 1);
+    match prolog {
+        Some(prolog) => {
+            result.push_str(prolog);
+        }
+        None if standalone => {
+            result.push_str(XML_PROLOG);
+            result.push('\n');
+        }
+        None => {}
+    }
+    result.push_str(&body[..insert_at]);
+    result.push_str(&declarations);
+    result.push_str(&body[insert_at..]);
+    Ok(HtmlString::new(result))
+}
+
+/// Register the standalone SVG renderer on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(render_svg, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,246 @@
+// =============================================================================
+// TABLES - Data tables from lists of records, with optional footer totals
+// =============================================================================
+//
+//   table_from_records([{"item": "Tea", "amount": 3}, {"item": "Cake", "amount": 4}],
+//                      totals={"amount": "sum"})
+//   -> <table><thead><tr><th>item</th><th>amount</th></tr></thead>
+//      <tbody><tr><td>Tea</td><td>3</td></tr><tr><td>Cake</td><td>4</td></tr></tbody>
+//      <tfoot><tr><td></td><td>7</td></tr></tfoot></table>
+//
+// Columns default to the keys of the first record, in order; a missing key or
+// None renders an empty cell. Cells, headers and totals use the normal child
+// conversion, so numbers format like `Td(3.5)`. Aggregates are computed here
+// over the numeric values of a column (int, float and number-like objects such
+// as Decimal or numpy scalars - bool is not a number), skipping None:
+//   sum, avg, min, max   blank when the column holds non-numeric values or none
+//   count                non-None values, any type
+// Integer sums stay exact until they overflow i64, then continue as floats.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyInt, PyString};
+
+use crate::{
+    build_html_tag_with_datastar, process_child_object, process_kwargs, AttrMap, DatastarProcessor, HtmlString,
+};
+
+/// Footer aggregate for one column
+#[derive(Debug, Clone, Copy)]
+enum Aggregate {
+    Sum,
+    Avg,
+    Count,
+    Min,
+    Max,
+}
+
+impl Aggregate {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value {
+            "sum" => Ok(Aggregate::Sum),
+            "avg" => Ok(Aggregate::Avg),
+            "count" => Ok(Aggregate::Count),
+            "min" => Ok(Aggregate::Min),
+            "max" => Ok(Aggregate::Max),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown aggregate: {:?} (expected \"sum\", \"avg\", \"count\", \"min\" or \"max\")",
+                value
+            ))),
+        }
+    }
+}
+
+/// A numeric cell value: exact for Python ints that fit i64, f64 otherwise
+fn numeric_value(value: &Bound<'_, PyAny>) -> Option<(Option<i64>, f64)> {
+    if value.is_instance_of::<PyBool>() || value.is_instance_of::<PyString>() {
+        return None;
+    }
+    if value.is_instance_of::<PyInt>() {
+        let exact = value.extract::<i64>().ok();
+        return Some((exact, value.extract::<f64>().ok()?));
+    }
+    value.extract::<f64>().ok().map(|float| (None, float))
+}
+
+/// The footer value for a column, or None for a blank cell
+fn aggregate<'py>(
+    aggregate: Aggregate,
+    values: &[Bound<'py, PyAny>],
+    py: Python<'py>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let present: Vec<&Bound<'py, PyAny>> = values.iter().filter(|value| !value.is_none()).collect();
+    if let Aggregate::Count = aggregate {
+        return Ok(Some(present.len().into_pyobject(py)?.into_any()));
+    }
+
+    let mut numbers = Vec::with_capacity(present.len());
+    for value in &present {
+        match numeric_value(value) {
+            Some(number) => numbers.push(number),
+            None => return Ok(None),
+        }
+    }
+    if numbers.is_empty() {
+        return Ok(None);
+    }
+
+    let result = match aggregate {
+        Aggregate::Sum => {
+            let exact = numbers.iter().try_fold(0i64, |total, (exact, _)| total.checked_add((*exact)?));
+            match exact {
+                Some(total) => total.into_pyobject(py)?.into_any(),
+                None => numbers.iter().map(|(_, float)| float).sum::<f64>().into_pyobject(py)?.into_any(),
+            }
+        }
+        Aggregate::Avg => {
+            let total: f64 = numbers.iter().map(|(_, float)| float).sum();
+            (total / numbers.len() as f64).into_pyobject(py)?.into_any()
+        }
+        Aggregate::Min | Aggregate::Max => {
+            // Return the original object so it renders exactly like its cell
+            let mut best = 0;
+            for (index, (_, float)) in numbers.iter().enumerate() {
+                let better = match aggregate {
+                    Aggregate::Min => *float < numbers[best].1,
+                    _ => *float > numbers[best].1,
+                };
+                if better {
+                    best = index;
+                }
+            }
+            present[best].clone()
+        }
+        Aggregate::Count => unreachable!(),
+    };
+    Ok(Some(result))
+}
+
+/// Append `<tag>content</tag>` for each cell of a row
+fn push_row(out: &mut String, cell_tag: &str, cells: &[String]) {
+    out.push_str("<tr>");
+    for cell in cells {
+        out.push('<');
+        out.push_str(cell_tag);
+        out.push('>');
+        out.push_str(cell);
+        out.push_str("</");
+        out.push_str(cell_tag);
+        out.push('>');
+    }
+    out.push_str("</tr>");
+}
+
+/// Render a list of dicts as a table, with an optional footer of column totals
+///
+/// `columns` picks and orders the keys (default: the first record's keys).
+/// `totals` maps column keys to "sum", "avg", "count", "min" or "max" and adds
+/// a `<tfoot>` row; columns without an aggregate, and numeric aggregates over
+/// non-numeric columns, get a blank cell. `totals_label` fills the first
+/// footer cell when that column has no aggregate. Other keywords become
+/// attributes of the `<table>`.
+///
+/// Example:
+///   table_from_records(rows, totals={"amount": "sum"}, totals_label="Total", cls="report")
+///   Output: <table class="report"><thead>...</thead><tbody>...</tbody>
+///           <tfoot><tr><td>Total</td><td>7</td></tr></tfoot></table>
+#[pyfunction]
+#[pyo3(signature = (records, columns = None, totals = None, totals_label = None, **attrs))]
+fn table_from_records(
+    records: &Bound<'_, PyAny>,
+    columns: Option<Vec<String>>,
+    totals: Option<&Bound<'_, PyDict>>,
+    totals_label: Option<Py<PyAny>>,
+    attrs: Option<&Bound<'_, PyDict>>,
+    py: Python,
+) -> PyResult<HtmlString> {
+    let mut rows = Vec::new();
+    for record in records.try_iter()? {
+        let record = record?;
+        let record = record.downcast::<PyDict>().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+                "table_from_records() expects dict records, got {}",
+                record.get_type().name().map(|name| name.to_string()).unwrap_or_default()
+            ))
+        })?;
+        rows.push(record.clone());
+    }
+
+    let columns = match columns {
+        Some(columns) => columns,
+        None => match rows.first() {
+            Some(first) => first.keys().iter().map(|key| key.str()?.extract::<String>()).collect::<PyResult<_>>()?,
+            None => Vec::new(),
+        },
+    };
+
+    let mut aggregates = Vec::new();
+    if let Some(totals) = totals {
+        for (column, name) in totals.iter() {
+            let column = column.extract::<String>()?;
+            if !columns.contains(&column) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "totals names column {:?}, which is not in the table",
+                    column
+                )));
+            }
+            aggregates.push((column, Aggregate::parse(&name.extract::<String>()?)?));
+        }
+    }
+
+    let mut body = String::with_capacity(64 + rows.len() * columns.len() * 16);
+    if !columns.is_empty() {
+        body.push_str("<thead>");
+        push_row(&mut body, "th", &columns);
+        body.push_str("</thead>");
+    }
+    body.push_str("<tbody>");
+
+    let mut column_values: Vec<Vec<Bound<'_, PyAny>>> = vec![Vec::new(); columns.len()];
+    for row in &rows {
+        let mut cells = Vec::with_capacity(columns.len());
+        for (index, column) in columns.iter().enumerate() {
+            let value = row.get_item(column)?.unwrap_or_else(|| py.None().into_bound(py));
+            // None and missing keys are empty cells, whatever none_children says
+            let cell = if value.is_none() { String::new() } else { process_child_object(&value.clone().unbind(), py)? };
+            cells.push(cell);
+            column_values[index].push(value);
+        }
+        push_row(&mut body, "td", &cells);
+    }
+    body.push_str("</tbody>");
+
+    if totals.is_some() {
+        let mut cells = Vec::with_capacity(columns.len());
+        for (index, column) in columns.iter().enumerate() {
+            let cell = match aggregates.iter().find(|(name, _)| name == column) {
+                Some((_, kind)) => match aggregate(*kind, &column_values[index], py)? {
+                    Some(total) => process_child_object(&total.unbind(), py)?,
+                    None => String::new(),
+                },
+                None if index == 0 => match &totals_label {
+                    Some(label) => process_child_object(label, py)?,
+                    None => String::new(),
+                },
+                None => String::new(),
+            };
+            cells.push(cell);
+        }
+        body.push_str("<tfoot>");
+        push_row(&mut body, "td", &cells);
+        body.push_str("</tfoot>");
+    }
+
+    let mut table_attrs = AttrMap::default();
+    let mut datastar_attrs = AttrMap::default();
+    if let Some(attrs) = attrs {
+        process_kwargs(attrs, &DatastarProcessor::new(), &mut table_attrs, &mut datastar_attrs, py)?;
+    }
+    let content = HtmlString::new(body).into_pyobject(py)?.into_any().unbind();
+    build_html_tag_with_datastar("table", vec![content], &table_attrs, &datastar_attrs, py)
+}
+
+/// Register the table helper on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(table_from_records, m)?)?;
+    Ok(())
+}
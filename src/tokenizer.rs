@@ -0,0 +1,341 @@
+// =============================================================================
+// TOKENIZER - Lightweight, non-validating scan of rendered markup
+// =============================================================================
+//
+// Splits markup into doctype/comment/tag/text tokens that borrow from the
+// input. It is deliberately forgiving: a stray `<` that doesn't start a tag is
+// kept as text, and unterminated constructs run to the end of the input.
+// Content of raw-text elements (script, style, textarea, title and any
+// registered ones) is emitted as a single RawText token so formatters never
+// look inside it.
+//
+// `tokenize()` exposes the same scan to Python as a list of HtmlToken objects,
+// and `count_nodes()` counts elements from it.
+
+use memchr::memchr;
+use pyo3::prelude::*;
+
+use crate::config::render_config;
+use crate::elements::{is_escapable_raw_text_element, is_raw_text_element};
+use crate::self_close_suffix;
+
+/// A single piece of markup; `raw` slices reproduce the input exactly
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token<'a> {
+    /// `<!doctype ...>` or any other `<!...>` declaration
+    Doctype(&'a str),
+    /// `<!-- ... -->` including the delimiters
+    Comment(&'a str),
+    /// Opening tag; `name` is lowercased
+    StartTag { name: String, raw: &'a str, self_closing: bool },
+    /// Closing tag; `name` is lowercased
+    EndTag { name: String, raw: &'a str },
+    /// Character data between tags
+    Text(&'a str),
+    /// Verbatim content of a raw-text element
+    RawText(&'a str),
+}
+
+impl<'a> Token<'a> {
+    /// The exact source text of the token
+    #[inline]
+    pub(crate) fn raw(&self) -> &'a str {
+        match self {
+            Token::Doctype(raw) | Token::Comment(raw) | Token::Text(raw) | Token::RawText(raw) => raw,
+            Token::StartTag { raw, .. } | Token::EndTag { raw, .. } => raw,
+        }
+    }
+}
+
+/// Find the `>` closing a tag, skipping over quoted attribute values
+#[inline]
+fn find_tag_end(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut quote: Option<u8> = None;
+    for (offset, &b) in bytes[from..].iter().enumerate() {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None => match b {
+                b'"' | b'\'' => quote = Some(b),
+                b'>' => return Some(from + offset),
+                _ => {}
+            },
+        }
+    }
+    None
+}
+
+/// Length of the tag name starting at `from`
+#[inline]
+fn tag_name_len(bytes: &[u8], from: usize) -> usize {
+    bytes[from..]
+        .iter()
+        .take_while(|b| !b.is_ascii_whitespace() && **b != b'>' && **b != b'/')
+        .count()
+}
+
+/// Find `</name` (ASCII case-insensitive) at or after `from`
+fn find_closing_tag(input: &str, from: usize, name: &str) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let mut pos = from;
+    while let Some(offset) = memchr(b'<', &bytes[pos..]) {
+        let start = pos + offset;
+        let name_start = start + 2;
+        let name_end = name_start + name.len();
+        if bytes.get(start + 1) == Some(&b'/')
+            && name_end <= bytes.len()
+            && bytes[name_start..name_end].eq_ignore_ascii_case(name.as_bytes())
+        {
+            return Some(start);
+        }
+        pos = start + 1;
+    }
+    None
+}
+
+/// Split markup into tokens in a single forward pass
+pub(crate) fn tokenize(input: &str) -> Vec<Token<'_>> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut text_start = 0;
+
+    while let Some(offset) = memchr(b'<', &bytes[pos..]) {
+        let start = pos + offset;
+        let next = bytes.get(start + 1).copied();
+
+        let (token, end) = if input[start..].starts_with("<!--") {
+            let end = input[start + 4..].find("-->").map_or(input.len(), |i| start + 4 + i + 3);
+            (Token::Comment(&input[start..end]), end)
+        } else if next == Some(b'!') {
+            let end = memchr(b'>', &bytes[start..]).map_or(input.len(), |i| start + i + 1);
+            (Token::Doctype(&input[start..end]), end)
+        } else if next == Some(b'/') && bytes.get(start + 2).is_some_and(|b| b.is_ascii_alphabetic()) {
+            let name_len = tag_name_len(bytes, start + 2);
+            let end = find_tag_end(bytes, start).map_or(input.len(), |i| i + 1);
+            let name = input[start + 2..start + 2 + name_len].to_ascii_lowercase();
+            (Token::EndTag { name, raw: &input[start..end] }, end)
+        } else if next.is_some_and(|b| b.is_ascii_alphabetic()) {
+            let name_len = tag_name_len(bytes, start + 1);
+            let end = find_tag_end(bytes, start).map_or(input.len(), |i| i + 1);
+            let raw = &input[start..end];
+            let name = input[start + 1..start + 1 + name_len].to_ascii_lowercase();
+            let self_closing = raw.ends_with("/>");
+            (Token::StartTag { name, raw, self_closing }, end)
+        } else {
+            // Literal `<` - keep scanning, it stays part of the current text run
+            pos = start + 1;
+            continue;
+        };
+
+        if text_start < start {
+            tokens.push(Token::Text(&input[text_start..start]));
+        }
+
+        // Raw-text and escapable raw-text elements swallow everything up to
+        // their closing tag, which is then picked up by the next iteration
+        let resume = match &token {
+            Token::StartTag { name, self_closing: false, .. }
+                if is_raw_text_element(name) || is_escapable_raw_text_element(name) =>
+            {
+                find_closing_tag(input, end, name).unwrap_or(input.len())
+            }
+            _ => end,
+        };
+        tokens.push(token);
+        if end < resume {
+            tokens.push(Token::RawText(&input[end..resume]));
+        }
+        pos = resume;
+        text_start = resume;
+    }
+
+    if text_start < input.len() {
+        tokens.push(Token::Text(&input[text_start..]));
+    }
+    tokens
+}
+
+/// Split the attribute section of a start tag into (name, value) pairs
+///
+/// Bare attributes get None. Names are lowercased; values are returned as
+/// written (quotes removed, entities not decoded).
+pub(crate) fn parse_attributes(raw: &str) -> Vec<(String, Option<String>)> {
+    let mut attrs = parse_attributes_as_written(raw);
+    for (name, _) in &mut attrs {
+        name.make_ascii_lowercase();
+    }
+    attrs
+}
+
+/// Like `parse_attributes`, keeping names in their original case (SVG `viewBox`)
+pub(crate) fn parse_attributes_as_written(raw: &str) -> Vec<(String, Option<String>)> {
+    let bytes = raw.as_bytes();
+    let end = raw.strip_suffix('>').map_or(raw.len(), |r| r.len());
+    let mut pos = 1 + tag_name_len(bytes, 1).min(end.saturating_sub(1));
+    let mut attrs = Vec::new();
+
+    loop {
+        while pos < end && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b'/') {
+            pos += 1;
+        }
+        if pos >= end {
+            break;
+        }
+
+        let name_start = pos;
+        while pos < end && !bytes[pos].is_ascii_whitespace() && !matches!(bytes[pos], b'=' | b'/') {
+            pos += 1;
+        }
+        // Keep a lone '=' from looping forever
+        if pos == name_start {
+            pos += 1;
+            continue;
+        }
+        let name = raw[name_start..pos].to_string();
+
+        let mut value_pos = pos;
+        while value_pos < end && bytes[value_pos].is_ascii_whitespace() {
+            value_pos += 1;
+        }
+        if value_pos >= end || bytes[value_pos] != b'=' {
+            attrs.push((name, None));
+            continue;
+        }
+        value_pos += 1;
+        while value_pos < end && bytes[value_pos].is_ascii_whitespace() {
+            value_pos += 1;
+        }
+
+        let value = match bytes.get(value_pos) {
+            Some(&quote @ (b'"' | b'\'')) if value_pos < end => {
+                let value_start = value_pos + 1;
+                let value_end = memchr(quote, &bytes[value_start..end]).map_or(end, |i| value_start + i);
+                pos = (value_end + 1).min(end);
+                &raw[value_start..value_end]
+            }
+            _ => {
+                let value_start = value_pos;
+                pos = value_start;
+                while pos < end && !bytes[pos].is_ascii_whitespace() {
+                    pos += 1;
+                }
+                &raw[value_start..pos]
+            }
+        };
+        attrs.push((name, Some(value.to_string())));
+    }
+    attrs
+}
+
+/// Tag name of a start tag in its original case (SVG `foreignObject`)
+pub(crate) fn start_tag_name(raw: &str) -> &str {
+    let len = tag_name_len(raw.as_bytes(), 1).min(raw.len().saturating_sub(1));
+    &raw[1..1 + len]
+}
+
+/// Serialize a start tag from parsed attributes - the inverse of `parse_attributes`
+///
+/// Values are written double-quoted with `"` escaped; a self-closed tag ends
+/// in the render mode's suffix (`/>`, or ` />` in XHTML mode).
+pub(crate) fn write_start_tag(name: &str, attrs: &[(String, Option<String>)], self_closing: bool) -> String {
+    let mut tag = String::with_capacity(name.len() + attrs.len() * 16 + 4);
+    tag.push('<');
+    tag.push_str(name);
+    for (attr, value) in attrs {
+        tag.push(' ');
+        tag.push_str(attr);
+        if let Some(value) = value {
+            tag.push_str("=\"");
+            tag.push_str(&value.replace('"', "&quot;"));
+            tag.push('"');
+        }
+    }
+    tag.push_str(if self_closing { self_close_suffix(render_config().mode) } else { ">" });
+    tag
+}
+
+/// One token of markup, as returned by `tokenize()`
+///
+/// Schema:
+///   kind          "doctype", "comment", "start_tag", "end_tag", "text" or "raw_text"
+///   raw           the exact source text; joining every token's raw gives the input back
+///   name          lowercased tag name for start_tag/end_tag, otherwise None
+///   attrs         (name, value) pairs for start_tag (value None for bare attributes)
+///   self_closing  True for start tags written as `<tag/>`
+#[pyclass(module = "rusty_tags.core", frozen, get_all)]
+pub struct HtmlToken {
+    kind: &'static str,
+    raw: String,
+    name: Option<String>,
+    attrs: Vec<(String, Option<String>)>,
+    self_closing: bool,
+}
+
+#[pymethods]
+impl HtmlToken {
+    fn __repr__(&self) -> String {
+        match &self.name {
+            Some(name) => format!("HtmlToken({}, {:?}, {:?})", self.kind, name, self.raw),
+            None => format!("HtmlToken({}, {:?})", self.kind, self.raw),
+        }
+    }
+}
+
+impl HtmlToken {
+    fn from_token(token: &Token<'_>) -> Self {
+        let (kind, raw, name, self_closing) = match token {
+            Token::Doctype(raw) => ("doctype", *raw, None, false),
+            Token::Comment(raw) => ("comment", *raw, None, false),
+            Token::StartTag { name, raw, self_closing } => ("start_tag", *raw, Some(name.clone()), *self_closing),
+            Token::EndTag { name, raw } => ("end_tag", *raw, Some(name.clone()), false),
+            Token::Text(raw) => ("text", *raw, None, false),
+            Token::RawText(raw) => ("raw_text", *raw, None, false),
+        };
+        let attrs = if kind == "start_tag" { parse_attributes(raw) } else { Vec::new() };
+        HtmlToken { kind, raw: raw.to_string(), name, attrs, self_closing }
+    }
+}
+
+/// Split markup (a str or HtmlString) into tokens with the crate's own
+/// non-validating tokenizer
+///
+/// Example:
+///   tokenize('<p class="x">Hi</p>')
+///   Output: [HtmlToken(start_tag, "p", ...), HtmlToken(text, "Hi"), HtmlToken(end_tag, "p", "</p>")]
+#[pyfunction(name = "tokenize")]
+fn py_tokenize(markup: PyObject, py: Python) -> PyResult<Vec<HtmlToken>> {
+    let markup = crate::process_child_object(&markup, py)?;
+    Ok(tokenize(&markup).iter().map(HtmlToken::from_token).collect())
+}
+
+/// Number of element nodes in rendered markup, e.g. for structure-size budgets
+///
+/// Every start tag counts once (void and self-closing elements included);
+/// end tags, comments and the doctype never count. With `text=True`, text runs
+/// that are not whitespace-only (including script/style content) count too.
+///
+/// Example:
+///   count_nodes(Ul(Li("a"), Li("b")))
+///   Output: 3
+#[pyfunction]
+#[pyo3(signature = (element, text = false))]
+fn count_nodes(element: PyObject, text: bool, py: Python) -> PyResult<usize> {
+    let markup = crate::process_child_object(&element, py)?;
+    Ok(tokenize(&markup)
+        .iter()
+        .filter(|token| match token {
+            Token::StartTag { .. } => true,
+            Token::Text(raw) | Token::RawText(raw) => text && !raw.trim().is_empty(),
+            _ => false,
+        })
+        .count())
+}
+
+/// Register the tokenizer API on the extension module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<HtmlToken>()?;
+    m.add_function(wrap_pyfunction!(py_tokenize, m)?)?;
+    m.add_function(wrap_pyfunction!(count_nodes, m)?)?;
+    Ok(())
+}
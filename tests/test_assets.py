@@ -0,0 +1,175 @@
+"""
+Tests for cache-busting asset URLs (cache_bust) and resource hints
+"""
+
+import pytest
+from rusty_tags import Div, Head, Html, Img, Link, P, Script, HtmlString, cache_bust, resource_hints
+
+
+class TestCacheBust:
+    """Test version query strings on local asset URLs."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_global_version(self):
+        """Test script, stylesheet and image URLs get the version."""
+        page = Head(Script(src="/app.js"), Link(rel="stylesheet", href="css/site.css"), Img(src="/logo.png"))
+        result = str(cache_bust(page, version="42"))
+        assert result == (
+            '<head><script src="/app.js?v=42"></script>'
+            '<link rel="stylesheet" href="css/site.css?v=42"></link>'
+            '<img src="/logo.png?v=42"></img></head>'
+        )
+
+    def test_versions_mapping_wins(self):
+        """Test per-path versions take precedence and unlisted paths are untouched."""
+        page = Div(Script(src="/app.js"), Script(src="/vendor.js"))
+        result = str(cache_bust(page, versions={"/app.js": "3f2a9c"}))
+        assert result == '<div><script src="/app.js?v=3f2a9c"></script><script src="/vendor.js"></script></div>'
+        result = str(cache_bust(page, version="1", versions={"/app.js": "3f2a9c"}))
+        assert result == '<div><script src="/app.js?v=3f2a9c"></script><script src="/vendor.js?v=1"></script></div>'
+
+    def test_non_local_urls_untouched(self):
+        """Test scheme, protocol-relative, empty and fragment-only URLs are left alone."""
+        for url in ["https://cdn.example.com/a.js", "//cdn.example.com/a.js", "data:text/javascript,1", "", "#x"]:
+            markup = str(Script(src=url))
+            assert str(cache_bust(Script(src=url), version="1")) == markup
+
+    def test_existing_query_and_fragment(self):
+        """Test an existing query is extended and the fragment stays last."""
+        assert str(cache_bust(Img(src="/a.png?w=200#top"), version="2")) == '<img src="/a.png?w=200&v=2#top"></img>'
+        assert str(cache_bust(Img(src="/a.png?v=1"), version="2")) == '<img src="/a.png?v=1"></img>'
+        assert str(cache_bust(Img(src="/a.png"), version="2", param="rev")) == '<img src="/a.png?rev=2"></img>'
+
+    def test_other_elements_and_attributes_untouched(self):
+        """Test anchors, text and non-URL attributes pass through verbatim."""
+        page = Div(P("See /app.js"), Link(rel="icon", href="/favicon.ico"), HtmlString('<a href="/app.js">x</a>'))
+        result = str(cache_bust(page, version="9"))
+        assert '<a href="/app.js">x</a>' in result
+        assert "<p>See /app.js</p>" in result
+        assert 'href="/favicon.ico?v=9"' in result
+
+    def test_page_links_untouched(self):
+        """Test only asset rels are rewritten; canonical, alternate and prev/next keep their URLs."""
+        page = Head(
+            Link(rel="canonical", href="/about"),
+            Link(rel="alternate", type="application/rss+xml", href="/feed.xml"),
+            Link(rel="next", href="/page/2"),
+            Link(href="/no-rel.css"),
+            Link(rel="shortcut icon", href="/favicon.ico"),
+            Link(rel="modulepreload", href="/app.mjs"),
+            Link(rel="Stylesheet", href="/site.css"),
+        )
+        result = str(cache_bust(page, version="1"))
+        for url in ["/about", "/feed.xml", "/page/2", "/no-rel.css"]:
+            assert f'href="{url}"' in result
+        for url in ["/favicon.ico", "/app.mjs", "/site.css"]:
+            assert f'href="{url}?v=1"' in result
+
+    def test_version_encoded(self):
+        """Test version values are percent-encoded."""
+        assert str(cache_bust(Script(src="/a.js"), version="1.0 beta&x")) == '<script src="/a.js?v=1.0%20beta%26x"></script>'
+
+    def test_document(self):
+        """Test a full document keeps its doctype and structure."""
+        result = str(cache_bust(Html(Head(Script(src="/a.js"))), version="1"))
+        assert result == '<!doctype html><html><head><script src="/a.js?v=1"></script></head></html>'
+
+    def test_self_closed_tags_follow_render_mode(self):
+        """Test rewritten self-closed tags keep the configured self-closing form."""
+        from rusty_tags import configure
+        assert str(cache_bust(HtmlString('<img src="/a.png"/>'), version="1")) == '<img src="/a.png?v=1"/>'
+        configure(mode="xhtml")
+        result = str(cache_bust(Head(Link(rel="icon", href="/i.png"), Img(src="/a.png")), version="1"))
+        assert result == '<head><link rel="icon" href="/i.png?v=1" /><img src="/a.png?v=1" /></head>'
+
+    def test_invalid_arguments(self):
+        """Test a missing version and bad parameter names raise ValueError."""
+        with pytest.raises(ValueError, match="version"):
+            cache_bust(Script(src="/a.js"))
+        for param in ["", "a=b", "v&x"]:
+            with pytest.raises(ValueError):
+                cache_bust(Script(src="/a.js"), version="1", param=param)
+
+
+class TestResourceHints:
+    """Test preconnect, dns-prefetch and preload link bundles."""
+
+    def test_recommended_order(self):
+        """Test preconnect comes before dns-prefetch, then preload, whatever the argument order."""
+        result = str(resource_hints(
+            preload=[{"href": "/app.js", "as": "script"}],
+            dns_prefetch=["https://api.example.com"],
+            preconnect=["https://cdn.example.com"],
+        ))
+        assert result == (
+            '<link rel="preconnect" href="https://cdn.example.com"></link>'
+            '<link rel="dns-prefetch" href="https://api.example.com"></link>'
+            '<link rel="preload" href="/app.js" as="script"></link>'
+        )
+
+    def test_entries_keep_their_order(self):
+        """Test several hints of one kind render in the order given."""
+        result = str(resource_hints(preconnect=["https://b.example", "https://a.example"]))
+        assert result.index("b.example") < result.index("a.example")
+
+    def test_empty(self):
+        """Test no hints render nothing."""
+        assert str(resource_hints()) == ""
+        assert str(resource_hints(preconnect=[], preload=[])) == ""
+
+    def test_dict_entries_add_attributes(self):
+        """Test dict entries keep href first, then their own attributes."""
+        result = str(resource_hints(
+            preconnect=[{"href": "https://fonts.gstatic.com", "crossorigin": True}],
+            preload=[{"as": "image", "href": "/hero.webp", "type": "image/webp", "fetchpriority": "high"}],
+        ))
+        assert result == (
+            '<link rel="preconnect" href="https://fonts.gstatic.com" crossorigin></link>'
+            '<link rel="preload" href="/hero.webp" as="image" type="image/webp" fetchpriority="high"></link>'
+        )
+
+    def test_font_preload_gets_crossorigin(self):
+        """Test font preloads are marked crossorigin unless the entry sets it."""
+        result = str(resource_hints(preload=[{"href": "/f.woff2", "as": "font", "type": "font/woff2"}]))
+        assert result == '<link rel="preload" href="/f.woff2" as="font" type="font/woff2" crossorigin></link>'
+        result = str(resource_hints(preload=[{"href": "/f.woff2", "as": "font", "crossorigin": "use-credentials"}]))
+        assert result == '<link rel="preload" href="/f.woff2" as="font" crossorigin="use-credentials"></link>'
+
+    def test_valid_as_values(self):
+        """Test every fetch destination is accepted for preloads."""
+        for destination in ["audio", "document", "embed", "fetch", "font", "image",
+                            "object", "script", "style", "track", "video", "worker"]:
+            result = str(resource_hints(preload=[{"href": "/x", "as": destination}]))
+            assert f'as="{destination}"' in result
+
+    def test_invalid_as_values(self):
+        """Test unknown or missing destinations raise ValueError."""
+        for destination in ["js", "css", "Script", ""]:
+            with pytest.raises(ValueError, match="Invalid preload"):
+                resource_hints(preload=[{"href": "/x", "as": destination}])
+        with pytest.raises(ValueError, match='needs an "as"'):
+            resource_hints(preload=[{"href": "/x"}])
+        with pytest.raises(ValueError, match="needs a dict"):
+            resource_hints(preload=["/x"])
+
+    def test_invalid_entries(self):
+        """Test entries without href, with rel or of the wrong type are rejected."""
+        with pytest.raises(ValueError, match="non-empty"):
+            resource_hints(preconnect=[""])
+        with pytest.raises(ValueError, match="non-empty"):
+            resource_hints(dns_prefetch=[{"crossorigin": True}])
+        with pytest.raises(ValueError, match="cannot set"):
+            resource_hints(preconnect=[{"href": "https://a", "rel": "prefetch"}])
+        with pytest.raises(TypeError, match="URL string or a dict"):
+            resource_hints(preconnect=[42])
+
+    def test_fits_in_head(self):
+        """Test the hints nest like any other children."""
+        result = str(Head(resource_hints(dns_prefetch=["https://a.example"]), Script(src="/app.js")))
+        assert result == (
+            '<head><link rel="dns-prefetch" href="https://a.example"></link>'
+            '<script src="/app.js"></script></head>'
+        )
@@ -0,0 +1,80 @@
+"""
+Tests for block/extends template inheritance
+"""
+
+import pytest
+from rusty_tags import Html, Head, Title, Body, Main, Footer, P, Div, block, extends
+
+
+def base_layout():
+    return Html(
+        Head(Title(block("title", "Site"))),
+        Body(
+            Main(block("content", P("Nothing here yet"))),
+            Footer(block("footer", "(c) Site")),
+        ),
+    )
+
+
+class TestBlock:
+    """Test block() markers."""
+
+    def test_block_renders_markers_around_default(self):
+        """Test a block wraps its default content in comment markers."""
+        assert str(block("content", P("x"))) == "<!--block:content--><p>x</p><!--/block:content-->"
+
+    def test_empty_block(self):
+        """Test a block without default content."""
+        assert str(block("sidebar")) == "<!--block:sidebar--><!--/block:sidebar-->"
+
+    def test_invalid_block_name(self):
+        """Test block names are validated."""
+        with pytest.raises(ValueError, match="Invalid block name"):
+            block("bad name")
+        with pytest.raises(ValueError):
+            block("")
+
+
+class TestExtends:
+    """Test extends() override semantics."""
+
+    def test_defaults_without_overrides(self):
+        """Test extends() with no overrides keeps defaults and drops markers."""
+        result = str(extends(base_layout()))
+        assert "<!--" not in result
+        assert "<title>Site</title>" in result
+        assert "<main><p>Nothing here yet</p></main>" in result
+
+    def test_override_replaces_block(self):
+        """Test overridden blocks replace their defaults; others keep defaults."""
+        result = str(extends(base_layout(), {"title": "About", "content": Div("Hello")}))
+        assert "<title>About</title>" in result
+        assert "<main><div>Hello</div></main>" in result
+        assert "<footer>(c) Site</footer>" in result
+
+    def test_unknown_block_raises(self):
+        """Test overriding a block the base doesn't define raises."""
+        with pytest.raises(ValueError, match="no block named 'contnet'"):
+            extends(base_layout(), {"contnet": "x"})
+
+    def test_nested_blocks(self):
+        """Test nested blocks can be overridden independently of their parent."""
+        base = Div(block("outer", P("before"), block("inner", "default")))
+        assert str(extends(base, {"inner": "custom"})) == "<div><p>before</p>custom</div>"
+        assert str(extends(base, {"outer": "replaced"})) == "<div>replaced</div>"
+
+    def test_multi_level_inheritance(self):
+        """Test keep_blocks=True allows extending an already extended layout."""
+        section = extends(base_layout(), {"title": "Docs"}, keep_blocks=True)
+        assert "<!--block:content-->" in str(section)
+        page = extends(section, {"content": P("Page body")})
+        result = str(page)
+        assert "<title>Docs</title>" in result
+        assert "<main><p>Page body</p></main>" in result
+        assert "<!--" not in result
+
+    def test_unclosed_block_raises(self):
+        """Test malformed base content is reported."""
+        from rusty_tags import HtmlString
+        with pytest.raises(ValueError, match="Unclosed block 'content'"):
+            extends(HtmlString("<div><!--block:content-->x</div>"))
@@ -0,0 +1,165 @@
+"""
+Tests for the optional RustyTags component kit (rusty_tags.components).
+
+Tests cover:
+- card() with and without footer, custom classes
+- grid() column layout, gap and validation
+- navbar() brand and link rendering
+- live_region() / alert() ARIA attributes and politeness validation
+"""
+
+import pytest
+from rusty_tags import Div, P, Span, configure, reset_config
+from rusty_tags.components import card, grid, navbar, live_region, alert
+
+
+class TestCard:
+    """Test card() component helper."""
+
+    def test_card_without_footer(self):
+        """Test card renders header and body inside an article."""
+        result = str(card("Title", "Body"))
+        assert result == (
+            '<article class="card">'
+            '<header class="card-header">Title</header>'
+            '<div class="card-body">Body</div>'
+            '</article>'
+        )
+
+    def test_card_with_footer(self):
+        """Test card renders optional footer section."""
+        result = str(card("Title", "Body", footer="Footer"))
+        assert '<footer class="card-footer">Footer</footer></article>' in result
+
+    def test_card_custom_class(self):
+        """Test card derives sub-element classes from custom base class."""
+        result = str(card("T", "B", cls="panel"))
+        assert '<article class="panel">' in result
+        assert '<header class="panel-header">' in result
+        assert '<div class="panel-body">' in result
+
+    def test_card_with_tag_children(self):
+        """Test card accepts rendered tags as title/body."""
+        result = str(card(Span("Title"), P("Text", cls="lead")))
+        assert '<header class="card-header"><span>Title</span></header>' in result
+        assert '<p class="lead">Text</p>' in result
+
+
+class TestGrid:
+    """Test grid() component helper."""
+
+    def test_grid_wraps_items(self):
+        """Test grid wraps each item in a grid cell."""
+        result = str(grid(["A", "B"], cols=2))
+        assert result == (
+            '<div class="grid" style="display:grid;grid-template-columns:repeat(2,minmax(0,1fr))">'
+            '<div class="grid-item">A</div>'
+            '<div class="grid-item">B</div>'
+            '</div>'
+        )
+
+    def test_grid_gap(self):
+        """Test grid emits gap when provided."""
+        result = str(grid([Div("x")], cols=1, gap="1rem"))
+        assert "repeat(1,minmax(0,1fr));gap:1rem" in result
+
+    def test_grid_zero_cols_raises(self):
+        """Test grid rejects zero columns."""
+        with pytest.raises(ValueError):
+            grid(["A"], cols=0)
+
+
+class TestNavbar:
+    """Test navbar() component helper."""
+
+    def test_navbar_with_links(self):
+        """Test navbar renders brand and link list."""
+        result = str(navbar("Acme", [("Home", "/"), ("Docs", "/docs")]))
+        assert result == (
+            '<nav class="navbar">'
+            '<a class="navbar-brand" href="/">Acme</a>'
+            '<ul class="navbar-links">'
+            '<li><a href="/">Home</a></li>'
+            '<li><a href="/docs">Docs</a></li>'
+            '</ul></nav>'
+        )
+
+    def test_navbar_without_links(self):
+        """Test navbar omits link list when empty."""
+        result = str(navbar("Acme", brand_href="/home"))
+        assert result == '<nav class="navbar"><a class="navbar-brand" href="/home">Acme</a></nav>'
+
+
+class TestTagPipeline:
+    """Test the layout components go through the regular tag builders."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_matches_tag_functions(self):
+        """Test component markup is what the equivalent tag calls produce."""
+        from rusty_tags import A, Article, Footer, Header, Li, Nav, Ul
+        assert str(navbar("Acme", [("Home", "/")])) == str(Nav(
+            A("Acme", cls="navbar-brand", href="/"),
+            Ul(Li(A("Home", href="/")), cls="navbar-links"),
+            cls="navbar",
+        ))
+        assert str(card("T", "B", footer="F")) == str(Article(
+            Header("T", cls="card-header"), Div("B", cls="card-body"), Footer("F", cls="card-footer"),
+            cls="card",
+        ))
+
+    def test_xhtml_mode(self):
+        """Test empty component parts follow the configured render mode."""
+        configure(mode="xhtml")
+        assert str(card("", "Body")) == (
+            '<article class="card"><header class="card-header" />'
+            '<div class="card-body">Body</div></article>'
+        )
+
+    def test_on_tag_rendered_sees_every_element(self):
+        """Test the telemetry hook is called for each element a component builds."""
+        seen = []
+        configure(on_tag_rendered=seen.append)
+        navbar("Acme", [("Home", "/")])
+        assert seen == ["a", "a", "li", "ul", "nav"]
+
+    def test_max_document_size(self):
+        """Test the document size limit covers component output."""
+        configure(max_document_size=64)
+        with pytest.raises(ValueError):
+            grid(["x" * 40, "y" * 40], cols=2)
+
+
+class TestLiveRegion:
+    """Test live_region() and alert() ARIA helpers."""
+
+    def test_polite_default(self):
+        """Test the default region is a polite status region."""
+        result = str(live_region("3 results", id="status"))
+        assert result == '<div role="status" aria-live="polite" id="status">3 results</div>'
+
+    def test_assertive_and_off(self):
+        """Test assertive regions use role="alert" and off has no role."""
+        assert str(live_region(P("Saved"), politeness="assertive")) == (
+            '<div role="alert" aria-live="assertive"><p>Saved</p></div>'
+        )
+        assert str(live_region(politeness="off")) == '<div aria-live="off"></div>'
+
+    def test_invalid_politeness(self):
+        """Test politeness values other than off/polite/assertive are rejected."""
+        for politeness in ["loud", "Polite", ""]:
+            with pytest.raises(ValueError, match="politeness"):
+                live_region("x", politeness=politeness)
+
+    def test_attrs_and_override(self):
+        """Test keyword attributes map like Div's and can override defaults."""
+        assert str(live_region("x", cls="sr-only", aria_atomic="true")) == (
+            '<div role="status" aria-live="polite" class="sr-only" aria-atomic="true">x</div>'
+        )
+        assert str(live_region("x", role="log")) == '<div role="log" aria-live="polite">x</div>'
+
+    def test_alert(self):
+        """Test alert() renders role="alert" with children and attributes."""
+        assert str(alert("Payment failed", cls="error")) == '<div role="alert" class="error">Payment failed</div>'
+        assert str(alert(Span("!"), " Oops")) == '<div role="alert"><span>!</span> Oops</div>'
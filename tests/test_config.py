@@ -0,0 +1,877 @@
+"""
+Tests for the process-wide rendering configuration.
+
+Tests cover:
+- configure() / get_config() / reset_config() round trip
+- Validation of unknown options and option types
+"""
+
+import pytest
+from rusty_tags import configure, get_config, reset_config
+
+
+class TestRenderConfig:
+    """Test configure/get_config/reset_config."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_defaults(self):
+        """Test default configuration values."""
+        config = get_config()
+        assert config["mode"] == "html"
+        assert config["self_close_empty"] is False
+        assert config["svg_self_closing"] is False
+        assert config["warn_deprecated_aliases"] is False
+        assert config["strict"] is False
+        assert config["profile"] is False
+        assert config["trailing_newline"] is False
+        assert config["guard_event_handlers"] is False
+        assert config["no_pool"] is False
+        assert config["none_children"] == "skip"
+        assert config["strip_testids"] is False
+        assert config["max_attr_value_len"] is None
+        assert config["flatten_depth"] is None
+        assert config["lowercase_attrs"] is False
+        assert config["max_document_size"] is None
+        assert config["attr_name_policy"] == "passthrough"
+        assert config["html_auto_structure"] is True
+        assert config["html_doctype"] is True
+        assert config["on_tag_rendered"] is None
+        assert config["child_method_errors"] == "propagate"
+
+    def test_configure_updates_option(self):
+        """Test configure() changes the reported option."""
+        configure(svg_self_closing=True)
+        assert get_config()["svg_self_closing"] is True
+
+    def test_reset_restores_defaults(self):
+        """Test reset_config() restores defaults."""
+        configure(svg_self_closing=True)
+        reset_config()
+        assert get_config()["svg_self_closing"] is False
+
+    def test_unknown_option_raises(self):
+        """Test unknown option names are rejected."""
+        with pytest.raises(ValueError, match="Unknown render option"):
+            configure(no_such_option=True)
+
+    def test_invalid_option_leaves_config_untouched(self):
+        """Test a failing configure() call applies none of its options."""
+        with pytest.raises(ValueError):
+            configure(svg_self_closing=True, no_such_option=True)
+        assert get_config()["svg_self_closing"] is False
+
+    def test_wrong_type_raises(self):
+        """Test option values are type checked."""
+        with pytest.raises(TypeError):
+            configure(svg_self_closing="yes")
+
+
+class TestDeprecatedAliasWarnings:
+    """Test opt-in DeprecationWarning for nonstandard attribute aliases."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_no_warning_by_default(self):
+        """Test aliases render silently unless warnings are enabled."""
+        import warnings
+        from rusty_tags import Div
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            result = str(Div("x", klass="a"))
+        assert result == '<div class="a">x</div>'
+        assert caught == []
+
+    def test_warns_for_class_aliases(self):
+        """Test htmlClass/klass warn and suggest cls."""
+        from rusty_tags import Div
+        configure(warn_deprecated_aliases=True)
+        with pytest.warns(DeprecationWarning, match="'klass' is deprecated, use 'cls'"):
+            result = str(Div("x", klass="a"))
+        assert result == '<div class="a">x</div>'
+        with pytest.warns(DeprecationWarning, match="'htmlClass'"):
+            Div("x", htmlClass="a")
+
+    def test_warns_for_for_aliases(self):
+        """Test fr/htmlFor warn and suggest for_."""
+        from rusty_tags import Label
+        configure(warn_deprecated_aliases=True)
+        with pytest.warns(DeprecationWarning, match="use 'for_'"):
+            result = str(Label("Email", fr="email"))
+        assert result == '<label for="email">Email</label>'
+
+    def test_standard_names_do_not_warn(self):
+        """Test cls/for_ never warn."""
+        import warnings
+        from rusty_tags import Label
+        configure(warn_deprecated_aliases=True)
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            Label("Email", cls="a", for_="email")
+        assert caught == []
+
+    def test_warning_filter_error_raises(self):
+        """Test warnings escalated to errors propagate out of the tag call."""
+        import warnings
+        from rusty_tags import Div
+        configure(warn_deprecated_aliases=True)
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            with pytest.raises(DeprecationWarning):
+                Div("x", klass="a")
+
+
+class TestConfigAcrossThreads:
+    """Test every thread sees configure()/reset_config() changes."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_changes_reach_other_threads(self):
+        """Test a thread that already rendered picks up later changes."""
+        import threading
+        from rusty_tags import Div
+        seen = []
+        step = threading.Event()
+        done = threading.Event()
+
+        def worker():
+            seen.append(str(Div(id="a")))
+            step.set()
+            done.wait()
+            seen.append(str(Div(id="a")))
+
+        thread = threading.Thread(target=worker)
+        thread.start()
+        step.wait()
+        configure(mode="xhtml")
+        done.set()
+        thread.join()
+        assert seen == ['<div id="a"></div>', '<div id="a" />']
+
+    def test_reset_reaches_this_thread(self):
+        """Test reset_config() invalidates the snapshot of the calling thread."""
+        from rusty_tags import Div
+        configure(strip_testids=True)
+        assert str(Div(data_testid="x")) == "<div></div>"
+        reset_config()
+        assert str(Div(data_testid="x")) == '<div data-testid="x"></div>'
+
+
+class TestSelfCloseEmpty:
+    """Test self_close_empty in HTML vs XML mode."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_default_renders_end_tags(self):
+        """Test childless elements keep their end tag by default."""
+        from rusty_tags import Div
+        assert str(Div(id="a")) == '<div id="a"></div>'
+
+    def test_ignored_in_html_mode(self):
+        """Test self_close_empty never applies in HTML mode."""
+        from rusty_tags import Div
+        configure(self_close_empty=True)
+        assert str(Div(id="a")) == '<div id="a"></div>'
+
+    def test_xml_mode_self_closes_empty(self):
+        """Test childless elements self-close in XML mode."""
+        from rusty_tags import Div, Span, CustomTag
+        configure(mode="xml", self_close_empty=True)
+        assert str(Div(id="a")) == '<div id="a"/>'
+        assert str(CustomTag("item", key="1")) == '<item key="1"/>'
+        assert str(Div(Span(cls="x"), id="a")) == '<div id="a"><span class="x"/></div>'
+
+    def test_xml_mode_keeps_non_empty(self):
+        """Test elements with children keep their end tag in XML mode."""
+        from rusty_tags import Div
+        configure(mode="xml", self_close_empty=True)
+        assert str(Div("text", id="a")) == '<div id="a">text</div>'
+
+    def test_xml_mode_alone_changes_nothing(self):
+        """Test XML mode without self_close_empty keeps end tags."""
+        from rusty_tags import Div
+        configure(mode="xml")
+        assert str(Div(id="a")) == '<div id="a"></div>'
+
+    def test_invalid_mode(self):
+        """Test unknown modes raise and leave the config untouched."""
+        with pytest.raises(ValueError, match="Unknown render mode"):
+            configure(mode="sgml")
+        assert get_config()["mode"] == "html"
+
+
+class TestXhtmlMode:
+    """Test XHTML mode self-closing every childless element."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_mode_reported(self):
+        """Test xhtml is accepted and reported as the mode."""
+        configure(mode="xhtml")
+        assert get_config()["mode"] == "xhtml"
+
+    def test_childless_elements_self_close(self):
+        """Test container, void and custom tags all self-close with a space."""
+        from rusty_tags import Div, Span, P, Br, Img, Input, CustomTag
+        configure(mode="xhtml")
+        assert str(Div(id="a")) == '<div id="a" />'
+        assert str(Span(cls="x")) == '<span class="x" />'
+        assert str(P(id="p")) == '<p id="p" />'
+        assert str(Br(id="b")) == '<br id="b" />'
+        assert str(Img(src="a.png", alt="")) == '<img src="a.png" alt="" />'
+        assert str(Input(type="text", name="q")) == '<input type="text" name="q" />'
+        assert str(CustomTag("item", key="1")) == '<item key="1" />'
+
+    def test_tags_without_arguments(self):
+        """Test bare tags render as empty elements with a space."""
+        from rusty_tags import Div, Br
+        configure(mode="xhtml")
+        assert str(Div()) == '<div />'
+        assert str(Br()) == '<br />'
+
+    def test_svg_leaves(self):
+        """Test SVG leaves self-close without svg_self_closing."""
+        from rusty_tags import Svg, Circle
+        configure(mode="xhtml")
+        result = str(Svg(Circle(cx="5", cy="5", r="4"), viewBox="0 0 10 10"))
+        assert result == '<svg viewBox="0 0 10 10"><circle cx="5" cy="5" r="4" /></svg>'
+
+    def test_non_empty_keep_end_tags(self):
+        """Test elements with children keep their end tag."""
+        from rusty_tags import Div, Span, Ul, Li
+        configure(mode="xhtml")
+        assert str(Div("text", id="a")) == '<div id="a">text</div>'
+        assert str(Div(Span(cls="x"), id="a")) == '<div id="a"><span class="x" /></div>'
+        assert str(Ul(Li("one"), Li(id="two"))) == '<ul><li>one</li><li id="two" /></ul>'
+
+    def test_empty_string_child_is_empty(self):
+        """Test children that render to nothing leave the element childless."""
+        from rusty_tags import Div
+        configure(mode="xhtml")
+        assert str(Div("", id="a")) == '<div id="a" />'
+        assert str(Div(None, id="a")) == '<div id="a" />'
+
+    def test_self_close_empty_not_needed(self):
+        """Test self_close_empty is implied and does not change the spacing."""
+        from rusty_tags import Div
+        configure(mode="xhtml", self_close_empty=True)
+        assert str(Div(id="a")) == '<div id="a" />'
+
+    def test_html_mode_unchanged(self):
+        """Test switching back to html restores end tags."""
+        from rusty_tags import Div
+        configure(mode="xhtml")
+        configure(mode="html")
+        assert str(Div(id="a")) == '<div id="a"></div>'
+
+
+class TestRenderProfile:
+    """Test opt-in per-tag render profiling."""
+
+    def setup_method(self, method):
+        from rusty_tags import reset_profile
+        reset_profile()
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_profile
+        reset_config()
+        reset_profile()
+
+    def test_nothing_recorded_when_off(self):
+        """Test no timings are collected by default."""
+        from rusty_tags import Div, render_profile
+        Div("x", id="a")
+        assert render_profile() == {}
+
+    def test_records_calls_per_tag(self):
+        """Test each tag type accumulates calls and time."""
+        from rusty_tags import Div, Li, Ul, render_profile
+        configure(profile=True)
+        Div(Ul(Li("a"), Li("b"), Li("c")), id="main")
+        profile = render_profile()
+        assert profile["li"]["calls"] == 3
+        assert profile["ul"]["calls"] == 1
+        assert profile["div"]["calls"] == 1
+        assert all(entry["ns"] >= 0 for entry in profile.values())
+
+    def test_custom_and_special_tags(self):
+        """Test CustomTag, Html and OptionEl use their rendered tag names."""
+        from rusty_tags import CustomTag, Html, OptionEl, render_profile
+        configure(profile=True)
+        CustomTag("my-el", "x")
+        Html("x")
+        OptionEl("x", value="1")
+        assert {"my-el", "html", "option"} <= set(render_profile())
+
+    def test_disable_stops_recording(self):
+        """Test turning profiling off stops collection but keeps data."""
+        from rusty_tags import Div, render_profile
+        configure(profile=True)
+        Div("x")
+        configure(profile=False)
+        Div("y")
+        assert render_profile()["div"]["calls"] == 1
+
+    def test_reset_profile(self):
+        """Test reset_profile clears collected timings."""
+        from rusty_tags import Div, render_profile, reset_profile
+        configure(profile=True)
+        Div("x")
+        reset_profile()
+        assert render_profile() == {}
+
+
+class TestTrailingNewline:
+    """Test the opt-in trailing newline at document end."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_off_by_default(self):
+        """Test documents end at </html> unless enabled."""
+        from rusty_tags import Html
+        assert str(Html("x")) == "<!doctype html><html>x</html>"
+
+    def test_html_appends_newline(self):
+        """Test Html(...) ends with a single newline when enabled."""
+        from rusty_tags import Html, Div
+        configure(trailing_newline=True)
+        assert str(Html("x")) == "<!doctype html><html>x</html>\n"
+        assert str(Div("x")) == "<div>x</div>"
+
+    def test_render_to_file_option(self, tmp_path):
+        """Test render_to_file honors the argument and the config default."""
+        from rusty_tags import Div, Html, render_to_file
+        path = tmp_path / "out.html"
+        render_to_file(Div("x"), path, trailing_newline=True)
+        assert path.read_text() == "<div>x</div>\n"
+
+        configure(trailing_newline=True)
+        render_to_file(Div("x"), path)
+        assert path.read_text() == "<div>x</div>\n"
+        render_to_file(Html("x"), path)
+        assert path.read_text().endswith("</html>\n")
+        render_to_file(Div("x"), path, trailing_newline=False)
+        assert path.read_text() == "<div>x</div>"
+
+
+class TestEventHandlerGuard:
+    """Test guard_event_handlers requires unsafe_attr() for on* attributes."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_plain_handlers_allowed_by_default(self):
+        """Test onclick renders as usual while the guard is off."""
+        from rusty_tags import Button, unsafe_attr
+        assert str(Button("Go", onclick="go()")) == '<button onclick="go()">Go</button>'
+        assert str(Button("Go", **unsafe_attr("onclick", "go()"))) == '<button onclick="go()">Go</button>'
+
+    def test_plain_handler_rejected(self):
+        """Test a plain on* value raises once the guard is on."""
+        from rusty_tags import Button, Div, CustomTag
+        configure(guard_event_handlers=True)
+        with pytest.raises(ValueError, match="unsafe_attr"):
+            Button("Go", onclick="go()")
+        with pytest.raises(ValueError):
+            Div({"onmouseover": "x()"})
+        with pytest.raises(ValueError):
+            CustomTag("my-widget", onload="x()")
+
+    def test_unsafe_attr_allowed(self):
+        """Test unsafe_attr() values pass the guard, positionally or as kwargs."""
+        from rusty_tags import Button, Div, CustomTag, unsafe_attr
+        configure(guard_event_handlers=True)
+        assert str(Button("Go", **unsafe_attr("onclick", "go()"))) == '<button onclick="go()">Go</button>'
+        assert str(Div(unsafe_attr("onscroll", "s()"))) == '<div onscroll="s()"></div>'
+        assert str(CustomTag("my-widget", **unsafe_attr("onload", "x()"))) == '<my-widget onload="x()"></my-widget>'
+
+    def test_mismatched_name_rejected(self):
+        """Test an unsafe_attr value can't be reused for another attribute."""
+        from rusty_tags import Button, unsafe_attr
+        configure(guard_event_handlers=True)
+        marked = unsafe_attr("onclick", "go()")["onclick"]
+        with pytest.raises(ValueError):
+            Button("Go", onsubmit=marked)
+
+    def test_other_attributes_unaffected(self):
+        """Test Datastar on_* shorthands and non-handler attributes still work."""
+        from rusty_tags import Button
+        configure(guard_event_handlers=True)
+        assert "data-on:click" in str(Button("Go", on_click="go()"))
+        assert str(Button("Go", cls="once")) == '<button class="once">Go</button>'
+
+
+class TestNoneChildren:
+    """Test configure(none_children=...) for None children."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_skip_by_default(self):
+        """Test None children are left out by default."""
+        from rusty_tags import Div
+        assert str(Div("a", None, "b")) == "<div>ab</div>"
+
+    def test_error_mode_raises(self):
+        """Test every child path raises TypeError for None in error mode."""
+        from rusty_tags import Div, Fragment, CustomTag, Textarea, HtmlElement, render_each
+        configure(none_children="error")
+        with pytest.raises(TypeError, match="None"):
+            Div("a", None)
+        with pytest.raises(TypeError):
+            Fragment(None)
+        with pytest.raises(TypeError):
+            CustomTag("my-el", None)
+        with pytest.raises(TypeError):
+            Textarea(None)
+        with pytest.raises(TypeError):
+            HtmlElement("div", {}, [None]).to_html()
+        with pytest.raises(TypeError):
+            render_each([None])
+
+    def test_error_mode_keeps_helpers_working(self):
+        """Test when()/unless() and Page don't produce None children."""
+        from rusty_tags import Div, Page, when, unless
+        configure(none_children="error")
+        assert str(Div(when(False, "x"), unless(True, "y"), "z")) == "<div>z</div>"
+        assert "<title>T</title>" in str(Page(Div("x"), title="T"))
+
+    def test_empty_mode_keeps_item_slots(self):
+        """Test "empty" keeps a "" entry per None item where "skip" drops it."""
+        from rusty_tags import Div, render_each, render_fragment_list
+        items = ["a", None, "b"]
+        assert render_fragment_list(items) == ["a", "b"]
+        assert [str(item) for item in render_each(items)] == ["a", "b"]
+        configure(none_children="empty")
+        assert get_config()["none_children"] == "empty"
+        assert render_fragment_list(items) == ["a", "", "b"]
+        assert [str(item) for item in render_each(items)] == ["a", "", "b"]
+        assert str(Div("a", None)) == "<div>a</div>"
+
+    def test_invalid_value(self):
+        """Test unknown modes are rejected and leave the config untouched."""
+        for value in ["ignore", "Empty"]:
+            with pytest.raises(ValueError, match="none_children"):
+                configure(none_children=value)
+        assert get_config()["none_children"] == "skip"
+
+
+class BrokenHtml:
+    """Child whose __html__ raises but whose str() works."""
+
+    def __html__(self):
+        raise KeyError("missing context")
+
+    def __str__(self):
+        return "fallback"
+
+
+class TestChildMethodErrors:
+    """Test configure(child_method_errors=...) for raising markup methods."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_propagates_by_default(self):
+        """Test the original exception reaches the caller with a note."""
+        from rusty_tags import Div
+        with pytest.raises(KeyError, match="missing context") as info:
+            Div(BrokenHtml())
+        assert info.value.__notes__ == ["while rendering a BrokenHtml child with __html__()"]
+
+    def test_every_protocol_propagates(self):
+        """Test _repr_html_ and render errors propagate like __html__."""
+        from rusty_tags import Div, Fragment
+
+        class BrokenRepr:
+            def _repr_html_(self):
+                raise RuntimeError("repr failed")
+
+        class BrokenRender:
+            def render(self):
+                raise ValueError("render failed")
+
+        with pytest.raises(RuntimeError, match="repr failed") as info:
+            Div(BrokenRepr())
+        assert info.value.__notes__ == ["while rendering a BrokenRepr child with _repr_html_()"]
+        with pytest.raises(ValueError, match="render failed"):
+            Fragment(BrokenRender())
+
+    def test_fallthrough_uses_next_protocol(self):
+        """Test fallthrough skips the failing method, down to str()."""
+        from rusty_tags import Div
+
+        class RenderAfterHtml(BrokenHtml):
+            def render(self):
+                return "<b>rendered</b>"
+
+        configure(child_method_errors="fallthrough")
+        assert str(Div(BrokenHtml())) == "<div>fallback</div>"
+        assert str(Div(RenderAfterHtml())) == "<div><b>rendered</b></div>"
+
+    def test_working_methods_unaffected(self):
+        """Test non-raising methods and non-string results behave as before."""
+        from rusty_tags import Div
+
+        class Widget:
+            def __html__(self):
+                return "<i>w</i>"
+
+        class NotMarkup:
+            def __html__(self):
+                return 42
+
+            def __str__(self):
+                return "text"
+
+        assert str(Div(Widget())) == "<div><i>w</i></div>"
+        assert str(Div(NotMarkup())) == "<div>text</div>"
+
+    def test_invalid_value(self):
+        """Test unknown values are rejected and leave the config untouched."""
+        with pytest.raises(ValueError, match="child_method_errors"):
+            configure(child_method_errors="ignore")
+        assert get_config()["child_method_errors"] == "propagate"
+
+
+class TestStripTestIds:
+    """Test configure(strip_testids=True) drops data-testid attributes."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_kept_by_default(self):
+        """Test data-testid is rendered unless stripping is enabled."""
+        from rusty_tags import Button, testid
+        assert str(Button("Go", **testid("submit"))) == '<button data-testid="submit">Go</button>'
+        assert str(Button("Go", data_testid="submit")) == '<button data-testid="submit">Go</button>'
+
+    def test_stripped_everywhere(self):
+        """Test every spelling and render path drops the attribute."""
+        from rusty_tags import Button, Div, HtmlString, testid
+        configure(strip_testids=True)
+        assert str(Button("Go", data_testid="x", type="submit")) == '<button type="submit">Go</button>'
+        assert str(Div(**testid("card"))) == "<div></div>"
+        assert str(Div("x", {"data-testid": "y"}, data_show="$open")) == '<div data-show="$open">x</div>'
+        assert "data-testid" not in str(HtmlString('<p data-testid="p">x</p>').parse().to_html())
+
+    def test_other_data_attributes_untouched(self):
+        """Test only data-testid is removed."""
+        from rusty_tags import Div
+        configure(strip_testids=True)
+        assert str(Div("x", data_test="a", data_testid_extra="b")) == '<div data-test="a" data-testid-extra="b">x</div>'
+
+
+class TestFlattenDepth:
+    """Test list/iterable flattening and configure(flatten_depth=...)."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_flattens_every_level_by_default(self):
+        """Test nested lists, tuples and generators are flattened into the parent."""
+        from rusty_tags import Div, Ul, Li, P, Fragment
+        assert str(Div([1, 2, P("x")])) == "<div>12<p>x</p></div>"
+        assert str(Div(["a", ("b", ["c"])])) == "<div>abc</div>"
+        assert str(Ul(Li(x) for x in "ab")) == "<ul><li>a</li><li>b</li></ul>"
+        assert str(Fragment(["x", None, "y"])) == "xy"
+
+    def test_depth_limit(self):
+        """Test lists deeper than flatten_depth use the normal conversion."""
+        from rusty_tags import Div, register_child_renderer, unregister_child_renderer
+        configure(flatten_depth=1)
+        assert str(Div(["a", ["b"]])) == "<div>a['b']</div>"
+        register_child_renderer(list, lambda items: "<ul>" + "".join(f"<li>{i}</li>" for i in items) + "</ul>")
+        try:
+            assert str(Div([["x", "y"], ["z"]])) == "<div><ul><li>x</li><li>y</li></ul><ul><li>z</li></ul></div>"
+        finally:
+            unregister_child_renderer(list)
+
+    def test_zero_disables_flattening(self):
+        """Test flatten_depth=0 renders iterables with str()."""
+        from rusty_tags import Div
+        configure(flatten_depth=0)
+        assert str(Div(["a"])) == "<div>['a']</div>"
+
+    def test_generators_are_consumed_once(self):
+        """Test a generator fills only the first element it is passed to."""
+        from rusty_tags import Div
+        items = (x for x in "ab")
+        assert str(Div(items)) == "<div>ab</div>"
+        assert str(Div("-", items)) == "<div>-</div>"
+
+    def test_self_containing_list(self):
+        """Test a cyclic list raises RecursionError instead of crashing."""
+        from rusty_tags import Div
+        cyclic = ["x"]
+        cyclic.append(cyclic)
+        with pytest.raises(RecursionError):
+            Div(cyclic)
+
+    def test_invalid_depth(self):
+        """Test negative or non-integer depths are rejected."""
+        with pytest.raises(ValueError):
+            configure(flatten_depth=-1)
+        with pytest.raises(ValueError):
+            configure(flatten_depth="1")
+
+
+class TestLowercaseAttrs:
+    """Test configure(lowercase_attrs=True) lowercases emitted attribute names."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_case_kept_by_default(self):
+        """Test attribute names are written as given unless enabled."""
+        from rusty_tags import Div
+        assert str(Div("x", dataFoo="1")) == '<div dataFoo="1">x</div>'
+
+    def test_lowercases_names(self):
+        """Test kwargs, dict attributes and Datastar attributes are lowercased."""
+        from rusty_tags import Div
+        configure(lowercase_attrs=True)
+        assert str(Div("x", dataFoo="1", ariaLabel="a")) == '<div datafoo="1" arialabel="a">x</div>'
+        assert str(Div({"DATA-X": "1"})) == '<div data-x="1"></div>'
+        assert str(Div(data_bind_userName="name")) == '<div data-bind-username="name"></div>'
+
+    def test_values_untouched(self):
+        """Test only names change, never values."""
+        from rusty_tags import Div
+        configure(lowercase_attrs=True)
+        assert str(Div(Title="Hello World")) == '<div title="Hello World"></div>'
+
+    def test_svg_camel_case_preserved(self):
+        """Test case-sensitive SVG attributes keep their camelCase spelling."""
+        from rusty_tags import Svg, LinearGradient
+        configure(lowercase_attrs=True)
+        svg = Svg(LinearGradient(gradientUnits="userSpaceOnUse", ID="g"), viewBox="0 0 10 10", preserveAspectRatio="none")
+        assert str(svg) == (
+            '<svg viewBox="0 0 10 10" preserveAspectRatio="none">'
+            '<lineargradient gradientUnits="userSpaceOnUse" id="g"></lineargradient></svg>'
+        )
+
+
+class TestMaxDocumentSize:
+    """Test the opt-in max_document_size guard on rendered output."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_unlimited_by_default(self):
+        """Test large output renders when no limit is set."""
+        from rusty_tags import Div, P
+        assert len(str(Div(*[P("x" * 100) for _ in range(1000)]))) > 100_000
+
+    def test_element_over_limit_raises(self):
+        """Test the error names the tag being built and the size reached."""
+        from rusty_tags import Div, Li, P, Ul
+        configure(max_document_size=1000)
+        assert str(Div(P("x" * 100)))
+        with pytest.raises(ValueError, match=r"while building <ul>, over max_document_size=1000"):
+            Ul(*[Li("item %d" % i) for i in range(200)])
+
+    def test_stops_while_children_accumulate(self):
+        """Test a long child list is cut off near the limit, not after the last child."""
+        from rusty_tags import Div, P
+        configure(max_document_size=1000)
+        with pytest.raises(ValueError) as info:
+            Div(*[P("x" * 50) for _ in range(10_000)])
+        reached = int(str(info.value).split("~")[1].split(" ")[0])
+        assert 1000 < reached < 1100
+
+    def test_fragment_and_document(self):
+        """Test Fragment and Html(...) documents are limited too."""
+        from rusty_tags import Body, Fragment, Html, P
+        configure(max_document_size=500)
+        with pytest.raises(ValueError, match="while building a Fragment"):
+            Fragment(*[P("paragraph") for _ in range(100)])
+        with pytest.raises(ValueError, match="max_document_size=500"):
+            Html(Body("x" * 600))
+
+    def test_invalid_limits(self):
+        """Test zero, negative and non-integer limits are rejected; None disables."""
+        from rusty_tags import Div
+        for bad in (0, -1, "10"):
+            with pytest.raises(ValueError, match="max_document_size must be a positive integer"):
+                configure(max_document_size=bad)
+        configure(max_document_size=10)
+        configure(max_document_size=None)
+        assert get_config()["max_document_size"] is None
+        assert str(Div("x" * 100)).startswith("<div>")
+
+
+class TestOnTagRendered:
+    """Test the opt-in on_tag_rendered telemetry callback."""
+
+    def teardown_method(self, method):
+        import sys
+        sys.unraisablehook = sys.__unraisablehook__
+        reset_config()
+
+    def test_called_per_element(self):
+        """Test the callback gets each finished element's tag name, innermost first."""
+        from rusty_tags import Body, CustomTag, Html, Li, Ul
+        seen = []
+        configure(on_tag_rendered=seen.append)
+        Html(Body(Ul(Li("a"), Li("b")), CustomTag("my-card", "x")))
+        assert seen == ["li", "li", "ul", "my-card", "body", "html"]
+        assert get_config()["on_tag_rendered"] == seen.append
+
+    def test_exception_does_not_break_render(self):
+        """Test a failing callback is reported as unraisable and the tag still renders."""
+        import sys
+        from rusty_tags import P
+        reported = []
+        sys.unraisablehook = lambda unraisable: reported.append(unraisable.exc_value)
+
+        def broken(tag):
+            raise RuntimeError("telemetry down")
+
+        configure(on_tag_rendered=broken)
+        assert str(P("ok")) == "<p>ok</p>"
+        assert [str(exc) for exc in reported] == ["telemetry down"]
+
+    def test_no_reentry(self):
+        """Test tags rendered inside the callback do not call it again."""
+        from rusty_tags import Div, Span
+        seen = []
+
+        def record(tag):
+            seen.append(tag)
+            Span(tag)
+
+        configure(on_tag_rendered=record)
+        Div("x")
+        assert seen == ["div"]
+
+    def test_removed_with_none_and_reset(self):
+        """Test None and reset_config() unregister the callback."""
+        from rusty_tags import Div
+        seen = []
+        configure(on_tag_rendered=seen.append)
+        configure(on_tag_rendered=None)
+        Div("x")
+        configure(on_tag_rendered=seen.append)
+        reset_config()
+        Div("x")
+        assert seen == []
+
+    def test_requires_callable(self):
+        """Test non-callables are rejected and leave the config untouched."""
+        with pytest.raises(TypeError, match="on_tag_rendered must be callable or None"):
+            configure(on_tag_rendered="log")
+        assert get_config()["on_tag_rendered"] is None
+
+
+class TestAttrNamePolicy:
+    """Test attr_name_policy handling of illegal characters in attribute names."""
+
+    EVIL = {'x" onmouseover="alert(1)': "y", "ok": "1"}
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_passthrough_by_default(self):
+        """Test names are emitted unchanged unless a policy is chosen."""
+        from rusty_tags import Div
+        assert str(Div("a", self.EVIL)) == '<div x" onmouseover="alert(1)="y" ok="1">a</div>'
+
+    def test_strip_removes_illegal_characters(self):
+        """Test whitespace, quotes, <, >, / and = are removed from names."""
+        from rusty_tags import Div, render_attrs
+        configure(attr_name_policy="strip")
+        assert str(Div("a", self.EVIL)) == '<div xonmouseoveralert(1)="y" ok="1">a</div>'
+        assert render_attrs({"a\tb<c>": "1"}) == ' abc="1"'
+
+    def test_strip_drops_names_left_empty(self):
+        """Test an attribute whose whole name is illegal is left out."""
+        from rusty_tags import Div
+        configure(attr_name_policy="strip")
+        assert str(Div("a", {'"/=': "gone"}, id="x")) == '<div id="x">a</div>'
+
+    def test_framework_syntax_is_legal(self):
+        """Test Alpine/Vue/Datastar punctuation (@ : . __) passes every policy."""
+        from rusty_tags import Div
+        attrs = {"x-on:click.prevent": "z", "@click": "w", "data-on:click__window": "x"}
+        expected = '<div x-on:click.prevent="z" @click="w" data-on:click__window="x" data-on:click="y">a</div>'
+        for policy in ("strip", "error"):
+            configure(attr_name_policy=policy)
+            assert str(Div("a", attrs, ds_on_click="y")) == expected
+
+    def test_error_raises_everywhere(self):
+        """Test the error policy covers tags, documents, render_attrs and with_attrs."""
+        from rusty_tags import Body, CustomTag, Div, Html, P, render_attrs, with_attrs
+        configure(attr_name_policy="error")
+        with pytest.raises(ValueError, match="Illegal character in attribute name"):
+            Div("a", self.EVIL)
+        with pytest.raises(ValueError, match='"a b"'):
+            Html(Body("x"), **{"a b": "1"})
+        with pytest.raises(ValueError):
+            CustomTag("x-card", **{"a>b": "1"})
+        with pytest.raises(ValueError):
+            render_attrs(self.EVIL)
+        with pytest.raises(ValueError):
+            with_attrs(P("x"), **{"a=b": "1"})
+
+    def test_unknown_policy(self):
+        """Test unknown policy names are rejected."""
+        with pytest.raises(ValueError, match="Unknown attr_name_policy"):
+            configure(attr_name_policy="escape")
+        assert get_config()["attr_name_policy"] == "passthrough"
+
+
+class TestHtmlAutoStructure:
+    """Test html_auto_structure as the process-wide default for Html(auto_structure=...)."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_disabled_globally(self):
+        """Test Html keeps the doctype but skips hoisting."""
+        from rusty_tags import Body, Html, Portal, Title
+        configure(html_auto_structure=False)
+        assert get_config()["html_auto_structure"] is False
+        assert str(Html(Body("x"), lang="en")) == '<!doctype html><html lang="en"><body>x</body></html>'
+        result = str(Html(Body(Portal("head", Title("T")))))
+        assert result.startswith("<!doctype html><html>") and "<!--portal:head-->" in result
+
+    def test_doctype_option(self):
+        """Test html_doctype=False drops the doctype on both paths."""
+        from rusty_tags import Body, Head, Html, Title
+        configure(html_doctype=False)
+        assert get_config()["html_doctype"] is False
+        assert str(Html(Head(Title("T")), Body("x"))) == "<html><head><title>T</title></head><body>x</body></html>"
+        configure(html_auto_structure=False)
+        assert str(Html(Body("x"))) == "<html><body>x</body></html>"
+        assert str(Html("x", auto_structure=True)) == "<html>x</html>"
+
+    def test_trailing_newline_on_every_path(self):
+        """Test trailing_newline applies to plain wrappers as well."""
+        from rusty_tags import Html
+        configure(trailing_newline=True)
+        assert str(Html("x")) == "<!doctype html><html>x</html>\n"
+        assert str(Html("x", auto_structure=False)) == "<html>x</html>\n"
+        configure(html_auto_structure=False)
+        assert str(Html("x")) == "<!doctype html><html>x</html>\n"
+
+    def test_call_level_wins(self):
+        """Test an explicit auto_structure keyword overrides the global setting."""
+        from rusty_tags import Html
+        configure(html_auto_structure=False)
+        assert str(Html("x", auto_structure=True)) == "<!doctype html><html>x</html>"
+        reset_config()
+        assert str(Html("x", auto_structure=False)) == "<html>x</html>"
+
+    def test_reset_restores_structure(self):
+        """Test reset_config() brings back the doctype."""
+        from rusty_tags import Html
+        configure(html_auto_structure=False)
+        reset_config()
+        assert str(Html("x")) == "<!doctype html><html>x</html>"
@@ -0,0 +1,126 @@
+"""
+Tests for request-scoped context values and the helpers that consume them
+"""
+
+import asyncio
+import pytest
+from rusty_tags import Form, Input, request_context, get_request_context, csrf_input, csp_nonce
+
+
+class TestRequestContext:
+    """Test request_context() storage semantics."""
+
+    def test_empty_outside_context(self):
+        """Test no values are visible outside a request_context block."""
+        assert get_request_context() == {}
+        assert csp_nonce() is None
+
+    def test_values_visible_inside_block(self):
+        """Test values are visible inside the block and removed after it."""
+        with request_context(csrf="abc", user="ann") as ctx:
+            assert ctx == {"csrf": "abc", "user": "ann"}
+            assert get_request_context() == {"csrf": "abc", "user": "ann"}
+        assert get_request_context() == {}
+
+    def test_nested_blocks_layer_values(self):
+        """Test nested blocks override only what they set and restore on exit."""
+        with request_context(csrf="outer", nonce="n1"):
+            with request_context(nonce="n2"):
+                assert get_request_context() == {"csrf": "outer", "nonce": "n2"}
+            assert csp_nonce() == "n1"
+
+    def test_restored_after_exception(self):
+        """Test the outer context is restored when the block raises."""
+        with pytest.raises(RuntimeError):
+            with request_context(csrf="abc"):
+                raise RuntimeError("boom")
+        assert get_request_context() == {}
+
+    def test_context_not_reentrant(self):
+        """Test entering the same context object twice raises."""
+        ctx = request_context(csrf="abc")
+        with ctx:
+            with pytest.raises(RuntimeError):
+                ctx.__enter__()
+
+    def test_isolated_between_asyncio_tasks(self):
+        """Test concurrent tasks see their own values."""
+        async def handler(token):
+            with request_context(csrf=token):
+                await asyncio.sleep(0)
+                return str(csrf_input())
+
+        async def main():
+            return await asyncio.gather(handler("one"), handler("two"))
+
+        first, second = asyncio.run(main())
+        assert 'value="one"' in first
+        assert 'value="two"' in second
+
+
+class TestContextHelpers:
+    """Test helpers reading request-scoped values."""
+
+    def test_csrf_input(self):
+        """Test csrf_input emits a hidden input with the context token."""
+        with request_context(csrf="tok123"):
+            assert str(csrf_input()) == '<input type="hidden" name="csrf_token" value="tok123"></input>'
+            assert 'name="_csrf"' in str(csrf_input("_csrf"))
+
+    def test_csrf_input_without_token(self):
+        """Test csrf_input raises outside a context with a token."""
+        with pytest.raises(ValueError, match="request_context"):
+            csrf_input()
+
+    def test_csrf_input_in_form(self):
+        """Test csrf_input composes like any other child."""
+        with request_context(csrf="t"):
+            result = str(Form(csrf_input(), Input(name="email"), method="post"))
+        assert result.startswith('<form method="post"><input type="hidden" name="csrf_token" value="t">')
+
+    def test_csp_nonce(self):
+        """Test csp_nonce returns the context nonce."""
+        with request_context(nonce="r4nd0m"):
+            assert csp_nonce() == "r4nd0m"
+
+
+class TestFormCsrf:
+    """Test Form() inserts the hidden CSRF input."""
+
+    def test_post_form_uses_context_token(self):
+        """Test non-GET forms get the context token as their first child."""
+        with request_context(csrf="t"):
+            result = str(Form(Input(name="email"), method="post"))
+        assert result == '<form method="post"><input type="hidden" name="csrf_token" value="t"></input><input name="email"></input></form>'
+
+    def test_get_forms_are_left_alone(self):
+        """Test GET and method-less forms never receive the token."""
+        with request_context(csrf="t"):
+            assert "csrf" not in str(Form(Input(name="q"), method="GET"))
+            assert "csrf" not in str(Form(Input(name="q")))
+            assert "csrf" not in str(Form(Input(name="q"), method="dialog"))
+
+    def test_explicit_token(self):
+        """Test csrf="token" inserts that token regardless of context or method."""
+        result = str(Form("x", csrf="abc", action="/a"))
+        assert result == '<form action="/a"><input type="hidden" name="csrf_token" value="abc"></input>x</form>'
+
+    def test_opt_out_and_require(self):
+        """Test csrf=False skips the input and csrf=True requires a context token."""
+        with request_context(csrf="t"):
+            assert str(Form("x", method="post", csrf=False)) == '<form method="post">x</form>'
+            assert 'value="t"' in str(Form("x", csrf=True))
+        with pytest.raises(ValueError, match="request_context"):
+            Form("x", csrf=True)
+
+    def test_no_duplicate_with_csrf_input(self):
+        """Test an explicit csrf_input() child is not inserted twice."""
+        with request_context(csrf="t"):
+            result = str(Form(csrf_input(), Input(name="email"), method="post"))
+        assert result.count('name="csrf_token"') == 1
+
+    def test_custom_field_name(self):
+        """Test request_context(csrf_field=...) renames the field everywhere."""
+        with request_context(csrf="t", csrf_field="_csrf"):
+            assert 'name="_csrf"' in str(Form(method="post"))
+            assert 'name="_csrf"' in str(csrf_input())
@@ -0,0 +1,61 @@
+"""
+Tests for the css() nested rule serializer
+"""
+
+import pytest
+from rusty_tags import Style, css
+
+
+class TestCss:
+    """Test css() serialization of nested rule dicts."""
+
+    def test_flat_rules(self):
+        """Test selectors with declarations, in insertion order."""
+        assert css({"body": {"margin": 0, "font_family": "sans-serif"}}) == (
+            "body{margin:0;font-family:sans-serif}"
+        )
+
+    def test_pseudo_selectors_and_descendants(self):
+        """Test pseudo-classes attach and other nested keys become descendants."""
+        rules = {".btn": {"color": "red", ":hover": {"color": "blue"}, "::after": {"content": "''"}, ".icon": {"width": "16px"}}}
+        assert css(rules) == (
+            ".btn{color:red}.btn:hover{color:blue}.btn::after{content:''}.btn .icon{width:16px}"
+        )
+
+    def test_ampersand_and_comma_lists(self):
+        """Test & parent references and comma-separated selector expansion."""
+        rules = {".a, .b": {"&.active": {"color": "red"}, "span, em": {"margin": 1}}}
+        assert css(rules) == (
+            ".a.active,.b.active{color:red}.a span,.a em,.b span,.b em{margin:1}"
+        )
+
+    def test_media_queries(self):
+        """Test at-rules wrap nested rules, at top level and inside a rule."""
+        rules = {
+            "@media (max-width: 600px)": {".nav": {"display": "none"}},
+            ".card": {"padding": "2rem", "@media print": {"padding": 0}},
+        }
+        assert css(rules) == (
+            "@media (max-width: 600px){.nav{display:none}}"
+            ".card{padding:2rem}@media print{.card{padding:0}}"
+        )
+
+    def test_values(self):
+        """Test numbers, custom properties and None."""
+        rules = {":root": {"--brand-color": "#f00", "line_height": 1.5, "z_index": 10, "opacity": 1.0, "color": None}}
+        assert css(rules) == ":root{--brand-color:#f00;line-height:1.5;z-index:10;opacity:1}"
+
+    def test_style_breakout_escaped(self):
+        """Test '<' can't close the surrounding <style> element."""
+        result = str(Style(css({"a": {"content": "'</style><script>'"}})))
+        assert result.count("</style>") == 1
+        assert "\\3c /style>" in result
+
+    def test_invalid_input(self):
+        """Test structural characters, top-level declarations and bad types are rejected."""
+        with pytest.raises(ValueError):
+            css({"a": {"color": "red} body{color:blue"}})
+        with pytest.raises(ValueError):
+            css({"color": "red"})
+        with pytest.raises(TypeError):
+            css({"a": {"color": ["red"]}})
@@ -0,0 +1,110 @@
+"""
+Tests for HTML email output (render_email)
+"""
+
+import pytest
+from rusty_tags import (
+    Html, Head, Body, Title, Table, Tr, Td, A, P, Img, Script, Div,
+    HtmlString, Link, render_email, conditional_comment,
+)
+
+
+class TestInlineStyles:
+    """Test class styles are inlined into style attributes."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_string_declarations(self):
+        """Test a CSS string is inlined as written, trailing ';' trimmed."""
+        result = render_email(P("Hi", cls="lead"), styles={"lead": "font-size:18px;"})
+        assert str(result) == '<p class="lead" style="font-size:18px">Hi</p>'
+
+    def test_dict_declarations(self):
+        """Test a property dict is serialized like css() declarations."""
+        result = render_email(A("Go", href="/go", cls="btn"), styles={"btn": {"background": "#0a7", "font_size": 14}})
+        assert str(result) == '<a href="/go" class="btn" style="background:#0a7;font-size:14">Go</a>'
+
+    def test_multiple_classes_and_existing_style(self):
+        """Test classes apply in attribute order and the inline style comes last."""
+        cell = Td("x", cls="pad muted", style="color:red")
+        result = render_email(cell, styles={"muted": "color:#999", "pad": "padding:8px"})
+        assert str(result) == '<td class="pad muted" style="padding:8px;color:#999;color:red">x</td>'
+
+    def test_unmatched_tags_untouched(self):
+        """Test tags without a listed class are copied byte for byte."""
+        table = Table(Tr(Td("a", cls="other"), Td(Img(src="logo.png", alt="")), width="100%"))
+        assert str(render_email(table, styles={"btn": "color:red"})) == str(table)
+
+    def test_quotes_in_values(self):
+        """Test quotes in declarations are escaped inside the attribute."""
+        result = render_email(P("x", cls="f"), styles={"f": {"font_family": '"Helvetica Neue", Arial'}})
+        assert str(result) == '<p class="f" style="font-family:&quot;Helvetica Neue&quot;, Arial">x</p>'
+
+    def test_self_closed_tags_follow_render_mode(self):
+        """Test restyled self-closed tags keep the configured self-closing form."""
+        from rusty_tags import configure
+        configure(mode="xhtml")
+        result = render_email(Img(src="logo.png", cls="logo"), styles={"logo": "border:0"})
+        assert str(result) == '<img src="logo.png" class="logo" style="border:0" />'
+
+    def test_invalid_values(self):
+        """Test dict values follow css() validation."""
+        with pytest.raises(ValueError):
+            render_email(P("x", cls="f"), styles={"f": {"color": "red;background:url(x)"}})
+        with pytest.raises(TypeError):
+            render_email(P("x", cls="f"), styles={"f": {"bold": True}})
+
+
+class TestEmailCleanup:
+    """Test scripts and doctype are removed and everything else is kept."""
+
+    def test_scripts_removed(self):
+        """Test script elements disappear with their content."""
+        result = render_email(Div(Script("track('</div>')"), P("body")))
+        assert str(result) == "<div><p>body</p></div>"
+
+    def test_doctype_removed(self):
+        """Test the doctype added by Html() is dropped."""
+        page = Html(Head(Title("Welcome")), Body(P("Hi")))
+        result = str(render_email(page))
+        assert not result.lower().startswith("<!doctype")
+        assert result.startswith("<html>") and "<title>Welcome</title>" in result
+
+    def test_conditional_comments_kept(self):
+        """Test Outlook conditional comments pass through."""
+        mso = HtmlString("<!--[if mso]><table><tr><td><![endif]--><p>x</p>")
+        assert str(render_email(mso)) == str(mso)
+
+    def test_no_styles(self):
+        """Test styles are optional."""
+        assert str(render_email(P("x", cls="lead"))) == '<p class="lead">x</p>'
+
+
+class TestConditionalComment:
+    """Test the legacy conditional comment wrapper."""
+
+    def test_wraps_markup_unescaped(self):
+        """Test the node is rendered inside the block without escaping."""
+        result = conditional_comment("lt IE 9", Link(rel="stylesheet", href="/ie.css"))
+        assert isinstance(result, HtmlString)
+        assert str(result) == '<!--[if lt IE 9]><link rel="stylesheet" href="/ie.css"></link><![endif]-->'
+        assert str(conditional_comment(" mso ", "<table>")) == "<!--[if mso]><table><![endif]-->"
+
+    def test_survives_render_email(self):
+        """Test the block passes through render_email inside a message."""
+        message = Div(conditional_comment("mso", Table(Tr(Td("x")))), P("body"))
+        expected = "<div><!--[if mso]><table><tr><td>x</td></tr></table><![endif]--><p>body</p></div>"
+        assert str(render_email(message)) == expected
+
+    def test_invalid_condition(self):
+        """Test conditions that would break the comment syntax are rejected."""
+        for condition in ["", "  ", "IE]", "gt IE 8>", "x--y"]:
+            with pytest.raises(ValueError):
+                conditional_comment(condition, P("x"))
+
+    def test_content_cannot_close_comment(self):
+        """Test content ending the comment early is rejected."""
+        with pytest.raises(ValueError, match="-->"):
+            conditional_comment("mso", HtmlString("<!-- note --><p>x</p>"))
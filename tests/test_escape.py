@@ -0,0 +1,68 @@
+"""
+Tests for scoped custom escaping of text children (escape_with)
+"""
+
+import asyncio
+import pytest
+from rusty_tags import Div, P, Span, Textarea, Safe, HtmlString, escape_with
+
+
+def jinja_escape(text):
+    return text.replace("{{", "{{ '{{' }}")
+
+
+class TestEscapeWith:
+    """Test escape_with() applies a custom escape to plain text children."""
+
+    def test_default_leaves_text_alone(self):
+        """Test text children are unchanged outside an escape_with block."""
+        assert str(Div("{{ x }}")) == "<div>{{ x }}</div>"
+
+    def test_plain_strings_escaped_in_block(self):
+        """Test every plain string child built in the block goes through func."""
+        with escape_with(jinja_escape):
+            html = Div(P("{{ x }}"), Span("plain"))
+        assert str(html) == "<div><p>{{ '{{' }} x }}</p><span>plain</span></div>"
+        assert str(Div("{{ x }}")) == "<div>{{ x }}</div>"
+
+    def test_markup_children_untouched(self):
+        """Test HtmlString and Safe children are not passed to func."""
+        with escape_with(str.upper):
+            html = Div(HtmlString("<b>keep</b>"), Safe("<i>"), "text", 3)
+        assert str(html) == "<div><b>keep</b>&lt;i&gt;TEXT3</div>"
+
+    def test_textarea_uses_custom_escape(self):
+        """Test textarea content uses the custom function instead of RCDATA escaping."""
+        with escape_with(str.upper):
+            assert str(Textarea("a<b")) == "<textarea>A<B</textarea>"
+        assert str(Textarea("a<b")) == "<textarea>a&lt;b</textarea>"
+
+    def test_none_restores_default_in_nested_block(self):
+        """Test escape_with(None) switches the override off for a nested block."""
+        with escape_with(str.upper):
+            with escape_with(None):
+                assert str(P("x")) == "<p>x</p>"
+            assert str(P("x")) == "<p>X</p>"
+
+    def test_non_callable_rejected(self):
+        """Test only callables (or None) are accepted."""
+        with pytest.raises(TypeError):
+            escape_with("upper")
+
+    def test_non_str_result_rejected(self):
+        """Test func must return a str."""
+        with escape_with(len):
+            with pytest.raises(TypeError):
+                P("abc")
+
+    def test_isolated_per_task(self):
+        """Test concurrent asyncio tasks don't see each other's function."""
+        async def render(func):
+            with escape_with(func):
+                await asyncio.sleep(0)
+                return str(P("x"))
+
+        async def main():
+            return await asyncio.gather(render(str.upper), render(lambda s: s * 2))
+
+        assert asyncio.run(main()) == ["<p>X</p>", "<p>xx</p>"]
@@ -0,0 +1,383 @@
+"""
+Tests for pretty-print / minify rendering passes
+"""
+
+import pytest
+from rusty_tags import Div, P, Pre, Script, Style, Textarea, Ul, Li, Br, Span, Fragment, HtmlString, render_variants, strip_comments, render_pretty, strip_tags
+
+
+class TestRenderVariants:
+    """Test render_variants() producing pretty and minified output in one pass."""
+
+    def test_both_variants_by_default(self):
+        """Test the default call returns both keys."""
+        result = render_variants(Div(P("Hi")))
+        assert set(result) == {"pretty", "minified"}
+        assert str(result["pretty"]) == "<div>\n  <p>Hi</p>\n</div>"
+        assert str(result["minified"]) == "<div><p>Hi</p></div>"
+
+    def test_single_variant(self):
+        """Test disabling one variant omits its key."""
+        assert set(render_variants(Div("x"), minified=False)) == {"pretty"}
+        assert set(render_variants(Div("x"), pretty=False)) == {"minified"}
+
+    def test_no_variant_raises(self):
+        """Test requesting nothing is an error."""
+        with pytest.raises(ValueError):
+            render_variants(Div("x"), pretty=False, minified=False)
+
+    def test_pretty_nesting(self):
+        """Test nested elements are indented per level."""
+        result = render_variants(Div(Ul(Li("a"), Li("b")), id="main"), minified=False)
+        assert str(result["pretty"]) == (
+            '<div id="main">\n'
+            '  <ul>\n'
+            '    <li>a</li>\n'
+            '    <li>b</li>\n'
+            '  </ul>\n'
+            '</div>'
+        )
+
+    def test_pretty_empty_and_void(self):
+        """Test empty elements stay on one line and void tags don't indent."""
+        result = render_variants(Fragment(HtmlString("<div></div>"), HtmlString("<hr>"), Span("x")), minified=False)
+        assert str(result["pretty"]) == "<div></div>\n<hr>\n<span>x</span>"
+
+    def test_minify_collapses_whitespace(self):
+        """Test whitespace runs collapse and indentation disappears."""
+        markup = HtmlString("<div>\n  <p>Hello   \n world</p>\n  <!-- note -->\n</div>")
+        result = render_variants(markup, pretty=False)
+        assert str(result["minified"]) == "<div><p>Hello world</p></div>"
+
+    def test_minify_keeps_conditional_comments(self):
+        """Test IE conditional comments survive minification."""
+        markup = HtmlString("<div><!--[if IE]><p>old</p><![endif]--></div>")
+        assert str(render_variants(markup, pretty=False)["minified"]) == str(markup)
+
+    def test_pre_is_verbatim(self):
+        """Test <pre> content is untouched by both passes."""
+        element = Div(Pre("line 1\n    line 2\n"))
+        result = render_variants(element)
+        assert "<pre>line 1\n    line 2\n</pre>" in str(result["pretty"])
+        assert str(result["minified"]) == "<div><pre>line 1\n    line 2\n</pre></div>"
+
+    def test_raw_text_is_verbatim(self):
+        """Test script and textarea content is never reformatted."""
+        code = "if (a < b) {\n  go();\n}"
+        element = Div(Script(code), Textarea("  keep\n  this  "))
+        result = render_variants(element)
+        assert f"<script>{code}</script>" in str(result["pretty"])
+        assert f"<script>{code}</script>" in str(result["minified"])
+        assert "<textarea>  keep\n  this  </textarea>" in str(result["minified"])
+
+    def test_variants_are_html_strings(self):
+        """Test results keep the HtmlString content type."""
+        result = render_variants(Div(Br()))
+        assert hasattr(result["pretty"], "__html__")
+        assert hasattr(result["minified"], "__html__")
+
+
+class TestRenderPretty:
+    """Test render_pretty() and its indent unit."""
+
+    def test_default_two_spaces(self):
+        """Test the default indent matches render_variants()."""
+        element = Div(P("Hi"))
+        assert str(render_pretty(element)) == "<div>\n  <p>Hi</p>\n</div>"
+        assert str(render_pretty(element)) == str(render_variants(element)["pretty"])
+
+    def test_int_indent_is_spaces(self):
+        """Test an int indent writes that many spaces per level."""
+        result = render_pretty(Div(Ul(Li("a"))), indent=4)
+        assert str(result) == "<div>\n    <ul>\n        <li>a</li>\n    </ul>\n</div>"
+        assert str(render_pretty(Div(P("x")), indent=0)) == "<div>\n<p>x</p>\n</div>"
+
+    def test_tab_indent(self):
+        """Test a str indent is used literally."""
+        result = render_pretty(Div(Ul(Li("a"))), indent="\t")
+        assert str(result) == "<div>\n\t<ul>\n\t\t<li>a</li>\n\t</ul>\n</div>"
+
+    def test_render_variants_indent(self):
+        """Test render_variants() accepts the same indent argument."""
+        variants = render_variants(Div(P("Hi")), indent="\t")
+        assert str(variants["pretty"]) == "<div>\n\t<p>Hi</p>\n</div>"
+        assert str(variants["minified"]) == "<div><p>Hi</p></div>"
+
+    def test_pre_is_not_indented(self):
+        """Test <pre> content stays verbatim with a custom indent."""
+        result = render_pretty(Div(Pre("a\n  b")), indent="\t")
+        assert "<pre>a\n  b</pre>" in str(result)
+
+    def test_invalid_indent(self):
+        """Test non-whitespace strings, negative ints and other types are rejected."""
+        with pytest.raises(ValueError, match="spaces and tabs"):
+            render_pretty(Div("x"), indent="--")
+        with pytest.raises(ValueError, match="spaces and tabs"):
+            render_pretty(Div("x"), indent="\n")
+        with pytest.raises(ValueError):
+            render_pretty(Div("x"), indent=-1)
+        with pytest.raises(TypeError):
+            render_pretty(Div("x"), indent=1.5)
+        with pytest.raises(TypeError):
+            render_pretty(Div("x"), indent=True)
+
+
+class TestStripComments:
+    """Test strip_comments() removing comments from rendered output."""
+
+    def test_removes_comments(self):
+        """Test ordinary comments are removed and everything else is untouched."""
+        markup = HtmlString("<div>\n  <!-- TODO: remove -->\n  <p>Hi <!--x--> there</p>\n</div>")
+        assert str(strip_comments(markup)) == "<div>\n  \n  <p>Hi  there</p>\n</div>"
+
+    def test_keeps_marked_and_conditional_comments(self):
+        """Test <!--! --> and IE conditional comments survive."""
+        markup = (
+            "<!--! (c) Example -->"
+            "<!--[if IE]><link rel=stylesheet href=ie.css><![endif]-->"
+            "<!--[if !IE]><!--><p>modern</p><!--<![endif]-->"
+        )
+        assert str(strip_comments(HtmlString(markup))) == markup
+
+    def test_script_content_untouched(self):
+        """Test comment-like text inside script is not a comment."""
+        element = Div(Script('var s = "<!-- not a comment -->";'))
+        assert str(strip_comments(element)) == str(element)
+
+    def test_accepts_elements_and_returns_html_string(self):
+        """Test any child is accepted and the result is an HtmlString."""
+        result = strip_comments(Fragment(HtmlString("<!-- a -->"), P("x")))
+        assert isinstance(result, HtmlString)
+        assert str(result) == "<p>x</p>"
+
+    def test_minify_uses_same_rules(self):
+        """Test minified output keeps the same comments."""
+        markup = HtmlString("<div><!-- note --><!--! keep --></div>")
+        assert str(render_variants(markup, pretty=False)["minified"]) == "<div><!--! keep --></div>"
+
+
+class TestStripTags:
+    """Test strip_tags() text extraction."""
+
+    def test_removes_tags(self):
+        """Test inline tags join their text without extra spaces."""
+        assert strip_tags(P("Hello ", Span("big"), " world")) == "Hello big world"
+        assert strip_tags(P(Span("un"), Span("break"))) == "unbreak"
+
+    def test_block_tags_separate_words(self):
+        """Test block-level boundaries become a single space."""
+        assert strip_tags(Div(P("a"), P("b"), Ul(Li("c"), Li("d")))) == "a b c d"
+        assert strip_tags(Fragment("line", Br(), "next")) == "line next"
+
+    def test_decodes_entities(self):
+        """Test named and numeric entities are decoded, unknown ones kept."""
+        html = HtmlString("<p>Tom &amp; Jerry &lt;3 &#169; &#x2014; &quot;hi&quot; &bogus; a&b</p>")
+        assert strip_tags(html) == 'Tom & Jerry <3 \u00a9 \u2014 "hi" &bogus; a&b'
+
+    def test_collapses_whitespace(self):
+        """Test whitespace runs collapse and the ends are trimmed."""
+        html = HtmlString("<div>\n  <p>  many \n\t spaces  </p>\n</div>\n")
+        assert strip_tags(html) == "many spaces"
+
+    def test_drops_script_style_and_comments(self):
+        """Test script/style content and comments never reach the text."""
+        html = Div(Script("var x = '<p>no</p>';"), Style("p { color: red }"),
+                   HtmlString("<!-- note -->"), P("yes"))
+        assert strip_tags(html) == "yes"
+
+    def test_keeps_textarea_text(self):
+        """Test escaped raw-text content is decoded."""
+        assert strip_tags(Textarea("a < b")) == "a < b"
+
+    def test_returns_plain_str(self):
+        """Test the result is text, not markup."""
+        result = strip_tags(P("&lt;b&gt;"))
+        assert type(result) is str
+        assert result == "<b>"
+
+
+class TestElementCategories:
+    """Test the void / raw-text introspection API."""
+
+    def test_builtin_void_tags(self):
+        """Test spec void elements are reported, case-insensitively."""
+        from rusty_tags import is_void_tag
+        for name in ["br", "img", "input", "meta", "link", "hr", "wbr", "IMG"]:
+            assert is_void_tag(name)
+        for name in ["div", "span", "script", "textarea"]:
+            assert not is_void_tag(name)
+
+    def test_builtin_raw_text_tags(self):
+        """Test raw-text elements are reported."""
+        from rusty_tags import is_raw_text_tag
+        for name in ["script", "style", "Script"]:
+            assert is_raw_text_tag(name)
+        for name in ["div", "pre", "br", "textarea", "title"]:
+            assert not is_raw_text_tag(name)
+
+    def test_builtin_escapable_raw_text_tags(self):
+        """Test textarea and title are escapable raw text, matching how they render."""
+        from rusty_tags import is_escapable_raw_text_tag, Textarea
+        for name in ["textarea", "title", "TextArea"]:
+            assert is_escapable_raw_text_tag(name)
+        for name in ["script", "style", "div"]:
+            assert not is_escapable_raw_text_tag(name)
+        assert str(Textarea("<b>")) == "<textarea>&lt;b></textarea>"
+
+    def test_escapable_raw_text_not_tokenized_as_markup(self):
+        """Test the formatter keeps textarea content verbatim."""
+        markup = HtmlString("<textarea>  <b>not   markup</b>  </textarea>")
+        assert str(render_variants(markup, pretty=False)["minified"]) == str(markup)
+
+    def test_register_void_tag(self):
+        """Test registered void tags are reported and honored by the formatter."""
+        from rusty_tags import is_void_tag, register_void_tag
+        register_void_tag("X-Spacer")
+        assert is_void_tag("x-spacer")
+        markup = HtmlString("<div><x-spacer><p>a</p></div>")
+        pretty = str(render_variants(markup, minified=False)["pretty"])
+        assert pretty == "<div>\n  <x-spacer>\n  <p>a</p>\n</div>"
+
+    def test_register_raw_text_tag(self):
+        """Test registered raw-text tags keep their content verbatim."""
+        from rusty_tags import is_raw_text_tag, register_raw_text_tag
+        register_raw_text_tag("x-code")
+        assert is_raw_text_tag("x-code")
+        markup = HtmlString("<x-code>  <b>not   markup</b>  </x-code>")
+        assert str(render_variants(markup, pretty=False)["minified"]) == str(markup)
+
+    def test_register_invalid_name(self):
+        """Test invalid tag names are rejected."""
+        from rusty_tags import register_void_tag
+        with pytest.raises(ValueError):
+            register_void_tag("")
+        with pytest.raises(ValueError):
+            register_void_tag("bad tag")
+
+    def test_builtin_boolean_attrs(self):
+        """Test spec boolean attributes, including inert, are reported."""
+        from rusty_tags import is_boolean_attr
+        for name in ["disabled", "checked", "inert", "open", "Readonly", "shadowrootclonable"]:
+            assert is_boolean_attr(name)
+        for name in ["popover", "popovertarget", "draggable", "value"]:
+            assert not is_boolean_attr(name)
+
+    def test_boolean_attr_values(self):
+        """Test "" and the attribute's own name render bare; True/False as usual."""
+        from rusty_tags import Div, Input
+        assert str(Div("x", inert=True)) == "<div inert>x</div>"
+        assert str(Div("x", inert=False)) == "<div>x</div>"
+        assert str(Input(disabled="disabled", readonly="")) == "<input disabled readonly></input>"
+        assert str(Input(checked="Checked")) == "<input checked></input>"
+        # The value is compared with the attribute's rendered name, not the keyword
+        assert str(Input(readonly_="readonly", for_="for")) == '<input readonly for="for"></input>'
+        # Other values are left alone, and non-boolean attributes keep ""
+        assert str(Div("x", hidden="until-found")) == '<div hidden="until-found">x</div>'
+        assert str(Input(value="")) == '<input value=""></input>'
+
+    def test_popover_attrs(self):
+        """Test popover keywords and popovertarget ids render as strings."""
+        from rusty_tags import Button, Div
+        assert str(Div("x", popover=True)) == "<div popover>x</div>"
+        assert str(Div("x", popover="manual")) == '<div popover="manual">x</div>'
+        button = Button("Open", popovertarget="menu", popovertargetaction="toggle")
+        assert str(button) == '<button popovertarget="menu" popovertargetaction="toggle">Open</button>'
+
+    def test_register_boolean_attrs(self):
+        """Test registered boolean attributes are reported and rendered bare."""
+        from rusty_tags import Div, is_boolean_attr, register_boolean_attrs, unregister_boolean_attrs
+        register_boolean_attrs(["X-Collapsed", "writingsuggestionsoff"])
+        try:
+            assert is_boolean_attr("x-collapsed")
+            assert str(Div("x", x_collapsed="")) == "<div x-collapsed>x</div>"
+            with pytest.raises(ValueError):
+                register_boolean_attrs(["bad name"])
+        finally:
+            unregister_boolean_attrs(["X-Collapsed", "writingsuggestionsoff"])
+        assert not is_boolean_attr("x-collapsed")
+        assert str(Div("x", x_collapsed="")) == '<div x-collapsed="">x</div>'
+
+    def test_unregister_boolean_attrs(self):
+        """Test only registered names are removed; built-ins stay boolean."""
+        from rusty_tags import is_boolean_attr, register_boolean_attrs, unregister_boolean_attrs
+        register_boolean_attrs(["x-folded"])
+        assert unregister_boolean_attrs(["X-Folded"]) is True
+        assert unregister_boolean_attrs(["x-folded", "inert"]) is False
+        assert is_boolean_attr("inert")
+
+
+class TestTokenize:
+    """Test the tokenize() low-level API."""
+
+    def test_token_kinds(self):
+        """Test each construct maps to its token kind."""
+        from rusty_tags import tokenize
+        tokens = tokenize('<!doctype html><!-- c --><p class="x">Hi</p>')
+        assert [t.kind for t in tokens] == ["doctype", "comment", "start_tag", "text", "end_tag"]
+        assert tokens[2].name == "p"
+        assert tokens[4].name == "p"
+        assert tokens[3].name is None
+
+    def test_raw_round_trip(self):
+        """Test joining raw text reproduces the input."""
+        from rusty_tags import tokenize
+        markup = '<div id="a">x < y<script>if (a<b) {}</script><br/></div>'
+        assert "".join(t.raw for t in tokenize(markup)) == markup
+
+    def test_attributes(self):
+        """Test quoted, unquoted and bare attributes."""
+        from rusty_tags import tokenize
+        token = tokenize("""<input TYPE="text" value='a > b' size=10 disabled>""")[0]
+        assert token.attrs == [("type", "text"), ("value", "a > b"), ("size", "10"), ("disabled", None)]
+        assert token.self_closing is False
+
+    def test_self_closing_and_raw_text(self):
+        """Test self-closing flags and raw-text content."""
+        from rusty_tags import tokenize
+        tokens = tokenize('<circle r="1"/><style>p > a {}</style>')
+        assert tokens[0].self_closing is True
+        assert tokens[0].attrs == [("r", "1")]
+        assert [t.kind for t in tokens[1:]] == ["start_tag", "raw_text", "end_tag"]
+        assert tokens[2].raw == "p > a {}"
+
+    def test_accepts_html_string(self):
+        """Test rendered elements can be tokenized directly."""
+        from rusty_tags import tokenize
+        tokens = tokenize(Div(Span("x"), cls="c"))
+        assert [t.name for t in tokens if t.kind == "start_tag"] == ["div", "span"]
+        assert tokens[0].attrs == [("class", "c")]
+
+
+class TestDiff:
+    """Test diff() structural patches between renders."""
+
+    def test_identical_renders(self):
+        """Test no patches for equal markup."""
+        from rusty_tags import diff
+        assert diff(Div("x"), Div("x")) == []
+
+    def test_nested_text_change(self):
+        """Test unchanged start tags are descended into."""
+        from rusty_tags import diff
+        patches = diff(Ul(Li("a"), Li("b")), Ul(Li("a"), Li("c")))
+        assert patches == [{"op": "replace", "path": [0, 1, 0], "html": "c"}]
+
+    def test_attribute_change_replaces_element(self):
+        """Test a changed start tag replaces the whole element."""
+        from rusty_tags import diff
+        patches = diff(Div(Span("x", cls="a")), Div(Span("x", cls="b")))
+        assert patches == [{"op": "replace", "path": [0, 0], "html": '<span class="b">x</span>'}]
+
+    def test_insert_and_remove(self):
+        """Test extra siblings become inserts, missing ones removes (last first)."""
+        from rusty_tags import diff
+        grown = diff(Ul(Li("a")), Ul(Li("a"), Li("b")))
+        assert grown == [{"op": "insert", "path": [0, 1], "html": "<li>b</li>"}]
+        shrunk = diff(Ul(Li("a"), Li("b"), Li("c")), Ul(Li("a")))
+        assert shrunk == [{"op": "remove", "path": [0, 2]}, {"op": "remove", "path": [0, 1]}]
+
+    def test_top_level_fragment(self):
+        """Test top-level siblings of a fragment are diffed individually."""
+        from rusty_tags import diff
+        patches = diff(Fragment(P("1"), Br(), P("2")), Fragment(P("1"), Br(), P("3")))
+        assert patches == [{"op": "replace", "path": [2, 0], "html": "3"}]
@@ -0,0 +1,51 @@
+"""
+Tests for precompiled static markup (freeze / FrozenHtml)
+"""
+
+import pytest
+from rusty_tags import Div, P, Ul, Li, Fragment, CustomTag, HtmlString, FrozenHtml, freeze, render_each
+
+
+class TestFreeze:
+    """Test freeze() produces immutable markup that embeds as-is."""
+
+    def test_freeze_renders_once(self):
+        """Test the frozen content equals the element's rendering."""
+        frozen = freeze(Div(P("x"), cls="logo"))
+        assert isinstance(frozen, FrozenHtml)
+        assert frozen.content == '<div class="logo"><p>x</p></div>'
+        assert str(frozen) == frozen.__html__() == frozen.content
+        assert len(frozen) == len(frozen.content)
+
+    def test_embeds_in_tags_and_fragments(self):
+        """Test frozen children are embedded unchanged everywhere children are accepted."""
+        item = freeze(Li("static"))
+        assert str(Ul(item, Li("dynamic"), item)) == "<ul><li>static</li><li>dynamic</li><li>static</li></ul>"
+        assert str(Fragment(item, "x")) == "<li>static</li>x"
+        assert str(CustomTag("my-list", item)) == "<my-list><li>static</li></my-list>"
+        assert [str(s) for s in render_each([item])] == ["<li>static</li>"]
+
+    def test_accepts_any_child(self):
+        """Test strings, HtmlString and other FrozenHtml objects can be frozen."""
+        assert freeze("text").content == "text"
+        assert freeze(HtmlString("<b>x</b>")).content == "<b>x</b>"
+        frozen = freeze(P("x"))
+        assert freeze(frozen) == frozen
+
+    def test_equality_and_hash(self):
+        """Test FrozenHtml compares and hashes like its markup string."""
+        frozen = freeze(P("x"))
+        assert frozen == "<p>x</p>"
+        assert frozen != "<p>y</p>"
+        assert hash(frozen) == hash("<p>x</p>")
+        assert len({frozen, freeze(P("x"))}) == 1
+
+    def test_immutable(self):
+        """Test content can't be reassigned."""
+        frozen = freeze(P("x"))
+        with pytest.raises(AttributeError):
+            frozen.content = "<p>y</p>"
+
+    def test_encode(self):
+        """Test encode() returns UTF-8 bytes."""
+        assert freeze(P("ü")).encode() == "<p>ü</p>".encode()
@@ -108,6 +108,120 @@ class TestBooleanAttributes:
         result = str(Input(type="text", required=False))
         assert "required" not in result
 
+    def test_dialog_open(self):
+        """Test Dialog's open attribute is bare when True and omitted when False."""
+        from rusty_tags import Dialog
+        assert str(Dialog("Saved", open=True)) == "<dialog open>Saved</dialog>"
+        assert str(Dialog("Saved", open=False)) == "<dialog>Saved</dialog>"
+        assert str(Dialog("Saved", id="confirm")) == '<dialog id="confirm">Saved</dialog>'
+
+    def test_contenteditable_tri_state(self):
+        """Test contenteditable renders True/False as "true"/"false", never bare or omitted."""
+        assert str(Div("x", contenteditable=True)) == '<div contenteditable="true">x</div>'
+        assert str(Div("x", contenteditable=False)) == '<div contenteditable="false">x</div>'
+        assert str(Div("x", contenteditable="")) == '<div contenteditable="">x</div>'
+        assert str(Div("x", contenteditable="plaintext-only")) == '<div contenteditable="plaintext-only">x</div>'
+        assert str(Div("x", contenteditable=None)) == "<div>x</div>"
+
+    def test_true_false_keyword_attributes(self):
+        """Test draggable and spellcheck follow the same "true"/"false" rule on every path."""
+        from rusty_tags import render_attrs
+        assert str(Span("x", draggable=True)) == '<span draggable="true">x</span>'
+        assert str(Div("x", {"spellcheck": False})) == '<div spellcheck="false">x</div>'
+        assert render_attrs({"contentEditable": True}) == ' contentEditable="true"'
+
+
+class TestEmptyAttributeValues:
+    """Test None (dropped), "" (empty value) and False/True (boolean) attribute values."""
+
+    def test_kwargs(self):
+        """Test the three cases side by side on a tag function."""
+        result = str(Input(value="", title=None, disabled=True, hidden=False))
+        assert result == '<input value="" disabled></input>'
+
+    def test_empty_string_is_not_bare(self):
+        """Test "" renders attr="" rather than a bare attribute."""
+        assert str(Img(src="a.png", alt="")) == '<img src="a.png" alt=""></img>'
+
+    def test_positional_dict_and_ordered_attrs(self):
+        """Test dict children and ordered_attrs follow the same rules."""
+        assert str(Div("x", {"title": "", "lang": None, "hidden": True})) == '<div title="" hidden>x</div>'
+        result = str(Div("x", ordered_attrs=[("data-a", ""), ("data-b", None), ("data-c", False)]))
+        assert result == '<div data-a="">x</div>'
+
+    def test_plain_attribute_paths(self):
+        """Test CustomTag, Html, render_attrs, TagBuilder and HtmlElement agree."""
+        from rusty_tags import CustomTag, HtmlElement, render_attrs
+        assert str(CustomTag("my-el", a="", b=None, c=True)) == '<my-el a="" c></my-el>'
+        assert str(Html(lang=None, dir="")) == '<!doctype html><html dir=""></html>'
+        assert render_attrs({"a": "", "b": None, "c": True, "d": False}) == ' a="" c'
+        assert str(Div()("x", title="", lang=None)) == '<div title="">x</div>'
+        element = HtmlElement("div", {"a": "", "b": None, "c": True}, [])
+        assert element.to_html().content == '<div a="" c></div>'
+
+    def test_cls_none_dropped(self):
+        """Test cls=None leaves out the class attribute."""
+        assert str(Div("x", cls=None)) == "<div>x</div>"
+        assert str(Div("x", cls="")) == '<div class="">x</div>'
+
+
+class TestHtmlStringAttributeValues:
+    """Test HtmlString attribute values are written like HtmlString children."""
+
+    def test_trusted_written_verbatim(self):
+        """Test pre-rendered markup is used as-is, without a str() round trip."""
+        from rusty_tags import HtmlString
+        tooltip = Span(Strong("Heads up"), " read this")
+        assert str(Div("i", data_tooltip=tooltip)) == '<div data-tooltip="<span><strong>Heads up</strong> read this</span>">i</div>'
+        assert str(Div("i", {"title": HtmlString("a &amp; b")})) == '<div title="a &amp; b">i</div>'
+
+    def test_double_quotes_kept_inside_value(self):
+        """Test a '"' in trusted markup becomes &quot; instead of ending the attribute."""
+        from rusty_tags import HtmlString
+        tooltip = Span("Read", cls="note")
+        assert str(Div("i", data_tooltip=tooltip)) == '<div data-tooltip="<span class=&quot;note&quot;>Read</span>">i</div>'
+        injected = HtmlString('x" onclick="alert(1)')
+        assert str(Div(title=injected)) == '<div title="x&quot; onclick=&quot;alert(1)"></div>'
+        assert str(Div(title=HtmlString("it's"))) == '<div title="it\'s"></div>'
+
+    def test_untrusted_escaped_once(self):
+        """Test untrusted and Safe values are escaped exactly once."""
+        from rusty_tags import HtmlString
+        assert str(Div(title=HtmlString("<b>&", trusted=False))) == '<div title="&lt;b&gt;&amp;"></div>'
+        assert str(Div(title=Safe("<b>"))) == '<div title="&lt;b&gt;"></div>'
+
+    def test_empty_html_string(self):
+        """Test an empty HtmlString is an empty value, not an omitted attribute."""
+        from rusty_tags import HtmlString
+        assert str(Div(title=HtmlString(""))) == '<div title=""></div>'
+
+
+class TestTokenListAttributes:
+    """Test collections as class, rel and sandbox values."""
+
+    def test_sets_sorted(self):
+        """Test set and frozenset members are joined in sorted order."""
+        from rusty_tags import Iframe, Link
+        assert str(Div("x", cls={"card", "active"})) == '<div class="active card">x</div>'
+        assert str(Link(rel={"stylesheet", "preload"})) == '<link rel="preload stylesheet"></link>'
+        sandbox = frozenset({"allow-scripts", "allow-forms"})
+        assert str(Iframe(sandbox=sandbox)) == '<iframe sandbox="allow-forms allow-scripts"></iframe>'
+
+    def test_lists_keep_order(self):
+        """Test list and tuple members are joined as given."""
+        assert str(Div("x", cls=["card", "active"])) == '<div class="card active">x</div>'
+        assert str(A("x", rel=("noopener", "noreferrer"))) == '<a rel="noopener noreferrer">x</a>'
+        assert str(Div("x", {"class": ["b", "a"]})) == '<div class="b a">x</div>'
+
+    def test_empty_collection_dropped(self):
+        """Test an empty collection (or only blank tokens) omits the attribute."""
+        assert str(Div("x", cls=set())) == "<div>x</div>"
+        assert str(Div("x", cls=["", " "])) == "<div>x</div>"
+
+    def test_other_attributes_unchanged(self):
+        """Test other attributes still render a collection with str()."""
+        assert str(Div("x", data_tags={"a"})) == """<div data-tags="{'a'}">x</div>"""
+
 
 class TestNestedChildren:
     """Test HTML tags with nested children."""
@@ -150,12 +264,106 @@ class TestSpecialChildren:
         result = str(Div("before", None, "after"))
         assert result == "<div>beforeafter</div>"
 
+    def test_none_children_skipped_everywhere(self):
+        """Test None children render as nothing across tag, fragment and custom paths."""
+        from rusty_tags import Fragment, CustomTag, Textarea, Ul, Li
+        assert str(Div(None)) == "<div></div>"
+        assert str(Div(None, id="a")) == '<div id="a"></div>'
+        assert str(Fragment(None, "x", None)) == "x"
+        assert str(CustomTag("my-el", None, "x")) == "<my-el>x</my-el>"
+        assert str(Textarea(None, "text")) == "<textarea>text</textarea>"
+        assert str(Ul(Li("a"), None, Li("b"))) == "<ul><li>a</li><li>b</li></ul>"
+
+    def test_none_children_in_tag_builder_call(self):
+        """Test None children are skipped when calling a TagBuilder."""
+        assert str(Div()(None, "x", cls="box")) == '<div class="box">x</div>'
+
     def test_empty_div(self):
         """Test empty Div renders proper empty element."""
         result = str(Div())
         assert result == "<div/>"
 
 
+class NumpyLikeFloat32:
+    """Stand-in for np.float32: __float__ only, single-precision itemsize, noisy repr."""
+
+    dtype = "float32"
+    itemsize = 4
+
+    def __init__(self, value):
+        import struct
+        self.value = struct.unpack("f", struct.pack("f", value))[0]
+
+    def __float__(self):
+        return self.value
+
+    def __repr__(self):
+        return f"np.float32({self.value})"
+
+
+class NumpyLikeInt64:
+    """Stand-in for np.int64: __index__ only, noisy repr."""
+
+    def __init__(self, value):
+        self.value = value
+
+    def __index__(self):
+        return self.value
+
+    def __repr__(self):
+        return f"np.int64({self.value})"
+
+
+class NumpyLikeFloat64(NumpyLikeFloat32):
+    """Stand-in for a double-precision numpy scalar that is not a float subclass."""
+
+    dtype = "float64"
+    itemsize = 8
+
+    def __init__(self, value):
+        self.value = value
+
+    def __repr__(self):
+        return f"np.float64({self.value})"
+
+
+class TestNumericCoercion:
+    """Test numpy scalars render cleanly and Decimal/Fraction stay exact."""
+
+    def test_index_objects_render_as_ints(self):
+        """Test __index__ objects are written as plain integers."""
+        assert str(Div(NumpyLikeInt64(7), data_n=NumpyLikeInt64(3))) == '<div data-n="3">7</div>'
+
+    def test_single_precision_floats_render_shortest(self):
+        """Test float32-style scalars use their own shortest form."""
+        assert str(Div(NumpyLikeFloat32(0.1), width=NumpyLikeFloat32(0.1))) == '<div width="0.1">0.1</div>'
+
+    def test_double_precision_scalars(self):
+        """Test double-precision numpy-style scalars keep full precision."""
+        assert str(Div(NumpyLikeFloat64(0.1), x=NumpyLikeFloat64(2.5))) == '<div x="2.5">0.1</div>'
+
+    def test_decimal_renders_exactly(self):
+        """Test Decimal keeps its own digits instead of going through float."""
+        from decimal import Decimal
+        assert str(Div(Decimal("19.90"))) == "<div>19.90</div>"
+        assert str(Div(Decimal("1E+2"))) == "<div>1E+2</div>"
+        assert str(Div(Decimal("12345678901234567890.12"))) == "<div>12345678901234567890.12</div>"
+        assert str(Div(x=Decimal("0.10"))) == '<div x="0.10"></div>'
+
+    def test_fraction_renders_exactly(self):
+        """Test Fraction renders as written by str(), not as a rounded float."""
+        from fractions import Fraction
+        assert str(Div(Fraction(1, 3), x=Fraction(1, 2))) == '<div x="1/2">1/3</div>'
+
+    def test_large_ints_exact(self):
+        """Test ints beyond 64 bits keep every digit."""
+        assert str(Div(2 ** 70)) == "<div>1180591620717411303424</div>"
+
+    def test_python_floats_unchanged(self):
+        """Test native floats keep full double precision."""
+        assert str(Div(0.1 + 0.2, x=1.0)) == '<div x="1.0">0.30000000000000004</div>'
+
+
 class TestHeadingTags:
     """Test all heading tags H1-H6."""
 
@@ -274,6 +482,52 @@ class TestFormTags:
         assert 'for="email-input"' in result
 
 
+class TestOptionElement:
+    """Test Option(label, value=..., selected=..., disabled=...) permutations."""
+
+    def test_selected_disabled(self):
+        """Test both flags render as bare boolean attributes or are omitted."""
+        cases = [
+            (True, True, '<option value="3" selected disabled>Three</option>'),
+            (True, False, '<option value="3" selected>Three</option>'),
+            (False, True, '<option value="3" disabled>Three</option>'),
+            (False, False, '<option value="3">Three</option>'),
+            (None, None, '<option value="3">Three</option>'),
+        ]
+        for selected, disabled, expected in cases:
+            assert str(OptionEl("Three", value=3, selected=selected, disabled=disabled)) == expected
+
+    def test_option_alias(self):
+        """Test the Option alias renders the same element."""
+        from rusty_tags import Option
+        assert str(Option("A", value="a", selected=True)) == '<option value="a" selected>A</option>'
+
+    def test_value_coercion(self):
+        """Test values are coerced to text, with booleans as data rather than flags."""
+        assert str(OptionEl("Zero", value=0)) == '<option value="0">Zero</option>'
+        assert str(OptionEl("Half", value=0.5)) == '<option value="0.5">Half</option>'
+        assert str(OptionEl("Yes", value=True)) == '<option value="true">Yes</option>'
+        assert str(OptionEl("No", value=False)) == '<option value="false">No</option>'
+        assert str(OptionEl("Empty", value="")) == '<option value="">Empty</option>'
+        assert str(OptionEl("Label only", value=None)) == "<option>Label only</option>"
+
+    def test_select_from_data(self):
+        """Test the common select-building loop."""
+        choices = [(1, "One"), (2, "Two"), (3, "Three")]
+        current, unavailable = 2, {3}
+        select = Select(*[OptionEl(label, value=v, selected=v == current, disabled=v in unavailable)
+                          for v, label in choices], name="n")
+        assert str(select) == (
+            '<select name="n"><option value="1">One</option>'
+            '<option value="2" selected>Two</option>'
+            '<option value="3" disabled>Three</option></select>'
+        )
+
+    def test_other_tags_keep_boolean_value(self):
+        """Test the coercion is specific to <option>."""
+        assert str(Input(value=True)) == "<input value></input>"
+
+
 class TestTableTags:
     """Test table structure elements."""
 
@@ -438,6 +692,21 @@ class TestDocumentTags:
         assert "</body>" in result
         assert "</html>" in result
 
+    def test_html_without_auto_structure(self):
+        """Test auto_structure=False renders a plain <html> wrapper."""
+        result = str(Html(Body("x"), lang="en", auto_structure=False))
+        assert result == '<html lang="en"><body>x</body></html>'
+
+    def test_html_without_auto_structure_keeps_portals(self):
+        """Test Portal markers stay in place when auto_structure=False."""
+        from rusty_tags import Portal
+        result = str(Html(Body(Portal("head", Title("T"))), auto_structure=False))
+        assert result == "<html><body><!--portal:head--><title>T</title><!--/portal:head--></body></html>"
+
+    def test_html_auto_structure_default(self):
+        """Test auto_structure=True is the default document behavior."""
+        assert str(Html("x", auto_structure=True)) == str(Html("x")) == "<!doctype html><html>x</html>"
+
     def test_meta_with_charset(self):
         """Test Meta with charset attribute."""
         result = str(Meta(charset="utf-8"))
@@ -471,6 +740,30 @@ class TestDocumentTags:
         assert "console.log('Hello');" in result
         assert "</script>" in result
 
+    def test_html_template_element(self):
+        """Test HtmlTemplate renders the inert <template> element."""
+        from rusty_tags import HtmlTemplate, Template, Li
+        assert HtmlTemplate is Template
+        result = str(HtmlTemplate(Li("", cls="row"), id="row-tpl"))
+        assert result == '<template id="row-tpl"><li class="row"></li></template>'
+
+    def test_slot_element(self):
+        """Test Slot renders the shadow DOM <slot> with fallback children."""
+        from rusty_tags import Slot, Template, is_known_tag
+        assert str(Slot("Untitled", name="title")) == '<slot name="title">Untitled</slot>'
+        shadow = Template(H1(Slot(name="title")), Slot(name="body"), shadowrootmode="open")
+        expected = '<template shadowrootmode="open"><h1><slot name="title"></slot></h1><slot name="body"></slot></template>'
+        assert str(shadow) == expected
+        assert str(Span("Hi", slot="title")) == '<span slot="title">Hi</span>'
+        assert is_known_tag("slot")
+
+    def test_noscript_content_is_parsed_markup(self):
+        """Test Noscript children render as normal markup, not raw text."""
+        from rusty_tags import Noscript, Img, is_raw_text_tag
+        result = str(Noscript(Img(src="/pixel.gif", alt=""), "Enable JavaScript"))
+        assert result == '<noscript><img src="/pixel.gif" alt=""></img>Enable JavaScript</noscript>'
+        assert not is_raw_text_tag("noscript")
+
 
 class TestSpecialTags:
     """Test special tags (Fragment, Safe, CustomTag)."""
@@ -553,6 +846,93 @@ class TestAttributeMapping:
         assert 'type="text"' in result
 
 
+class TestHxOn:
+    """Test hx_on={event: handler} expands to hx-on:<event> attributes."""
+
+    def test_expands_events(self):
+        """Test each entry becomes one colon-named attribute, in order."""
+        result = str(Button("Go", hx_post="/go", hx_on={"click": "start()", "htmx:afterSwap": "init()"}))
+        assert result == '<button hx-post="/go" hx-on:click="start()" hx-on:htmx:afterSwap="init()">Go</button>'
+
+    def test_double_colon_shorthand(self):
+        """Test a leading ':' gives htmx's hx-on:: shorthand."""
+        assert str(Div(hx_on={":after-request": "done()"})) == '<div hx-on::after-request="done()"></div>'
+
+    def test_none_and_false_dropped(self):
+        """Test handlers follow normal attribute value rules."""
+        assert str(Div(hx_on={"click": None, "submit": False, "load": "x()"})) == '<div hx-on:load="x()"></div>'
+
+    def test_all_attribute_paths(self):
+        """Test dict children, CustomTag and TagBuilder calls expand it too."""
+        assert str(Div({"hx_on": {"click": "a()"}})) == '<div hx-on:click="a()"></div>'
+        assert str(CustomTag("my-el", hx_on={"click": "a()"})) == '<my-el hx-on:click="a()"></my-el>'
+        assert str(Div()("x", hx_on={"click": "a()"})) == '<div hx-on:click="a()">x</div>'
+
+    def test_string_value_is_plain_attribute(self):
+        """Test a str keeps the older single hx-on attribute."""
+        assert str(Div(hx_on="click: go()")) == '<div hx-on="click: go()"></div>'
+
+    def test_event_names_must_be_str(self):
+        """Test non-str event names are rejected."""
+        with pytest.raises(TypeError, match="hx_on"):
+            Div(hx_on={1: "x()"})
+
+
+class TestNameIntrospection:
+    """Test map_attr() / normalize_tag() report the names tags actually render."""
+
+    def test_map_attr_matches_rendering(self):
+        """Test map_attr agrees with the attribute name in the rendered tag."""
+        from rusty_tags import map_attr
+        for kwarg in ["cls", "_class", "klass", "fr", "for_", "type_", "data_x", "aria_label", "http_equiv"]:
+            assert f' {map_attr(kwarg)}="v"' in str(Div("x", **{kwarg: "v"}))
+        assert map_attr("cls") == "class"
+        assert map_attr("data_x") == "data-x"
+
+    def test_map_attr_datastar(self):
+        """Test Datastar shorthands report their data-* names."""
+        from rusty_tags import map_attr
+        assert map_attr("on_click") == "data-on:click"
+        assert map_attr("show") == "data-show"
+        assert map_attr("ds_text") == "data-text"
+        assert map_attr("signals") == "data-signals"
+        assert map_attr("show") in str(Div("x", show="$open"))
+
+    def test_normalize_tag(self):
+        """Test normalize_tag matches tag function and CustomTag output."""
+        from rusty_tags import normalize_tag, CustomTag
+        assert normalize_tag("OptionEl") == "option"
+        assert normalize_tag("Div") == "div"
+        assert normalize_tag("my-el") == "my-el"
+        assert str(CustomTag("Section", "x")) == f"<{normalize_tag('Section')}>x</section>"
+
+
+class TestCountNodes:
+    """Test count_nodes() element counting."""
+
+    def test_counts_elements(self):
+        """Test every element counts once, text does not by default."""
+        from rusty_tags import count_nodes
+        assert count_nodes(Ul(Li("a"), Li("b"))) == 3
+        assert count_nodes(Div(P("x", Br(), Strong("y")), Hr())) == 5
+        assert count_nodes(Fragment(P("a"), P("b"))) == 2
+        assert count_nodes("plain text") == 0
+
+    def test_text_nodes(self):
+        """Test text=True adds non-whitespace text runs."""
+        from rusty_tags import count_nodes, HtmlString
+        assert count_nodes(Ul(Li("a"), Li("b")), text=True) == 5
+        assert count_nodes(HtmlString("<div>\n  <p>x</p>\n</div>"), text=True) == 3
+        assert count_nodes(Script("go()"), text=True) == 2
+
+    def test_ignores_comments_and_doctype(self):
+        """Test comments, the doctype and raw-text markup are not elements."""
+        from rusty_tags import count_nodes, HtmlString
+        assert count_nodes(Html(Body(P("x")))) == 3
+        assert count_nodes(HtmlString("<!-- <p>no</p> --><p>yes</p>")) == 1
+        assert count_nodes(Script("'<div></div>'")) == 1
+
+
 class TestDictAsAttrs:
     """Test dictionary as positional argument expands to attributes."""
 
@@ -599,3 +979,784 @@ class TestHtmlProtocol:
 
         result = str(Div(TemplateWidget()))
         assert "<div class='template'>Rendered</div>" in result
+
+
+class TestAttributeOrder:
+    """Test attribute emission order and the ordered_attrs keyword."""
+
+    def test_kwargs_render_in_call_order(self):
+        """Test keyword attributes render in the order they were passed."""
+        result = str(Div("x", id="a", cls="b", title="c"))
+        assert result == '<div id="a" class="b" title="c">x</div>'
+
+    def test_ordered_attrs_list_of_pairs(self):
+        """Test ordered_attrs list renders pairs in the given order."""
+        result = str(Meta(ordered_attrs=[("name", "viewport"), ("content", "width=device-width")]))
+        assert result == '<meta name="viewport" content="width=device-width"></meta>'
+
+    def test_ordered_attrs_mapping(self):
+        """Test ordered_attrs accepts a mapping."""
+        from collections import OrderedDict
+        result = str(Div("x", ordered_attrs=OrderedDict([("data-y", "2"), ("data-x", "1")])))
+        assert result == '<div data-y="2" data-x="1">x</div>'
+
+    def test_ordered_attrs_render_before_other_kwargs(self):
+        """Test ordered_attrs come first regardless of keyword position."""
+        result = str(Div("x", id="main", ordered_attrs=[("data-x", "1"), ("data-y", "2")]))
+        assert result == '<div data-x="1" data-y="2" id="main">x</div>'
+
+    def test_keyword_overrides_ordered_value_in_place(self):
+        """Test a keyword repeating an ordered name overrides the value but keeps its position."""
+        result = str(Div("x", ordered_attrs=[("title", "old"), ("data-x", "1")], title="new"))
+        assert result == '<div title="new" data-x="1">x</div>'
+
+    def test_ordered_attrs_values_are_converted(self):
+        """Test ordered_attrs values follow normal attribute conversion."""
+        result = str(Input(ordered_attrs=[("type", "checkbox"), ("checked", True), ("hidden", False), ("tabindex", 2)]))
+        assert result == '<input type="checkbox" checked tabindex="2"></input>'
+
+    def test_ordered_attrs_on_custom_tag(self):
+        """Test CustomTag supports ordered_attrs."""
+        result = str(CustomTag("my-el", "x", ordered_attrs=[("b", "2"), ("a", "1")]))
+        assert result == '<my-el b="2" a="1">x</my-el>'
+
+    def test_ordered_attrs_invalid_entries_raise(self):
+        """Test ordered_attrs entries must be pairs."""
+        with pytest.raises(TypeError, match="pairs"):
+            Div("x", ordered_attrs=["not-a-pair"])
+
+
+class TestDuplicateAttributes:
+    """Test allow_duplicate_attrs with the ordered_attrs list form."""
+
+    def test_duplicates_merged_by_default(self):
+        """Test repeated names in ordered_attrs keep only the last value."""
+        assert str(Div(ordered_attrs=[("data-x", "1"), ("data-x", "2")])) == '<div data-x="2"></div>'
+
+    def test_duplicates_allowed_explicitly(self):
+        """Test every pair is emitted, in order, when duplicates are allowed."""
+        result = Div("c", ordered_attrs=[("data-x", "1"), ("data-x", "2")], allow_duplicate_attrs=True)
+        assert str(result) == '<div data-x="1" data-x="2">c</div>'
+
+    def test_names_are_still_mapped(self):
+        """Test aliases like cls are mapped before being appended."""
+        result = Span("c", ordered_attrs=[("cls", "a"), ("cls", "b")], allow_duplicate_attrs=True)
+        assert str(result) == '<span class="a" class="b">c</span>'
+
+    def test_html_and_custom_tag(self):
+        """Test the plain-kwargs path (Html, CustomTag) supports it too."""
+        pairs = [("x", "a"), ("x", "b")]
+        assert str(CustomTag("my-el", ordered_attrs=pairs, allow_duplicate_attrs=True)) == '<my-el x="a" x="b"></my-el>'
+        assert '<html x="a" x="b">' in str(Html(ordered_attrs=pairs, allow_duplicate_attrs=True))
+
+    def test_flag_is_not_rendered(self):
+        """Test the reserved keyword never becomes an attribute."""
+        assert str(Div("c", allow_duplicate_attrs=True)) == "<div>c</div>"
+
+    def test_flag_must_be_bool(self):
+        """Test non-bool flag values are rejected."""
+        with pytest.raises(TypeError):
+            Div(ordered_attrs=[("a", "1")], allow_duplicate_attrs="yes")
+
+
+class TestTextareaContent:
+    """Test Textarea content escaping and whitespace preservation."""
+
+    def test_textarea_escapes_markup_characters(self):
+        """Test & and < in string content are escaped."""
+        result = str(Textarea("a < b && c", name="note"))
+        assert result == '<textarea name="note">a &lt; b &amp;&amp; c</textarea>'
+
+    def test_textarea_cannot_be_closed_by_content(self):
+        """Test a closing tag in content cannot break out of the textarea."""
+        result = str(Textarea("</textarea><script>x</script>", name="note"))
+        assert result.count("</textarea>") == 1
+        assert result.endswith("&lt;/script></textarea>")
+
+    def test_textarea_preserves_whitespace(self):
+        """Test spaces, tabs and newlines are kept exactly."""
+        result = str(Textarea("line 1\n    line 2\n\tline 3  ", name="note"))
+        assert result == '<textarea name="note">line 1\n    line 2\n\tline 3  </textarea>'
+
+    def test_textarea_protects_leading_newline(self):
+        """Test a leading newline survives the browser's newline stripping."""
+        result = str(Textarea("\nfirst line", name="note"))
+        assert result == '<textarea name="note">\n\nfirst line</textarea>'
+
+    def test_textarea_quotes_not_escaped(self):
+        """Test quotes and > are left as-is in textarea content."""
+        result = str(Textarea('say "hi" > \'there\''))
+        assert result == '<textarea>say "hi" > \'there\'</textarea>'
+
+    def test_textarea_html_string_child_is_trusted(self):
+        """Test HtmlString children are inserted without re-escaping."""
+        result = str(Textarea(Safe("<b>"), name="note"))
+        assert result == '<textarea name="note">&lt;b&gt;</textarea>'
+
+    def test_other_tags_are_unaffected(self):
+        """Test normal elements keep raw string children."""
+        assert str(Div("a < b")) == "<div>a < b</div>"
+
+
+class TestLargeAttributeValues:
+    """Test multi-KB attribute values render correctly and don't skew pool stats."""
+
+    def setup_method(self, method):
+        from rusty_tags import reset_pool_stats
+        reset_pool_stats()
+
+    def test_data_uri_attribute(self):
+        """Test a multi-KB inline data URI is rendered intact."""
+        from rusty_tags import Img
+        data_uri = "data:image/png;base64," + "A" * 8192
+        result = str(Img(src=data_uri, alt="x"))
+        assert result == f'<img src="{data_uri}" alt="x"></img>'
+
+    def test_json_attribute(self):
+        """Test a large JSON payload in an attribute is rendered intact."""
+        import json
+        from rusty_tags import Div
+        payload = json.dumps({f"key{i}": "v" * 20 for i in range(200)})
+        result = str(Div("x", data_config=payload))
+        assert payload in result
+        assert result.endswith(">x</div>")
+
+    def test_large_values_bypass_pool(self):
+        """Test oversized buffers bypass the pool instead of counting as misses."""
+        from rusty_tags import Div, pool_stats
+        before = pool_stats()
+        Div("x", data_blob="z" * 5000)
+        after = pool_stats()
+        assert after["bypasses"] > before["bypasses"]
+        assert after["pooled"] <= 64
+
+    def test_oversized_buffers_are_not_pooled(self):
+        """Test buffers above the pooling limit are discarded, not hoarded."""
+        from rusty_tags import Div, pool_stats
+        for _ in range(20):
+            Div(Div("y" * 4096), data_blob="z" * 4096)
+        stats = pool_stats()
+        assert stats["discards"] > 0
+        assert stats["pooled"] <= 64
+
+    def test_small_renders_recycle_buffers(self):
+        """Test intermediate buffers are returned and reused for normal renders."""
+        from rusty_tags import Div, P, pool_stats
+        for _ in range(10):
+            Div(P("hello", cls="a"), id="main")
+        stats = pool_stats()
+        assert stats["returns"] > 0
+        assert stats["hits"] > 0
+        assert stats["bypasses"] == 0
+
+
+class TestMaxAttrValueLen:
+    """Test the strict-mode max_attr_value_len guard."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_off_by_default(self):
+        """Test long values render when no limit is configured."""
+        from rusty_tags import Input
+        assert len(str(Input(value="x" * 100_000))) > 100_000
+
+    def test_limit_raises_with_attribute_name(self):
+        """Test an over-long value raises ValueError naming the attribute."""
+        from rusty_tags import Input, Div, configure
+        configure(strict=True, max_attr_value_len=16)
+        with pytest.raises(ValueError, match="'value' value is 17 bytes, over max_attr_value_len=16"):
+            Input(value="x" * 17)
+        with pytest.raises(ValueError, match="'class'"):
+            Div("x", cls="c" * 20)
+        with pytest.raises(ValueError, match="'data-blob'"):
+            Div("x", {"data_blob": b"\x00" * 20})
+        assert str(Input(value="x" * 16)) == f'<input value="{"x" * 16}"></input>'
+
+    def test_only_enforced_in_strict_mode(self):
+        """Test the limit is ignored while strict mode is off."""
+        from rusty_tags import Input, configure, get_config
+        configure(max_attr_value_len=4)
+        assert get_config()["max_attr_value_len"] == 4
+        assert str(Input(value="long value")) == '<input value="long value"></input>'
+        configure(strict=True)
+        with pytest.raises(ValueError):
+            Input(value="long value")
+
+    def test_invalid_limit(self):
+        """Test the limit must be a positive integer or None."""
+        from rusty_tags import configure, get_config
+        for bad in (0, -1, "10"):
+            with pytest.raises(ValueError):
+                configure(max_attr_value_len=bad)
+        configure(strict=True, max_attr_value_len=8)
+        configure(max_attr_value_len=None)
+        assert get_config()["max_attr_value_len"] is None
+
+
+class TestPoolEfficiency:
+    """Guard the string pool's hit ratio on a representative page workload.
+
+    A refactor that stops returning buffers to the pool shows up here as a
+    falling hit_ratio long before it shows up in benchmarks.
+    """
+
+    MIN_HIT_RATIO = 0.9
+
+    def setup_method(self, method):
+        from rusty_tags import reset_pool_stats
+        reset_pool_stats()
+
+    def render_page(self, i):
+        return Html(
+            Head(Title(f"Page {i}"), Meta(charset="utf-8")),
+            Body(
+                Nav(Ul(*[Li(A(f"Link {j}", href=f"/{j}")) for j in range(8)])),
+                Main(
+                    Table(*[Tr(Td(str(row)), Td("cell", cls="c")) for row in range(20)]),
+                    Form(Input(name="q"), Button("Go"), method="get"),
+                ),
+                cls="page",
+            ),
+        )
+
+    def test_hit_ratio_reported(self):
+        """Test hit_ratio is 1.0 before any request and within [0, 1] after."""
+        from rusty_tags import pool_stats
+        assert pool_stats()["hit_ratio"] == 1.0
+        Div(P("x"))
+        assert 0.0 <= pool_stats()["hit_ratio"] <= 1.0
+
+    def test_hit_ratio_above_threshold(self):
+        """Test rendering many pages is served almost entirely from the pool."""
+        from rusty_tags import pool_stats
+        for i in range(200):
+            self.render_page(i)
+        stats = pool_stats()
+        assert stats["hits"] + stats["misses"] > 1000
+        assert stats["hit_ratio"] >= self.MIN_HIT_RATIO, stats
+
+
+class TestNoPool:
+    """Test configure(no_pool=True) for render-once processes."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_output_unchanged(self):
+        """Test pooled and unpooled renders produce identical markup."""
+        from rusty_tags import configure
+        page = lambda: str(Div(P("x", cls="a", data_role="r"), Span("y"), id="d", on_click="go()"))
+        pooled = page()
+        configure(no_pool=True)
+        assert page() == pooled
+
+    def test_pool_untouched(self):
+        """Test no buffers are requested from or returned to the pool."""
+        from rusty_tags import configure, pool_stats, reset_pool_stats
+        configure(no_pool=True)
+        reset_pool_stats()
+        for i in range(10):
+            Div(*[P(str(j), cls="c") for j in range(10)])
+        stats = pool_stats()
+        assert (stats["hits"], stats["misses"], stats["returns"], stats["discards"]) == (0, 0, 0, 0)
+
+
+class TestNameCacheConcurrency:
+    """Test tag/attribute name caches under many threads and unique names."""
+
+    def render(self, i):
+        return str(CustomTag(f"X-Stress-Element-{i}", **{f"data_stress_attribute_{i}": i}))
+
+    def expected(self, i):
+        return f'<x-stress-element-{i} data-stress-attribute-{i}="{i}"></x-stress-element-{i}>'
+
+    def test_unique_names_across_threads(self):
+        """Test every thread gets correct output for names past the cache limits."""
+        from concurrent.futures import ThreadPoolExecutor
+        with ThreadPoolExecutor(max_workers=8) as pool:
+            results = list(pool.map(self.render, range(20000)))
+        assert results == [self.expected(i) for i in range(20000)]
+
+    def test_shared_names_are_deterministic(self):
+        """Test threads racing to cache the same names render identical markup."""
+        from concurrent.futures import ThreadPoolExecutor
+        names = range(50000, 50500)
+        with ThreadPoolExecutor(max_workers=8) as pool:
+            runs = list(pool.map(lambda _: [self.render(i) for i in names], range(8)))
+        assert all(run == [self.expected(i) for i in names] for run in runs)
+
+
+class TestStrictTableSpans:
+    """Test strict-mode validation of Td/Th colspan and rowspan."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_lenient_mode_keeps_current_behavior(self):
+        """Test invalid spans render unchanged when strict mode is off."""
+        from rusty_tags import Td
+        assert str(Td("x", colspan=0)) == '<td colspan="0">x</td>'
+        assert str(Td("x", rowspan=-1)) == '<td rowspan="-1">x</td>'
+
+    def test_strict_accepts_positive_ints(self):
+        """Test positive int and numeric string spans pass."""
+        from rusty_tags import Td, Th, configure
+        configure(strict=True)
+        assert str(Td("x", colspan=2)) == '<td colspan="2">x</td>'
+        assert str(Th("h", rowspan="3")) == '<th rowspan="3">h</th>'
+
+    def test_strict_rejects_zero_and_negative(self):
+        """Test zero/negative spans raise and identify the cell."""
+        from rusty_tags import Td, Th, configure
+        configure(strict=True)
+        with pytest.raises(ValueError, match=r'Invalid colspan on <td id="total" colspan="0">'):
+            Td("x", id="total", colspan=0)
+        with pytest.raises(ValueError, match="Invalid rowspan on <th"):
+            Th("h", rowspan=-2)
+
+    def test_strict_rejects_non_integers(self):
+        """Test non-numeric spans raise."""
+        from rusty_tags import Td, configure
+        configure(strict=True)
+        with pytest.raises(ValueError, match="expected a positive integer, got 'wide'"):
+            Td("x", colspan="wide")
+
+    def test_strict_matches_names_case_insensitively(self):
+        """Test mixed-case span and dir names are validated like lowercase ones."""
+        from rusty_tags import Td, Th, configure
+        configure(strict=True)
+        with pytest.raises(ValueError, match="Invalid colspan on <td"):
+            Td("x", colSpan=0)
+        with pytest.raises(ValueError, match="Invalid rowspan on <th"):
+            Th("h", ROWSPAN="-1")
+        with pytest.raises(ValueError, match="Invalid dir"):
+            Td("x", Dir="sideways")
+        assert str(Td("x", colSpan=2)) == '<td colSpan="2">x</td>'
+
+    def test_strict_ignores_other_tags(self):
+        """Test span validation only applies to table cells."""
+        from rusty_tags import Div, configure
+        configure(strict=True)
+        assert str(Div("x", colspan=0)) == '<div colspan="0">x</div>'
+
+
+class TestDocumentLanguage:
+    """Test lang/dir on the document and strict dir validation."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_lang_and_dir_on_html(self):
+        """Test lang and dir render on the <html> element in order."""
+        result = str(Html(Body("مرحبا"), lang="ar", dir="rtl"))
+        assert result == '<!doctype html><html lang="ar" dir="rtl"><body>مرحبا</body></html>'
+
+    def test_dir_on_other_elements(self):
+        """Test dir works as a global attribute."""
+        assert str(P("abc", dir="auto")) == '<p dir="auto">abc</p>'
+
+    def test_strict_accepts_valid_dir(self):
+        """Test strict mode accepts ltr/rtl/auto in any case."""
+        from rusty_tags import configure
+        configure(strict=True)
+        for value in ["ltr", "rtl", "auto", "RTL"]:
+            Html("x", dir=value)
+            Div("x", dir=value)
+
+    def test_strict_rejects_invalid_dir(self):
+        """Test strict mode rejects other dir values on Html and regular tags."""
+        from rusty_tags import configure
+        configure(strict=True)
+        with pytest.raises(ValueError, match="Invalid dir"):
+            Html("x", lang="ar", dir="right-to-left")
+        with pytest.raises(ValueError, match="Invalid dir"):
+            Span("x", dir="rtl ltr")
+        with pytest.raises(ValueError, match="Invalid dir"):
+            Html("x", dir="up", auto_structure=False)
+
+    def test_lenient_mode_allows_any_dir(self):
+        """Test invalid dir values are passed through when strict is off."""
+        assert str(Div("x", dir="sideways")) == '<div dir="sideways">x</div>'
+
+
+class TestRenderAttrs:
+    """Test render_attrs() standalone attribute rendering."""
+
+    def test_basic_mapping(self):
+        """Test a mapping renders with a leading space in insertion order."""
+        from rusty_tags import render_attrs
+        assert render_attrs({"id": "main", "title": "x"}) == ' id="main" title="x"'
+
+    def test_alias_mapping(self):
+        """Test cls/for_ and underscore aliases map like in tag functions."""
+        from rusty_tags import render_attrs
+        assert render_attrs({"cls": "btn", "for_": "email", "data_id": 3}) == ' class="btn" for="email" data-id="3"'
+
+    def test_boolean_values(self):
+        """Test True renders a bare attribute and False is omitted."""
+        from rusty_tags import render_attrs
+        assert render_attrs({"disabled": True, "hidden": False}) == " disabled"
+
+    def test_empty_mapping(self):
+        """Test an empty mapping renders an empty string."""
+        from rusty_tags import render_attrs
+        assert render_attrs({}) == ""
+
+    def test_matches_tag_rendering(self):
+        """Test output matches the attribute portion of a rendered tag."""
+        from rusty_tags import render_attrs, Div
+        attrs = {"cls": "card", "ds_show": "$open"}
+        assert str(Div("x", **attrs)) == f"<div{render_attrs(attrs)}>x</div>"
+
+    def test_returns_plain_str(self):
+        """Test the result is a plain str, not HtmlString."""
+        from rusty_tags import render_attrs
+        assert type(render_attrs({"id": "a"})) is str
+
+
+class TestWithAttrs:
+    """Test with_attrs() attribute merging on rendered elements."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_appends_classes(self):
+        """Test class names are appended once, keeping existing ones first."""
+        from rusty_tags import with_attrs
+        result = with_attrs(Button("Save", cls="btn"), cls="primary btn")
+        assert str(result) == '<button class="btn primary">Save</button>'
+
+    def test_overrides_and_adds(self):
+        """Test other attributes replace in place and new ones are appended."""
+        from rusty_tags import with_attrs
+        result = with_attrs(Img(src="a.png", alt=""), alt="Logo", data_id=3)
+        assert str(result) == '<img src="a.png" alt="Logo" data-id="3"></img>'
+
+    def test_false_and_none_remove(self):
+        """Test False/None remove the attribute, including aliased names."""
+        from rusty_tags import with_attrs
+        result = with_attrs(Label("x", for_="a", hidden=True, title="t"), for_=None, hidden=False)
+        assert str(result) == '<label title="t">x</label>'
+
+    def test_aliases_and_datastar(self):
+        """Test keywords map like the tag functions do."""
+        from rusty_tags import with_attrs
+        result = with_attrs(Div("x"), ds_show="$open", hx_on={"click": "go()"})
+        assert str(result) == '<div data-show="$open" hx-on:click="go()">x</div>'
+
+    def test_only_root_changes(self):
+        """Test children and attribute case of the root are kept verbatim."""
+        from rusty_tags import Circle, Svg, with_attrs
+        icon = Svg(Circle(r="1", cls="dot"), viewBox="0 0 1 1")
+        assert str(with_attrs(icon, cls="icon")) == '<svg viewBox="0 0 1 1" class="icon"><circle r="1" class="dot"></circle></svg>'
+
+    def test_document_root(self):
+        """Test a doctype before the root element is allowed."""
+        from rusty_tags import with_attrs
+        result = with_attrs(Html(Body("x"), lang="en"), lang="fr")
+        assert str(result) == '<!doctype html><html lang="fr"><body>x</body></html>'
+
+    def test_multiple_roots_raise(self):
+        """Test fragments with several roots or top-level text are rejected."""
+        from rusty_tags import with_attrs
+        with pytest.raises(ValueError, match="single root element"):
+            with_attrs(Fragment(P("a"), P("b")), id="x")
+        with pytest.raises(ValueError, match="top-level text"):
+            with_attrs(Fragment("Hello ", B("x")), id="x")
+
+    def test_single_root_fragment(self):
+        """Test a fragment wrapping one element is decorated like the element."""
+        from rusty_tags import with_attrs
+        assert str(with_attrs(Fragment(" ", P("a")), id="x")) == ' <p id="x">a</p>'
+
+    def test_self_closed_root_follows_render_mode(self):
+        """Test a self-closed root keeps the configured self-closing form."""
+        from rusty_tags import with_attrs, configure, HtmlString
+        assert str(with_attrs(HtmlString('<img src="a.png"/>'), alt="")) == '<img src="a.png" alt=""/>'
+        configure(mode="xhtml")
+        assert str(with_attrs(Img(src="a.png"), alt="")) == '<img src="a.png" alt="" />'
+
+
+class TestCombine:
+    """Test combine() layering of attribute dicts."""
+
+    def test_classes_concatenate(self):
+        """Test class values join in layer order without duplicates."""
+        from rusty_tags import combine
+        assert combine({"cls": "btn"}, {"cls": "btn-lg btn"}, {"cls": "w-full"}) == {"cls": "btn btn-lg w-full"}
+
+    def test_last_value_wins(self):
+        """Test other keys take the value of the last layer that sets them."""
+        from rusty_tags import combine
+        merged = combine({"type": "button", "id": "a"}, {"type": "submit"}, {"title": "t"})
+        assert merged == {"type": "submit", "id": "a", "title": "t"}
+        assert list(merged) == ["type", "id", "title"]
+
+    def test_aliases_are_one_key(self):
+        """Test keys rendering as the same attribute merge, keeping the first spelling."""
+        from rusty_tags import combine
+        assert combine({"cls": "a"}, {"class_": "b"}, {"klass": "c"}) == {"cls": "a b c"}
+        assert combine({"for_": "x"}, {"fr": "y"}) == {"for_": "y"}
+
+    def test_none_and_false_override(self):
+        """Test falsy overrides replace the value so the attribute is dropped."""
+        from rusty_tags import combine
+        merged = combine({"cls": "a", "disabled": True}, {"cls": None, "disabled": False})
+        assert str(Button("x", **merged)) == "<button>x</button>"
+
+    def test_non_string_class_is_replaced(self):
+        """Test class values that are not strings follow last-wins."""
+        from rusty_tags import combine
+        assert combine({"cls": "a"}, {"cls": 1}) == {"cls": 1}
+
+    def test_inputs_unchanged_and_usable_as_kwargs(self):
+        """Test a new dict is returned and expands into a tag call."""
+        from rusty_tags import combine
+        base = {"cls": "btn", "type": "button"}
+        merged = combine(base, {"cls": "primary", "ds_on_click": "@post('/save')"})
+        assert base == {"cls": "btn", "type": "button"}
+        assert str(Button("Save", **merged)) == (
+            '<button class="btn primary" type="button" data-on:click="@post(\'/save\')">Save</button>'
+        )
+
+
+class TestStrictCustomTag:
+    """Test strict-mode tag name validation in CustomTag."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_lenient_mode_accepts_anything(self):
+        """Test CustomTag accepts unknown names when strict mode is off."""
+        assert str(CustomTag("divv", "x")) == "<divv>x</divv>"
+
+    def test_strict_rejects_typos(self):
+        """Test unknown names raise in strict mode."""
+        from rusty_tags import configure
+        configure(strict=True)
+        with pytest.raises(ValueError, match="Unknown tag name 'divv'"):
+            CustomTag("divv", "x")
+
+    def test_strict_accepts_known_html_and_svg(self):
+        """Test standard HTML and SVG names pass, case-insensitively."""
+        from rusty_tags import configure
+        configure(strict=True)
+        assert str(CustomTag("section", "x")) == "<section>x</section>"
+        assert "feGaussianBlur".lower() in str(CustomTag("feGaussianBlur", stdDeviation="2")).lower()
+        assert str(CustomTag("DIV", "x")).lower() == "<div>x</div>"
+
+    def test_strict_accepts_custom_elements(self):
+        """Test hyphenated custom element names always pass."""
+        from rusty_tags import configure
+        configure(strict=True)
+        assert str(CustomTag("my-widget", "x")) == "<my-widget>x</my-widget>"
+
+    def test_strict_accepts_registered_names(self):
+        """Test names registered with register_tag() pass."""
+        from rusty_tags import configure, register_tag, is_known_tag
+        assert not is_known_tag("legacyblink")
+        register_tag("legacyblink")
+        assert is_known_tag("LegacyBlink")
+        configure(strict=True)
+        assert str(CustomTag("legacyblink", "x")) == "<legacyblink>x</legacyblink>"
+
+
+class TestPortal:
+    """Test Portal deferred placement into <head> / end of <body>."""
+
+    def test_portal_hoists_into_head(self):
+        """Test head portals move before </head>."""
+        from rusty_tags import Portal
+        css = Link(rel="stylesheet", href="/w.css")
+        result = str(Html(Head(Title("App")), Body(Div(Portal("head", css), "Widget"))))
+        assert result == (
+            '<!doctype html><html><head><title>App</title>'
+            '<link rel="stylesheet" href="/w.css"></link></head>'
+            '<body><div>Widget</div></body></html>'
+        )
+
+    def test_portal_hoists_to_end_of_body(self):
+        """Test body portals move before </body>."""
+        from rusty_tags import Portal
+        result = str(Html(Body(Div(Portal("body", Script(src="/w.js")), "Widget"), P("after"))))
+        assert result.endswith('<p>after</p><script src="/w.js"></script></body></html>')
+
+    def test_identical_portals_emitted_once(self):
+        """Test a component used several times adds its assets once."""
+        from rusty_tags import Portal
+
+        def widget(label):
+            return Div(Portal("head", Link(rel="stylesheet", href="/w.css")), label)
+
+        result = str(Html(Head(), Body(widget("a"), widget("b"), widget("c"))))
+        assert result.count('href="/w.css"') == 1
+        assert "<div>a</div><div>b</div><div>c</div>" in result
+
+    def test_head_created_when_missing(self):
+        """Test a <head> is created for head portals when the document has none."""
+        from rusty_tags import Portal
+        result = str(Html(Body(Portal("head", Title("T")), "x")))
+        assert result == "<!doctype html><html><head><title>T</title></head><body>x</body></html>"
+
+    def test_portal_outside_document_keeps_markers(self):
+        """Test portals outside Html stay in place as comment markers."""
+        from rusty_tags import Portal
+        result = str(Div(Portal("head", "x")))
+        assert result == "<div><!--portal:head-->x<!--/portal:head--></div>"
+
+    def test_unknown_target_raises(self):
+        """Test only head/body targets are accepted."""
+        from rusty_tags import Portal
+        with pytest.raises(ValueError, match="Unknown portal target"):
+            Portal("footer", "x")
+
+
+class TestBase:
+    """Test the <base> element and its placement when hoisted into <head>."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_base_renders(self):
+        """Test Base is exported and known as a void element."""
+        from rusty_tags import Base, is_void_tag
+        assert str(Base(href="/app/", target="_blank")) == '<base href="/app/" target="_blank"></base>'
+        assert is_void_tag("base")
+
+    def test_hoisted_base_goes_first_in_head(self):
+        """Test a <base> portal lands ahead of head content that uses URLs."""
+        from rusty_tags import Base, Portal
+        page = Html(
+            Head(Link(rel="stylesheet", href="app.css")),
+            Body(Portal("head", Base(href="/app/"), Script(src="w.js")), "x"),
+        )
+        assert str(page) == (
+            '<!doctype html><html><head><base href="/app/"></base>'
+            '<link rel="stylesheet" href="app.css"></link><script src="w.js"></script>'
+            '</head><body>x</body></html>'
+        )
+
+    def test_hoisted_base_with_head_attributes(self):
+        """Test placement after a <head> start tag carrying attributes."""
+        from rusty_tags import Base, Portal
+        page = str(Html(Head(Title("T"), lang="en"), Body(Header(Portal("head", Base(href="/"))))))
+        assert '<head lang="en"><base href="/"></base><title>T</title></head>' in page
+        assert "<body><header></header></body>" in page
+
+    def test_hoisted_base_creates_head(self):
+        """Test a <head> is created with the <base> first when missing."""
+        from rusty_tags import Base, Portal
+        page = str(Html(Body(Portal("head", Title("T")), Portal("head", Base(href="/")))))
+        assert page.startswith('<!doctype html><html><head><base href="/"></base><title>T</title></head>')
+
+    def test_base_in_body_moves_to_head(self):
+        """Test a <base> written directly in the body is moved to the top of <head>."""
+        from rusty_tags import Base
+        page = Html(Head(Link(rel="stylesheet", href="app.css")), Body(Base(href="/app/"), "x"))
+        assert str(page) == (
+            '<!doctype html><html><head><base href="/app/"></base>'
+            '<link rel="stylesheet" href="app.css"></link></head><body>x</body></html>'
+        )
+
+    def test_base_after_head_links_moves_first(self):
+        """Test a <base> following links in <head> is moved ahead of them."""
+        from rusty_tags import Base
+        page = str(Html(Head(Link(rel="icon", href="i.png"), Base(href="/")), Body("x")))
+        assert '<head><base href="/"></base><link rel="icon" href="i.png"></link></head>' in page
+
+    def test_base_in_body_without_head(self):
+        """Test a <head> is created for a body <base> when the document has none."""
+        from rusty_tags import Base
+        page = str(Html(Body(Div(Base(href="/")), "x")))
+        assert page == '<!doctype html><html><head><base href="/"></base></head><body><div></div>x</body></html>'
+
+    def test_base_already_first_is_unchanged(self):
+        """Test a document whose <base> already leads <head> renders as written."""
+        from rusty_tags import Base
+        page = str(Html(Head(Base(href="/"), Title("T")), Body("x")))
+        assert page == '<!doctype html><html><head><base href="/"></base><title>T</title></head><body>x</body></html>'
+
+    def test_strict_rejects_second_base(self):
+        """Test strict mode allows at most one <base> per document."""
+        from rusty_tags import Base, Portal, configure
+        configure(strict=True)
+        assert "<base" in str(Html(Head(Base(href="/")), Body("x")))
+        with pytest.raises(ValueError, match="2 <base> elements"):
+            Html(Head(Base(href="/")), Body(Portal("head", Base(href="/other/"))))
+
+    def test_lenient_allows_second_base(self):
+        """Test duplicate <base> elements pass when strict mode is off."""
+        from rusty_tags import Base
+        page = str(Html(Head(Base(href="/"), Base(href="/b/")), Body("x")))
+        assert page.count("<base") == 2
+
+
+class TestScriptAttributes:
+    """Test common script attributes: defer, async_, type="module"."""
+
+    def test_defer_and_async_are_bare(self):
+        """Test defer/async_ True render as bare boolean attributes."""
+        result = str(Script(src="/app.js", defer=True, async_=True))
+        assert result == '<script src="/app.js" defer async></script>'
+
+    def test_false_booleans_omitted(self):
+        """Test defer/async_ False are left out."""
+        assert str(Script(src="/app.js", defer=False, async_=False)) == '<script src="/app.js"></script>'
+
+    def test_module_script(self):
+        """Test type="module" alongside async."""
+        result = str(Script(src="/app.js", type="module", async_=True))
+        assert result == '<script src="/app.js" type="module" async></script>'
+
+    def test_module_shorthand(self):
+        """Test module=True emits type="module" in its keyword position."""
+        result = str(Script(src="/app.js", module=True, async_=True))
+        assert result == '<script src="/app.js" type="module" async></script>'
+        assert str(Script(src="/app.js", type="module", module=True)) == '<script src="/app.js" type="module"></script>'
+
+    def test_module_false_is_dropped(self):
+        """Test module=False adds nothing."""
+        assert str(Script("run()", module=False)) == "<script>run()</script>"
+
+    def test_module_conflicting_type_raises(self):
+        """Test module=True cannot be combined with another type."""
+        with pytest.raises(ValueError, match="conflicts with type='text/javascript'"):
+            Script(src="/app.js", type="text/javascript", module=True)
+
+    def test_nomodule_and_crossorigin(self):
+        """Test other script boolean/keyword attributes."""
+        result = str(Script(src="/legacy.js", nomodule=True, crossorigin="anonymous"))
+        assert result == '<script src="/legacy.js" nomodule crossorigin="anonymous"></script>'
+
+    def test_trailing_underscore_is_dropped_generally(self):
+        """Test any keyword-escaping trailing underscore is removed."""
+        assert str(Input(name="q", list_="opts")) == '<input name="q" list="opts"></input>'
+        assert str(Div("x", data_is_="1")) == '<div data-is="1">x</div>'
+
+
+class TestBooleanChildren:
+    """Test bools are checked before ints in every conversion path."""
+
+    def test_bool_child_renders_lowercase(self):
+        """Test True/False children render as "true"/"false", not 1/0."""
+        assert str(Div(True)) == "<div>true</div>"
+        assert str(Div(False)) == "<div>false</div>"
+        assert str(Div(1, 0)) == "<div>10</div>"
+
+    def test_bool_child_in_fragment_and_custom_tag(self):
+        """Test the same conversion applies outside the tag macros."""
+        assert str(Fragment(True, False)) == "truefalse"
+        assert str(CustomTag("my-el", True)) == "<my-el>true</my-el>"
+
+    def test_bool_attribute_not_treated_as_int(self):
+        """Test True attributes are bare and False omitted, never "1"/"0"."""
+        assert str(Input(disabled=True, readonly=False)) == "<input disabled></input>"
+        assert str(Input(maxlength=1)) == '<input maxlength="1"></input>'
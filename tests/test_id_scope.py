@@ -0,0 +1,75 @@
+"""
+Tests for id prefixing of reusable components (id_scope / scoped_id)
+"""
+
+import asyncio
+import pytest
+from rusty_tags import (
+    Div, Label, Input, Td, A, CustomTag, Fragment, HtmlElement, id_scope, scoped_id,
+)
+
+
+class TestIdScope:
+    """Test id_scope() rewrites ids and id references consistently."""
+
+    def test_default_leaves_ids_alone(self):
+        """Test ids are unchanged outside an id_scope block."""
+        assert str(Div(id="main")) == '<div id="main"></div>'
+
+    def test_ids_and_references_prefixed(self):
+        """Test id, for and aria references share the prefix."""
+        with id_scope("card1"):
+            html = Fragment(
+                Label("Name", fr="name"),
+                Input(id="name", aria_describedby="help hint", form="signup"),
+            )
+        assert str(html) == (
+            '<label for="card1-name">Name</label>'
+            '<input id="card1-name" aria-describedby="card1-help card1-hint" form="card1-signup"></input>'
+        )
+
+    def test_other_attributes_untouched(self):
+        """Test non-reference attributes and URLs keep their values."""
+        with id_scope("c"):
+            html = A("Go", href="#top", cls="name", headers="")
+        assert str(html) == '<a href="#top" class="name" headers="">Go</a>'
+
+    def test_nested_scopes_compose(self):
+        """Test nested blocks join their prefixes and restore on exit."""
+        with id_scope("outer") as outer:
+            with id_scope("inner") as inner:
+                assert str(Div(id="x")) == '<div id="outer-inner-x"></div>'
+            assert str(Div(id="x")) == '<div id="outer-x"></div>'
+        assert (outer, inner) == ("outer", "outer-inner")
+
+    def test_positional_dicts_custom_tags_and_elements(self):
+        """Test the other attribute paths are scoped too."""
+        with id_scope("s"):
+            assert str(Td("x", {"headers": "h1 h2"})) == '<td headers="s-h1 s-h2">x</td>'
+            assert str(CustomTag("my-el", id="a")) == '<my-el id="s-a"></my-el>'
+            assert HtmlElement("div", {"id": "e"}, []).to_html().content == '<div id="s-e"></div>'
+
+    def test_scoped_id(self):
+        """Test scoped_id() returns the id as it will be written."""
+        assert scoped_id("name") == "name"
+        with id_scope("card1"):
+            assert scoped_id("name") == "card1-name"
+
+    def test_invalid_prefix_rejected(self):
+        """Test empty prefixes or prefixes with whitespace raise."""
+        with pytest.raises(ValueError):
+            id_scope("")
+        with pytest.raises(ValueError):
+            id_scope("a b")
+
+    def test_isolated_per_task(self):
+        """Test concurrent asyncio tasks keep their own prefix."""
+        async def render(prefix):
+            with id_scope(prefix):
+                await asyncio.sleep(0)
+                return str(Div(id="x"))
+
+        async def main():
+            return await asyncio.gather(render("a"), render("b"))
+
+        assert asyncio.run(main()) == ['<div id="a-x"></div>', '<div id="b-x"></div>']
@@ -118,6 +118,27 @@ def test_html_element_type():
     assert hasattr(doc, 'children')
 
 
+def test_none_children_serialize_as_empty():
+    """Test None children in an HtmlElement tree render as nothing"""
+    element = HtmlElement("div", {"id": "a"}, ["before", None, "after"])
+    assert element.to_html().content == '<div id="a">beforeafter</div>'
+
+
+def test_bool_children_serialize_like_tags():
+    """Test bool and int children in an HtmlElement tree match tag-function output"""
+    element = HtmlElement("span", {}, [True, " ", False, " ", 1])
+    assert element.to_html().content == "<span>true false 1</span>"
+    assert element.to_html().content == Span(True, " ", False, " ", 1).content
+
+
+def test_bool_attributes_serialize_like_tags():
+    """Test True attributes render bare and False attributes are omitted"""
+    doc = Input(name="q").parse()
+    doc.attributes["disabled"] = True
+    doc.attributes["readonly"] = False
+    assert doc.to_html().content == '<input name="q" disabled></input>'
+
+
 def test_modify_and_serialize():
     """Test modifying element and serializing back"""
     html = Div("Original", id="test")
@@ -351,3 +372,43 @@ def test_modify_specific_child_in_traversal():
 
 if __name__ == "__main__":
     pytest.main([__file__, "-v"])
+
+
+def test_debug_tree_single_root():
+    """Test debug_tree describes a single root element"""
+    from rusty_tags import debug_tree, P
+    tree = debug_tree(Div(P("Hi"), id="main"))
+    assert tree == {
+        "type": "element",
+        "tag": "div",
+        "attrs": {"id": "main"},
+        "children": [
+            {"type": "element", "tag": "p", "attrs": {}, "children": [{"type": "text", "text": "Hi"}]},
+        ],
+    }
+
+
+def test_debug_tree_multiple_roots():
+    """Test debug_tree wraps several roots in a fragment node"""
+    from rusty_tags import debug_tree, Fragment
+    tree = debug_tree(Fragment(Span("a"), "text", Span("b")))
+    assert tree["type"] == "fragment"
+    assert [child["type"] for child in tree["children"]] == ["element", "text", "element"]
+    assert tree["children"][1]["text"] == "text"
+
+
+def test_debug_tree_accepts_strings_and_elements():
+    """Test debug_tree accepts raw markup and parsed HtmlElement trees"""
+    from rusty_tags import debug_tree
+    from_str = debug_tree('<form><input name="email"></form>')
+    assert from_str["tag"] == "form"
+    assert from_str["children"][0]["attrs"] == {"name": "email"}
+
+    parsed = Form(Input(name="email")).parse()
+    assert debug_tree(parsed) == from_str
+
+
+def test_debug_tree_empty_output():
+    """Test debug_tree on empty output returns an empty fragment"""
+    from rusty_tags import debug_tree, Fragment
+    assert debug_tree(Fragment()) == {"type": "fragment", "children": []}
@@ -0,0 +1,70 @@
+"""
+Tests for id reference integrity checks (check_id_references)
+"""
+
+import warnings
+import pytest
+from rusty_tags import (
+    Html, Body, Div, Form, Label, Input, Small, Span, Button,
+    configure, reset_config, id_scope, check_id_references,
+)
+
+
+class TestCheckIdReferences:
+    """Test broken for / aria-* references are reported."""
+
+    def test_all_references_resolve(self):
+        """Test wired-up labels and descriptions give no messages."""
+        form = Form(
+            Label("Email", for_="email"),
+            Input(id="email", aria_describedby="email-help"),
+            Small("We never share it", id="email-help"),
+        )
+        assert check_id_references(form) == []
+
+    def test_missing_for_target(self):
+        """Test a label pointing at a renamed input is reported."""
+        form = Form(Label("Email", for_="email"), Input(id="mail"))
+        assert check_id_references(form) == ['<label for="email">: no element with id="email"']
+
+    def test_id_lists(self):
+        """Test every id of a space-separated aria reference is checked."""
+        dialog = Div(Span("Title", id="t"), role="dialog", aria_labelledby="t sub")
+        assert check_id_references(dialog) == ['<div aria-labelledby="t sub">: no element with id="sub"']
+
+    def test_reference_before_target(self):
+        """Test ids defined later in the document still count."""
+        button = Div(Button("Delete", aria_describedby="warn"), Span("Cannot be undone", id="warn"))
+        assert check_id_references(button) == []
+
+    def test_id_scope(self):
+        """Test references rewritten by id_scope stay consistent."""
+        with id_scope("billing"):
+            field = Div(Label("Name", for_="name"), Input(id="name"))
+        assert check_id_references(field) == []
+
+
+class TestStrictModeWarnings:
+    """Test Html documents warn about broken references in strict mode."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_strict_warns(self):
+        """Test each broken reference becomes a UserWarning."""
+        configure(strict=True)
+        with pytest.warns(UserWarning, match='no element with id="email"'):
+            Html(Body(Label("Email", for_="email"), Input(id="mail")))
+
+    def test_lenient_is_silent(self):
+        """Test nothing is checked outside strict mode."""
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            Html(Body(Label("Email", for_="email")))
+
+    def test_strict_valid_document(self):
+        """Test a correct document renders without warnings."""
+        configure(strict=True)
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            Html(Body(Label("Email", for_="email"), Input(id="email")))
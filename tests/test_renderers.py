@@ -0,0 +1,106 @@
+"""
+Tests for custom child renderers (register_child_renderer)
+"""
+
+import pytest
+from decimal import Decimal
+from rusty_tags import (
+    Div, Td, Fragment, HtmlString, freeze,
+    register_child_renderer, unregister_child_renderer,
+)
+
+
+class Money:
+    def __init__(self, cents):
+        self.cents = cents
+
+
+class Widget:
+    def __html__(self):
+        return "<b>widget</b>"
+
+
+def clear_renderers():
+    for predicate in (Money, Decimal, Widget, (Money, Widget), str):
+        unregister_child_renderer(predicate)
+
+
+class TestRegisterChildRenderer:
+    """Test registered renderers are used for matching children."""
+
+    def teardown_method(self, method):
+        clear_renderers()
+
+    def test_type_predicate(self):
+        """Test a type predicate matches instances of that type."""
+        register_child_renderer(Money, lambda m: f"${m.cents / 100:.2f}")
+        assert str(Td(Money(1250))) == "<td>$12.50</td>"
+        assert str(Fragment(Money(5), " ", Money(100))) == "$0.05 $1.00"
+
+    def test_tuple_of_types(self):
+        """Test a tuple of types matches any of them."""
+        register_child_renderer((Money, Widget), lambda obj: type(obj).__name__)
+        assert str(Div(Money(1), Widget())) == "<div>MoneyWidget</div>"
+
+    def test_callable_predicate(self):
+        """Test a callable predicate decides per child."""
+        def predicate(obj):
+            return isinstance(obj, Money) and obj.cents < 0
+
+        register_child_renderer(predicate, lambda m: HtmlString(f'<span class="neg">{m.cents}</span>'))
+        assert str(Div(Money(-3))) == '<div><span class="neg">-3</span></div>'
+        assert unregister_child_renderer(predicate) is True
+
+    def test_overrides_numbers_and_html_protocol(self):
+        """Test renderers take precedence over number formatting and __html__."""
+        register_child_renderer(Decimal, lambda d: f"{d:,.2f}")
+        register_child_renderer(Widget, lambda w: "custom")
+        assert str(Td(Decimal("1234.5"))) == "<td>1,234.50</td>"
+        assert str(Div(Widget())) == "<div>custom</div>"
+
+    def test_builtin_fast_paths_win(self):
+        """Test str, bool and markup children are never redirected."""
+        register_child_renderer(str, lambda s: "X")
+        assert str(Div("text", True, HtmlString("<i>y</i>"), freeze(Td("z")))) == "<div>texttrue<i>y</i><td>z</td></div>"
+
+    def test_most_recent_registration_wins(self):
+        """Test later registrations are consulted first."""
+        register_child_renderer(Money, lambda m: "first")
+        register_child_renderer(Money, lambda m: "second")
+        assert str(Div(Money(1))) == "<div>second</div>"
+
+    def test_unregister(self):
+        """Test unregistering restores the default conversion."""
+        register_child_renderer(Widget, lambda w: "custom")
+        assert unregister_child_renderer(Widget) is True
+        assert unregister_child_renderer(Widget) is False
+        assert str(Div(Widget())) == "<div><b>widget</b></div>"
+
+
+class TestRendererErrors:
+    """Test invalid registrations and renderer results are rejected."""
+
+    def teardown_method(self, method):
+        clear_renderers()
+
+    def test_invalid_arguments(self):
+        """Test predicate and renderer must be usable."""
+        with pytest.raises(TypeError):
+            register_child_renderer("Money", str)
+        with pytest.raises(TypeError):
+            register_child_renderer(Money, "not callable")
+
+    def test_bad_return_type(self):
+        """Test a renderer must return str or HtmlString."""
+        register_child_renderer(Money, lambda m: m.cents)
+        with pytest.raises(TypeError, match="must return str or HtmlString"):
+            str(Div(Money(1)))
+
+    def test_renderer_exceptions_propagate(self):
+        """Test exceptions raised by a renderer reach the caller."""
+        def boom(obj):
+            raise RuntimeError("boom")
+
+        register_child_renderer(Money, boom)
+        with pytest.raises(RuntimeError, match="boom"):
+            Div(Money(1))
@@ -0,0 +1,59 @@
+"""
+Tests for server-sent event framing (render_chunks_for_sse)
+"""
+
+import pytest
+from rusty_tags import Div, Li, P, Pre, HtmlString, render_chunks_for_sse
+
+
+class TestSseFrames:
+    """Test each element becomes one text/event-stream frame."""
+
+    def test_one_frame_per_element(self):
+        """Test frames are 'data: <markup>' followed by a blank line."""
+        assert render_chunks_for_sse([Li("a"), Li("b")]) == [
+            "data: <li>a</li>\n\n",
+            "data: <li>b</li>\n\n",
+        ]
+
+    def test_multiline_markup(self):
+        """Test every line of the markup gets its own data: line."""
+        frame, = render_chunks_for_sse([Div(P("x"), "\n", P("y"))])
+        assert frame == "data: <div><p>x</p>\ndata: <p>y</p></div>\n\n"
+
+    def test_all_line_break_styles(self):
+        """Test \\r\\n, \\r and \\n each end a data line, as the SSE parser does."""
+        frame, = render_chunks_for_sse([Pre("a\r\nb\rc\nd")])
+        assert frame == "data: <pre>a\ndata: b\ndata: c\ndata: d</pre>\n\n"
+
+    def test_trailing_newline_is_kept(self):
+        """Test a trailing line break survives as an empty data line."""
+        assert render_chunks_for_sse([HtmlString("<p>x</p>\n")]) == ["data: <p>x</p>\ndata: \n\n"]
+
+    def test_empty_and_plain_values(self):
+        """Test children follow the normal rendering rules."""
+        assert render_chunks_for_sse(["", 3, None]) == ["data: \n\n", "data: 3\n\n", "data: \n\n"]
+
+    def test_generators_and_str_result(self):
+        """Test any iterable works and frames are plain str."""
+        frames = render_chunks_for_sse(Li(str(i)) for i in range(3))
+        assert len(frames) == 3
+        assert all(type(frame) is str for frame in frames)
+        assert render_chunks_for_sse([]) == []
+
+
+class TestSseEventName:
+    """Test the optional event: line."""
+
+    def test_event_line_on_every_frame(self):
+        """Test event= prefixes each frame with 'event: <name>'."""
+        assert render_chunks_for_sse([Li("a"), Li("b")], event="item") == [
+            "event: item\ndata: <li>a</li>\n\n",
+            "event: item\ndata: <li>b</li>\n\n",
+        ]
+
+    def test_event_name_line_breaks_rejected(self):
+        """Test a line break in the name, which would inject fields, raises."""
+        for name in ("a\nb", "a\rdata: x"):
+            with pytest.raises(ValueError, match="cannot contain line breaks"):
+                render_chunks_for_sse([P("x")], event=name)
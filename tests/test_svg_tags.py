@@ -20,7 +20,9 @@ from rusty_tags import (
     # Special tags
     Fragment, Safe, CustomTag,
     # HTML tags for comparison
-    Div, Span
+    Div, Span,
+    # Rendering configuration
+    configure, reset_config,
 )
 
 
@@ -523,3 +525,96 @@ class TestSpecialTagsEdgeCases:
         result = str(CustomTag("widget", data_test="value", aria_label="label"))
         assert 'data-test="value"' in result
         assert 'aria-label="label"' in result
+
+
+class TestSvgSelfClosing:
+    """Test self-closing output for childless SVG leaf elements."""
+
+    def teardown_method(self, method):
+        reset_config()
+
+    def test_leaf_elements_use_closing_tag_by_default(self):
+        """Test SVG leaves render an explicit closing tag by default."""
+        result = str(Circle(r="40"))
+        assert result == '<circle r="40"></circle>'
+
+    def test_leaf_elements_self_close_when_configured(self):
+        """Test SVG leaves render self-closing with svg_self_closing."""
+        configure(svg_self_closing=True)
+        assert str(Circle(r="40")) == '<circle r="40"/>'
+        assert str(Rect(width="10")) == '<rect width="10"/>'
+        assert str(Path(d="M0 0")) == '<path d="M0 0"/>'
+        assert str(Stop(offset="0%")) == '<stop offset="0%"/>'
+        assert str(Use(href="#icon")) == '<use href="#icon"/>'
+
+    def test_leaf_with_children_keeps_closing_tag(self):
+        """Test SVG leaves with children (e.g. <title>) are not self-closed."""
+        configure(svg_self_closing=True)
+        result = str(Rect(CustomTag("title", "Box"), width="10"))
+        assert result == '<rect width="10"><title>Box</title></rect>'
+
+    def test_containers_are_not_self_closed(self):
+        """Test SVG container elements keep closing tags."""
+        configure(svg_self_closing=True)
+        assert str(G(id="layer")) == '<g id="layer"></g>'
+        assert str(Svg(width="10")) == '<svg width="10"></svg>'
+
+    def test_html_elements_are_not_self_closed(self):
+        """Test HTML elements are unaffected by SVG self-closing."""
+        configure(svg_self_closing=True)
+        assert str(Div(id="x")) == '<div id="x"></div>'
+
+    def test_nested_svg_output(self):
+        """Test self-closing leaves inside an SVG document."""
+        configure(svg_self_closing=True)
+        result = str(Svg(Circle(r="5"), Line(x2="1"), width="10"))
+        assert result == '<svg width="10"><circle r="5"/><line x2="1"/></svg>'
+
+
+class TestRenderSvg:
+    """Test render_svg() for standalone .svg documents."""
+
+    def test_adds_namespace_and_prolog(self):
+        """Test the root gets xmlns and the document the XML prolog."""
+        from rusty_tags import render_svg
+        result = str(render_svg(Svg(Circle(r="4"), viewBox="0 0 10 10")))
+        assert result == (
+            '<?xml version="1.0"?>\n'
+            '<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"><circle r="4"></circle></svg>'
+        )
+
+    def test_not_standalone(self):
+        """Test standalone=False adds the namespace only."""
+        from rusty_tags import render_svg
+        result = str(render_svg(Svg(Circle(r="4")), standalone=False))
+        assert result == '<svg xmlns="http://www.w3.org/2000/svg"><circle r="4"></circle></svg>'
+
+    def test_existing_xmlns_kept(self):
+        """Test an xmlns already on the root is not duplicated or changed."""
+        from rusty_tags import render_svg
+        svg = Svg(Rect(width="1"), xmlns="http://www.w3.org/2000/svg")
+        result = str(render_svg(svg, standalone=False))
+        assert result.count("xmlns=") == 1
+        assert result == str(svg)
+
+    def test_xlink_namespace_added_when_used(self):
+        """Test xmlns:xlink is declared when xlink: attributes appear."""
+        from rusty_tags import render_svg
+        svg = Svg(Use(**{"xlink:href": "#icon"}))
+        result = str(render_svg(svg, standalone=False))
+        assert 'xmlns:xlink="http://www.w3.org/1999/xlink"' in result
+        assert 'xmlns:xlink' not in str(render_svg(Svg(Use(href="#icon")), standalone=False))
+
+    def test_existing_prolog_not_duplicated(self):
+        """Test markup that already starts with a prolog keeps a single one."""
+        from rusty_tags import render_svg, HtmlString
+        markup = HtmlString('<?xml version="1.0" encoding="UTF-8"?>\n<svg><g></g></svg>')
+        result = str(render_svg(markup))
+        assert result.count("<?xml") == 1
+        assert result.startswith('<?xml version="1.0" encoding="UTF-8"?>\n<svg xmlns=')
+
+    def test_non_svg_root_rejected(self):
+        """Test a non-<svg> root raises ValueError."""
+        from rusty_tags import render_svg
+        with pytest.raises(ValueError):
+            render_svg(Div("x"))
@@ -0,0 +1,111 @@
+"""
+Tests for data tables built from records (table_from_records)
+"""
+
+import pytest
+from decimal import Decimal
+from rusty_tags import HtmlString, Strong, table_from_records
+
+
+ROWS = [
+    {"item": "Tea", "amount": 3, "price": 1.5},
+    {"item": "Cake", "amount": 4, "price": None},
+    {"item": "Pie", "price": 2},
+]
+
+
+def footer(result):
+    """The <tfoot> part of a rendered table."""
+    text = str(result)
+    return text[text.index("<tfoot>"):text.index("</tfoot>") + len("</tfoot>")]
+
+
+class TestTableFromRecords:
+    """Test the table structure built from a list of dicts."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_basic_table(self):
+        """Test headers come from the first record and missing values are empty cells."""
+        result = table_from_records(ROWS[:2], cls="report")
+        assert isinstance(result, HtmlString)
+        assert str(result) == (
+            '<table class="report"><thead><tr><th>item</th><th>amount</th><th>price</th></tr></thead>'
+            "<tbody><tr><td>Tea</td><td>3</td><td>1.5</td></tr><tr><td>Cake</td><td>4</td><td></td></tr></tbody>"
+            "</table>"
+        )
+
+    def test_columns_select_and_order(self):
+        """Test columns picks and orders the keys."""
+        result = str(table_from_records(ROWS, columns=["price", "item"]))
+        assert result.startswith("<table><thead><tr><th>price</th><th>item</th></tr></thead>")
+        assert "<tr><td></td><td>Cake</td></tr>" in result
+
+    def test_empty_cells_with_none_children_error(self):
+        """Test None and missing values stay empty cells when None children raise."""
+        from rusty_tags import configure
+        configure(none_children="error")
+        result = str(table_from_records(ROWS, totals={"price": "sum"}))
+        assert "<tr><td>Cake</td><td>4</td><td></td></tr><tr><td>Pie</td><td></td><td>2</td></tr>" in result
+        assert footer(result) == "<tfoot><tr><td></td><td></td><td>3.5</td></tr></tfoot>"
+
+    def test_cells_use_child_conversion(self):
+        """Test cell values can be elements and render like children."""
+        result = str(table_from_records([{"name": Strong("Ada")}]))
+        assert "<td><strong>Ada</strong></td>" in result
+
+    def test_empty_records(self):
+        """Test no records gives an empty table."""
+        assert str(table_from_records([])) == "<table><tbody></tbody></table>"
+
+    def test_non_dict_record(self):
+        """Test records must be dicts."""
+        with pytest.raises(TypeError, match="dict records"):
+            table_from_records([("Tea", 3)])
+
+
+class TestTableTotals:
+    """Test the optional <tfoot> row of column aggregates."""
+
+    def test_sum_avg_count(self):
+        """Test aggregates skip None and missing values."""
+        result = table_from_records(ROWS, totals={"amount": "sum", "price": "avg", "item": "count"})
+        assert footer(result) == "<tfoot><tr><td>3</td><td>7</td><td>1.75</td></tr></tfoot>"
+
+    def test_min_max_keep_cell_formatting(self):
+        """Test min/max render the winning value like its cell."""
+        result = table_from_records(ROWS, totals={"amount": "min", "price": "max"})
+        assert footer(result) == "<tfoot><tr><td></td><td>3</td><td>2</td></tr></tfoot>"
+
+    def test_non_numeric_column_blank(self):
+        """Test numeric aggregates over text leave a blank footer cell."""
+        result = table_from_records(ROWS, totals={"item": "sum", "amount": "sum"})
+        assert footer(result) == "<tfoot><tr><td></td><td>7</td><td></td></tr></tfoot>"
+        result = table_from_records([{"a": None}], totals={"a": "avg"})
+        assert footer(result) == "<tfoot><tr><td></td></tr></tfoot>"
+
+    def test_bool_is_not_numeric(self):
+        """Test booleans do not count as numbers."""
+        result = table_from_records([{"ok": True}, {"ok": False}], totals={"ok": "sum"})
+        assert footer(result) == "<tfoot><tr><td></td></tr></tfoot>"
+
+    def test_number_like_values(self):
+        """Test Decimal values and big ints are summed as floats."""
+        result = table_from_records([{"a": Decimal("1.5")}, {"a": 2}], totals={"a": "sum"})
+        assert footer(result) == "<tfoot><tr><td>3.5</td></tr></tfoot>"
+        result = table_from_records([{"a": 2 ** 63 - 1}, {"a": 1}], totals={"a": "sum"})
+        assert footer(result) == "<tfoot><tr><td>9.223372036854776e18</td></tr></tfoot>"
+
+    def test_totals_label(self):
+        """Test totals_label fills the first footer cell when it has no aggregate."""
+        result = table_from_records(ROWS, columns=["item", "amount"], totals={"amount": "sum"}, totals_label="Total")
+        assert footer(result) == "<tfoot><tr><td>Total</td><td>7</td></tr></tfoot>"
+
+    def test_invalid_totals(self):
+        """Test unknown aggregates and columns raise ValueError."""
+        with pytest.raises(ValueError, match="Unknown aggregate"):
+            table_from_records(ROWS, totals={"amount": "median"})
+        with pytest.raises(ValueError, match="not in the table"):
+            table_from_records(ROWS, totals={"qty": "sum"})
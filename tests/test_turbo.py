@@ -0,0 +1,81 @@
+"""
+Tests for the optional Hotwire/Turbo helpers (rusty_tags.turbo).
+
+Tests cover:
+- turbo_frame() ids, src and extra attributes
+- turbo_stream() actions, targets and <template> wrapping
+- Validation of actions and target arguments
+"""
+
+import pytest
+from rusty_tags import Div, Li
+from rusty_tags.turbo import turbo_frame, turbo_stream, TURBO_STREAM_ACTIONS
+
+
+class TestTurboFrame:
+    """Test turbo_frame() helper."""
+
+    def test_frame_with_content(self):
+        """Test frame wraps content and carries its id."""
+        assert str(turbo_frame(Div("x"), id="messages")) == (
+            '<turbo-frame id="messages"><div>x</div></turbo-frame>'
+        )
+
+    def test_lazy_frame(self):
+        """Test src and extra attributes such as loading."""
+        result = str(turbo_frame(id="cart", src="/cart", loading="lazy"))
+        assert result == '<turbo-frame id="cart" src="/cart" loading="lazy"></turbo-frame>'
+
+    def test_empty_id_rejected(self):
+        """Test frames need an id."""
+        with pytest.raises(ValueError):
+            turbo_frame("x", id="")
+
+
+class TestTurboStream:
+    """Test turbo_stream() helper."""
+
+    def test_append_wraps_template(self):
+        """Test content actions wrap children in <template>."""
+        result = str(turbo_stream("append", Li("new"), target="items"))
+        assert result == (
+            '<turbo-stream action="append" target="items">'
+            "<template><li>new</li></template></turbo-stream>"
+        )
+
+    def test_targets_selector(self):
+        """Test targets= renders a CSS selector target."""
+        result = str(turbo_stream("update", "0", targets=".counter"))
+        assert result == (
+            '<turbo-stream action="update" targets=".counter"><template>0</template></turbo-stream>'
+        )
+
+    def test_remove_and_refresh_have_no_template(self):
+        """Test content-less actions render without a template."""
+        assert str(turbo_stream("remove", target="item_1")) == (
+            '<turbo-stream action="remove" target="item_1"></turbo-stream>'
+        )
+        assert str(turbo_stream("refresh")) == '<turbo-stream action="refresh"></turbo-stream>'
+
+    def test_all_actions_known(self):
+        """Test the documented action set."""
+        assert TURBO_STREAM_ACTIONS == {
+            "append", "prepend", "replace", "update", "remove", "before", "after", "morph", "refresh",
+        }
+
+    def test_invalid_action(self):
+        """Test typos in actions raise."""
+        with pytest.raises(ValueError, match="Unknown turbo-stream action"):
+            turbo_stream("apend", "x", target="items")
+
+    def test_target_validation(self):
+        """Test exactly one of target/targets is required."""
+        with pytest.raises(ValueError):
+            turbo_stream("append", "x")
+        with pytest.raises(ValueError):
+            turbo_stream("append", "x", target="a", targets=".b")
+
+    def test_contentless_action_rejects_children(self):
+        """Test remove does not accept content."""
+        with pytest.raises(ValueError):
+            turbo_stream("remove", "x", target="a")
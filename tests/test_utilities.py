@@ -9,10 +9,13 @@ This module tests:
 - HtmlString methods (render, encode, __html__)
 """
 
+import ctypes
+import pickle
 import pytest
 from rusty_tags import (
     Page, page_template, create_template, AttrDict, when, unless, show,
-    Div, H1, Span, Meta, Link, Script, Fragment, HtmlString, Button, Input
+    Div, H1, P, Pre, Span, Meta, Link, Script, Fragment, HtmlString, Button, Input,
+    Safe, freeze, Li, Ul, render_pretty,
 )
 
 
@@ -326,6 +329,23 @@ class TestHtmlString:
         assert isinstance(encoded, bytes)
         assert b"content" in encoded
 
+    def test_htmlstring_buffer_protocol(self):
+        """Test memoryview() exposes the UTF-8 bytes read-only without copying."""
+        html = Div("héllo")
+        view = memoryview(html)
+        assert view.readonly
+        assert view.format == "B"
+        assert view.tobytes() == "<div>héllo</div>".encode()
+        assert view.obj is html
+        with pytest.raises(TypeError):
+            (ctypes.c_char * 3).from_buffer(html)
+
+    def test_htmlstring_buffer_outlives_name(self):
+        """Test a view keeps the HtmlString alive after other references go away."""
+        view = memoryview(P("x"))
+        assert bytes(view) == b"<p>x</p>"
+        assert bytes(memoryview(HtmlString(""))) == b""
+
     def test_htmlstring_html_protocol(self):
         """Test HtmlString __html__() protocol."""
         html = Div("content")
@@ -360,6 +380,713 @@ class TestHtmlString:
         assert "<span>Nested</span>" in result
         assert "<button>Click</button>" in result
 
+    def test_htmlstring_mimebundle(self):
+        """Test _repr_mimebundle_ exposes the content as text/html."""
+        html = Div("content")
+        assert html._repr_mimebundle_() == {"text/html": "<div>content</div>"}
+        assert html._repr_mimebundle_(include=["text/html"]) == {"text/html": "<div>content</div>"}
+
+    def test_htmlstring_mimebundle_include_exclude(self):
+        """Test include/exclude filters are honored."""
+        html = Div("content")
+        assert html._repr_mimebundle_(include=["text/plain"]) == {}
+        assert html._repr_mimebundle_(exclude={"text/html"}) == {}
+
+    def test_htmlstring_contains(self):
+        """Test `in` searches the markup like `in str(...)`."""
+        page = Div(P("Saved"), Span("error: name required", cls="error"))
+        assert "error" in page
+        assert 'class="error"' in page
+        assert Span("error: name required", cls="error") in page
+        assert "warning" not in page
+        assert "" in HtmlString("")
+
+    def test_htmlstring_contains_untrusted(self):
+        """Test an untrusted HtmlString is searched in its escaped form."""
+        text = HtmlString("a < b", trusted=False)
+        assert "&lt;" in text
+        assert "<" not in text
+
+    def test_htmlstring_contains_requires_string(self):
+        """Test non-string operands raise TypeError, like str."""
+        with pytest.raises(TypeError, match="requires string"):
+            3 in Div("3")
+
+    def test_htmlstring_format(self):
+        """Test f-strings embed the markup and format() matches str()."""
+        item = Li("a", cls="x")
+        assert f"{item}" == '<li class="x">a</li>'
+        assert format(item) == str(item)
+        assert f"{HtmlString('a < b', trusted=False)}" == "a &lt; b"
+
+    def test_htmlstring_format_pretty(self):
+        """Test the pretty spec gives the render_pretty() output."""
+        page = Div(P("Hi"))
+        assert f"{page:pretty}" == "<div>\n  <p>Hi</p>\n</div>"
+        assert f"{page:pretty}" == str(render_pretty(page))
+
+    def test_htmlstring_format_str_specs(self):
+        """Test other specs behave like str formatting."""
+        assert f"{Span('x'):>16}" == "  <span>x</span>"
+        with pytest.raises(ValueError):
+            f"{Span('x'):d}"
+
+    def test_htmlstring_repetition(self):
+        """Test HtmlString * n repeats the content into a new HtmlString."""
+        item = Span("x")
+        assert str(item * 3) == "<span>x</span>" * 3
+        assert str(2 * item) == "<span>x</span>" * 2
+        assert isinstance(item * 3, HtmlString)
+        assert str(item) == "<span>x</span>"
+
+    def test_htmlstring_repetition_non_positive(self):
+        """Test zero and negative counts give an empty HtmlString, like str."""
+        assert str(Span("x") * 0) == ""
+        assert str(Span("x") * -5) == ""
+
+    def test_htmlstring_repetition_as_child(self):
+        """Test repeated content nests like any other HtmlString."""
+        assert str(Div(Span("x") * 2)) == "<div><span>x</span><span>x</span></div>"
+
+    def test_htmlstring_repetition_too_large(self):
+        """Test huge counts raise instead of attempting the allocation."""
+        with pytest.raises((OverflowError, MemoryError)):
+            Span("x") * (2 ** 62)
+
+    def test_htmlstring_repetition_requires_int(self):
+        """Test non-int operands are rejected."""
+        with pytest.raises(TypeError):
+            Span("x") * "3"
+
+
+class TestHtmlStringReplace:
+    """Test HtmlString.replace() substring replacement."""
+
+    def test_replace_returns_html_string(self):
+        """Test replacement keeps the HtmlString type and leaves the original alone."""
+        html = Div(Link(rel="stylesheet", href="/static/app.css"))
+        busted = html.replace("/static/app.css", "/static/app.3f9a.css")
+        assert isinstance(busted, HtmlString)
+        assert str(busted) == '<div><link rel="stylesheet" href="/static/app.3f9a.css"></link></div>'
+        assert "app.css" in str(html)
+
+    def test_count(self):
+        """Test count limits replacements like str.replace."""
+        html = HtmlString("a-a-a")
+        assert str(html.replace("a", "b", 2)) == "b-b-a"
+        assert str(html.replace("a", "b", 0)) == "a-a-a"
+        assert str(html.replace("a", "b")) == "b-b-b"
+
+    def test_markup_replacement(self):
+        """Test new may be rendered markup, e.g. filling a placeholder."""
+        html = Div("{{content}}", cls="shell")
+        assert str(html.replace("{{content}}", P("hi"))) == '<div class="shell"><p>hi</p></div>'
+
+    def test_blind_replace(self):
+        """Test replacement is not structure-aware."""
+        assert str(Div("div").replace("div", "span")) == "<span>span</span>"
+
+
+class TestHtmlStringIndent:
+    """Test HtmlString.indent() line shifting."""
+
+    def test_matches_textwrap_indent(self):
+        """Test indent() behaves like textwrap.indent on the content."""
+        import textwrap
+        for content in ["<p>x</p>", "<ul>\n<li>a</li>\n\n<li>b</li>\n</ul>\n", "a\r\nb", "", "\n  \n"]:
+            html = HtmlString(content)
+            assert str(html.indent("  ")) == textwrap.indent(content, "  ")
+
+    def test_returns_new_html_string(self):
+        """Test the result is an HtmlString and the original is unchanged."""
+        html = Div("a\nb")
+        shifted = html.indent("> ")
+        assert isinstance(shifted, HtmlString)
+        assert str(shifted) == "> <div>a\n> b</div>"
+        assert str(html) == "<div>a\nb</div>"
+
+
+class TestHtmlStringLines:
+    """Test HtmlString.line_count() and splitlines()."""
+
+    def test_single_line(self):
+        """Test rendered markup without newlines is one line."""
+        html = Div("x")
+        assert html.line_count() == 1
+        assert html.splitlines() == ["<div>x</div>"]
+
+    def test_multiple_lines(self):
+        """Test lines split on newlines, with a trailing newline not adding a line."""
+        html = Pre("a\nb\r\nc\n")
+        assert html.splitlines() == ["<pre>a", "b", "c", "</pre>"]
+        assert html.line_count() == 4
+        assert HtmlString("a\nb\n").line_count() == 2
+
+    def test_matches_str_splitlines(self):
+        """Test results agree with str.splitlines for \n and \r\n content."""
+        for text in ["", "x", "x\n", "\n\n", "a\r\nb", "a\n\nb\n"]:
+            html = HtmlString(text)
+            assert html.splitlines() == text.splitlines()
+            assert html.splitlines(keepends=True) == text.splitlines(keepends=True)
+            assert html.line_count() == len(text.splitlines())
+
+
+class TestHtmlStringPickle:
+    """Test HtmlString pickling for multiprocessing pipelines."""
+
+    def test_every_protocol(self):
+        """Test content and type round-trip under every pickle protocol."""
+        page = Div(P("x"), cls="page")
+        for protocol in range(pickle.HIGHEST_PROTOCOL + 1):
+            restored = pickle.loads(pickle.dumps(page, protocol=protocol))
+            assert type(restored) is HtmlString
+            assert restored.content == '<div class="page"><p>x</p></div>'
+
+    def test_payload_is_just_the_content(self):
+        """Test a trusted value pickles as the class plus its content string."""
+        assert HtmlString("<b>x</b>").__reduce__() == (HtmlString, ("<b>x</b>",))
+        assert HtmlString("<b>", trusted=False).__reduce__() == (HtmlString, ("<b>", False))
+
+    def test_copy_and_deepcopy(self):
+        """Test the copy module uses the same reduction."""
+        import copy
+        raw = HtmlString("<i>", trusted=False)
+        assert copy.copy(raw).trusted is False
+        assert copy.deepcopy(Div("y")).content == "<div>y</div>"
+
+
+class TestEtag:
+    """Test HtmlString.etag() and render_cache_key() content hashes."""
+
+    def test_format(self):
+        """Test the etag is 32 lowercase hex digits."""
+        tag = Div("content").etag()
+        assert len(tag) == 32
+        assert all(c in "0123456789abcdef" for c in tag)
+
+    def test_content_based(self):
+        """Test equal content hashes equally and different content differently."""
+        assert Div("a", id="x").etag() == HtmlString('<div id="x">a</div>').etag()
+        assert Div("a").etag() != Div("b").etag()
+        assert HtmlString("").etag() != HtmlString(" ").etag()
+
+    def test_stable_value(self):
+        """Test the value is fixed (SipHash-2-4-128, zero key), not seeded per process."""
+        assert HtmlString("<p>hi</p>").etag() == "a22494f67854692263ea4aa017ed8109"
+        assert HtmlString("").etag() == "f4f2ced447ab02427de0a38047d74950"
+
+    def test_untrusted_hashes_markup(self):
+        """Test the hash covers the markup that would be sent."""
+        assert HtmlString("<b>", trusted=False).etag() == HtmlString("&lt;b&gt;").etag()
+
+    def test_render_cache_key(self):
+        """Test render_cache_key() renders the element and matches etag()."""
+        from rusty_tags import render_cache_key
+        page = Div(P("x"), cls="page")
+        assert render_cache_key(page) == page.etag()
+        assert render_cache_key(Fragment(P("a"), P("b"))) == HtmlString("<p>a</p><p>b</p>").etag()
+
+
+class TestHtmlStringTrusted:
+    """Test the trusted flag: untrusted content is escaped exactly once."""
+
+    def test_default_is_trusted(self):
+        """Test constructed and rendered HtmlStrings are trusted markup."""
+        assert HtmlString("<b>x</b>").trusted is True
+        assert Div("x").trusted is True
+        assert Safe("<b>").trusted is True
+        assert str(Div(HtmlString("<b>x</b>"))) == "<div><b>x</b></div>"
+
+    def test_untrusted_is_escaped_as_child(self):
+        """Test an untrusted HtmlString is escaped when embedded."""
+        raw = HtmlString("<script>alert(1)</script> & co", trusted=False)
+        assert raw.trusted is False
+        assert raw.content == "<script>alert(1)</script> & co"
+        assert str(P(raw)) == "<p>&lt;script&gt;alert(1)&lt;/script&gt; &amp; co</p>"
+
+    def test_output_methods_escape(self):
+        """Test every rendered form of an untrusted HtmlString is escaped markup."""
+        raw = HtmlString("<i>", trusted=False)
+        assert str(raw) == raw.render() == raw.__html__() == raw._repr_html_() == "&lt;i&gt;"
+        assert raw.encode() == bytes(raw) == b"&lt;i&gt;"
+        assert raw._repr_mimebundle_() == {"text/html": "&lt;i&gt;"}
+
+    def test_nesting_escapes_once(self):
+        """Test the escaped result is trusted, so deeper nesting never re-escapes."""
+        raw = HtmlString("a < b", trusted=False)
+        assert str(Div(Div(P(raw)))) == "<div><div><p>a &lt; b</p></div></div>"
+        assert str(Div(Fragment(raw, Safe("<")))) == "<div>a &lt; b&lt;</div>"
+        assert str(Div(freeze(Span(raw)))) == "<div><span>a &lt; b</span></div>"
+
+    def test_html_protocol_result_is_respected(self):
+        """Test an untrusted HtmlString returned from __html__ is escaped too."""
+        class Comment:
+            def __html__(self):
+                return HtmlString("<b>bold?</b>", trusted=False)
+
+        assert str(Div(Comment())) == "<div>&lt;b&gt;bold?&lt;/b&gt;</div>"
+
+    def test_operations_return_trusted_markup(self):
+        """Test replace, indent and repetition work on the escaped markup."""
+        raw = HtmlString("<x>\n<y>", trusted=False)
+        assert raw.indent("  ").trusted is True
+        assert str(raw.indent("  ")) == "  &lt;x&gt;\n  &lt;y&gt;"
+        assert str(raw.replace("&lt;", "[")) == "[x&gt;\n[y&gt;"
+        assert str(HtmlString("<", trusted=False) * 2) == "&lt;&lt;"
+        assert raw.splitlines() == ["&lt;x&gt;", "&lt;y&gt;"]
+
+    def test_pickle_keeps_flag(self):
+        """Test pickling round-trips the content and the flag."""
+        raw = pickle.loads(pickle.dumps(HtmlString("<i>", trusted=False)))
+        assert raw.content == "<i>" and raw.trusted is False
+        assert pickle.loads(pickle.dumps(HtmlString("<i>"))).trusted is True
+
+    def test_untrusted_has_no_buffer(self):
+        """Test the zero-copy buffer is refused, since the stored bytes are not markup."""
+        with pytest.raises(BufferError):
+            memoryview(HtmlString("<i>", trusted=False))
+
+
+class TestProgressMeterHelpers:
+    """Test progress_bar() and meter_bar() value clamping."""
+
+    def teardown_method(self, method):
+        from rusty_tags import reset_config
+        reset_config()
+
+    def test_progress_defaults(self):
+        """Test max defaults to 1 and indeterminate bars omit value."""
+        from rusty_tags import progress_bar
+        assert str(progress_bar(0.25)) == '<progress value="0.25" max="1"></progress>'
+        assert str(progress_bar()) == '<progress max="1"></progress>'
+        assert str(progress_bar(30.0, 120, "25%")) == '<progress value="30" max="120">25%</progress>'
+
+    def test_progress_clamps(self):
+        """Test out-of-range values are clamped to [0, max]."""
+        from rusty_tags import progress_bar
+        assert 'value="1"' in str(progress_bar(1.5))
+        assert 'value="0"' in str(progress_bar(-3, 10))
+
+    def test_meter_clamps_thresholds(self):
+        """Test value, low, high and optimum are kept within range."""
+        from rusty_tags import meter_bar
+        result = str(meter_bar(120, 0, 100, low=-5, high=90, optimum=50))
+        assert result == '<meter value="100" max="100" low="0" high="90" optimum="50"></meter>'
+        assert str(meter_bar(5, 1, 10)) == '<meter value="5" min="1" max="10"></meter>'
+
+    def test_strict_mode_raises(self):
+        """Test strict mode rejects out-of-range values instead of clamping."""
+        from rusty_tags import configure, progress_bar, meter_bar
+        configure(strict=True)
+        with pytest.raises(ValueError, match="strict"):
+            progress_bar(2)
+        with pytest.raises(ValueError):
+            meter_bar(0.5, low=0.8, high=0.2)
+        assert 'value="0.5"' in str(progress_bar(0.5))
+
+    def test_invalid_bounds(self):
+        """Test bad ranges and non-numeric values are always rejected."""
+        from rusty_tags import progress_bar, meter_bar
+        with pytest.raises(ValueError):
+            progress_bar(0, max=0)
+        with pytest.raises(ValueError):
+            meter_bar(1, min=5, max=1)
+        with pytest.raises(TypeError):
+            progress_bar("50%")
+        with pytest.raises(TypeError):
+            meter_bar(True)
+        with pytest.raises(ValueError):
+            progress_bar(float("nan"))
+
+
+class TestRenderEach:
+    """Test render_each() batch rendering."""
+
+    def test_renders_each_item(self):
+        """Test every item becomes its own HtmlString."""
+        from rusty_tags import render_each
+        result = render_each([Div("a"), Span("b"), "text", 3])
+        assert [str(item) for item in result] == ["<div>a</div>", "<span>b</span>", "text", "3"]
+        assert all(isinstance(item, HtmlString) for item in result)
+
+    def test_accepts_generators(self):
+        """Test any iterable works, including generators."""
+        from rusty_tags import render_each
+        result = render_each(Div(str(i)) for i in range(3))
+        assert [str(item) for item in result] == ["<div>0</div>", "<div>1</div>", "<div>2</div>"]
+
+    def test_empty_and_none(self):
+        """Test empty input and None items."""
+        from rusty_tags import render_each
+        assert render_each([]) == []
+        assert render_each([None]) == []
+
+    def test_non_iterable_raises(self):
+        """Test non-iterables are rejected."""
+        from rusty_tags import render_each
+        with pytest.raises(TypeError):
+            render_each(42)
+
+
+class TestConcat:
+    """Test concat() joining children into one HtmlString."""
+
+    def test_joins_parts(self):
+        """Test parts are joined in order, like Fragment."""
+        from rusty_tags import concat
+        result = concat(Div("a"), "text", 3, None, Span("b"))
+        assert isinstance(result, HtmlString)
+        assert str(result) == "<div>a</div>text3<span>b</span>"
+        assert str(result) == str(Fragment(Div("a"), "text", 3, None, Span("b")))
+
+    def test_flattens_iterables(self):
+        """Test lists and generators are flattened into the result."""
+        from rusty_tags import concat
+        result = concat(H1("t"), [P(str(i)) for i in range(2)], (Span(c) for c in "xy"))
+        assert str(result) == "<h1>t</h1><p>0</p><p>1</p><span>x</span><span>y</span>"
+
+    def test_empty(self):
+        """Test no parts give an empty HtmlString."""
+        from rusty_tags import concat
+        assert str(concat()) == ""
+
+    def test_nests_as_markup(self):
+        """Test the result is trusted markup when embedded."""
+        from rusty_tags import concat
+        assert str(Div(concat(P("a"), P("b")))) == "<div><p>a</p><p>b</p></div>"
+
+
+class TestSortedChildren:
+    """Test sorted_children() data-driven ordering."""
+
+    ROWS = [{"name": "b", "score": 2}, {"name": "a", "score": 3}, {"name": "c", "score": 1}]
+
+    def test_key_and_template(self):
+        """Test items are ordered by key before the template renders them."""
+        from rusty_tags import sorted_children
+        result = sorted_children(self.ROWS, key=lambda r: r["name"], template=lambda r: Li(r["name"]))
+        assert isinstance(result, HtmlString)
+        assert str(Ul(result)) == "<ul><li>a</li><li>b</li><li>c</li></ul>"
+
+    def test_reverse(self):
+        """Test reverse=True gives descending order."""
+        from rusty_tags import sorted_children
+        result = sorted_children(self.ROWS, key=lambda r: r["score"], reverse=True, template=lambda r: Li(r["score"]))
+        assert str(result) == "<li>3</li><li>2</li><li>1</li>"
+
+    def test_stable_for_equal_keys(self):
+        """Test equal keys keep their input order, like sorted()."""
+        from rusty_tags import sorted_children
+        items = [("x", 1), ("y", 0), ("z", 1)]
+        result = sorted_children(items, key=lambda i: i[1], template=lambda i: Span(i[0]))
+        assert str(result) == "<span>y</span><span>x</span><span>z</span>"
+
+    def test_plain_items_and_iterators(self):
+        """Test without template the sorted items render as Fragment children."""
+        from rusty_tags import sorted_children
+        assert str(sorted_children(x for x in [3, 1, 2])) == "123"
+        assert str(sorted_children([])) == ""
+
+    def test_mixed_types_need_a_key(self):
+        """Test unorderable items raise TypeError pointing at key=, and a key fixes it."""
+        from rusty_tags import sorted_children
+        mixed = [P("b"), "a"]
+        with pytest.raises(TypeError, match="pass key="):
+            sorted_children(mixed)
+        # Plain text first, then rendered elements
+        assert str(sorted_children(mixed, key=lambda item: isinstance(item, HtmlString))) == "a<p>b</p>"
+
+
+class TestRenderFragmentList:
+    """Test render_fragment_list() per-child rendering."""
+
+    def test_one_str_per_child(self):
+        """Test each child renders to a plain str; None items are left out."""
+        from rusty_tags import render_fragment_list
+        result = render_fragment_list([Div("a"), None, "text", 3, HtmlString("<b>x</b>")])
+        assert result == ["<div>a</div>", "text", "3", "<b>x</b>"]
+        assert all(type(item) is str for item in result)
+
+    def test_matches_fragment(self):
+        """Test joining the results gives the same markup as Fragment."""
+        from rusty_tags import render_fragment_list, Fragment
+        children = [Div("a"), Span("b"), None, 1.5]
+        assert "".join(render_fragment_list(children)) == str(Fragment(*children))
+
+    def test_accepts_generators(self):
+        """Test any iterable works."""
+        from rusty_tags import render_fragment_list
+        assert render_fragment_list(Span(str(i)) for i in range(2)) == ["<span>0</span>", "<span>1</span>"]
+        assert render_fragment_list([]) == []
+
+    def test_nested_lists_and_generators_flatten(self):
+        """Test nested lists, tuples and generators render like Fragment children, one entry each."""
+        from rusty_tags import render_fragment_list, Fragment
+        children = [P(1), [P(2), P(3)], (x for x in [1]), (Span("a"), [Span("b")]), []]
+        result = render_fragment_list(children)
+        assert result == ["<p>1</p>", "<p>2</p><p>3</p>", "1", "<span>a</span><span>b</span>", ""]
+        children = [P(1), [P(2), P(3)], (Span("a"), [Span("b")])]
+        assert "".join(render_fragment_list(children)) == str(Fragment(*children))
+
+
+class TestRenderToFile:
+    """Test render_to_file() and HtmlString.write_gzip()."""
+
+    def test_plain_write(self, tmp_path):
+        """Test uncompressed output matches the rendered HTML."""
+        from rusty_tags import render_to_file
+        path = tmp_path / "index.html"
+        written = render_to_file(Div("hello"), path)
+        assert path.read_text() == "<div>hello</div>"
+        assert written == len("<div>hello</div>")
+
+    def test_gzip_write(self, tmp_path):
+        """Test gzip output decompresses to the rendered HTML."""
+        import gzip
+        from rusty_tags import render_to_file
+        path = tmp_path / "index.html.gz"
+        page = Div(*[P(f"Paragraph {i}") for i in range(100)])
+        written = render_to_file(page, str(path), compress="gzip")
+        data = path.read_bytes()
+        assert written == len(data)
+        assert gzip.decompress(data).decode() == str(page)
+        assert written < len(str(page))
+
+    def test_write_gzip_method(self, tmp_path):
+        """Test HtmlString.write_gzip() writes compressed content."""
+        import gzip
+        path = tmp_path / "page.html.gz"
+        html = Div("café")
+        written = html.write_gzip(path)
+        assert written == path.stat().st_size
+        assert gzip.decompress(path.read_bytes()).decode() == "<div>café</div>"
+
+    def test_gzip_is_reproducible(self, tmp_path):
+        """Test the same content compresses to identical bytes (no header timestamp)."""
+        from rusty_tags import render_to_file
+        page = Div(*[P(f"Paragraph {i}") for i in range(50)])
+        first, second = tmp_path / "a.html.gz", tmp_path / "b.html.gz"
+        render_to_file(page, first, compress="gzip")
+        render_to_file(page, second, compress="gzip")
+        assert first.read_bytes() == second.read_bytes()
+        assert first.read_bytes()[4:8] == b"\x00\x00\x00\x00"
+        assert HtmlString(str(page)).write_gzip(tmp_path / "c.html.gz") == len(first.read_bytes())
+
+    def test_brotli_write(self, tmp_path):
+        """Test brotli output needs no extra package and is reproducible."""
+        from rusty_tags import render_to_file
+        page = Div(*[P(f"Paragraph {i}") for i in range(100)])
+        first, second = tmp_path / "a.html.br", tmp_path / "b.html.br"
+        written = render_to_file(page, first, compress="brotli")
+        render_to_file(page, second, compress="brotli")
+        assert written == first.stat().st_size
+        assert 0 < written < len(str(page))
+        assert first.read_bytes() == second.read_bytes()
+        try:
+            import brotli
+        except ImportError:
+            return
+        assert brotli.decompress(first.read_bytes()).decode() == str(page)
+
+    def test_unknown_compression_raises(self, tmp_path):
+        """Test unsupported compression names are rejected without writing."""
+        from rusty_tags import render_to_file
+        path = tmp_path / "x.html"
+        with pytest.raises(ValueError):
+            render_to_file(Div("x"), path, compress="zip")
+        assert not path.exists()
+
+    def test_missing_directory_raises(self, tmp_path):
+        """Test IO errors surface as OSError."""
+        from rusty_tags import render_to_file
+        with pytest.raises(OSError):
+            render_to_file(Div("x"), tmp_path / "missing" / "x.html")
+
+
+class TestJsonLd:
+    """Test json_ld() structured data scripts."""
+
+    @staticmethod
+    def body(html):
+        text = str(html)
+        return text[text.index(">") + 1:text.rindex("</script>")]
+
+    def test_wraps_json_in_script(self):
+        """Test the data is serialized into an ld+json script."""
+        from rusty_tags import json_ld
+        result = json_ld({"@context": "https://schema.org", "@type": "Person", "name": "Ada"})
+        assert str(result) == (
+            '<script type="application/ld+json">'
+            '{"@context":"https://schema.org","@type":"Person","name":"Ada"}</script>'
+        )
+
+    def test_nested_round_trip(self):
+        """Test nested structures survive a JSON round trip."""
+        import json
+        from rusty_tags import json_ld
+        data = {"@type": "Article", "author": [{"@type": "Person", "name": "Ada"}],
+                "rating": 4.5, "count": 3, "free": True, "image": None, "tags": ["a", "b"]}
+        assert json.loads(self.body(json_ld(data))) == data
+
+    def test_script_breakout_is_neutralized(self):
+        """Test </script>, <!-- and & inside strings are escaped but decode unchanged."""
+        import json
+        from rusty_tags import json_ld
+        data = {"headline": "</script><script>alert(1)</script><!-- & more \u2028"}
+        body = self.body(json_ld(data))
+        assert "<" not in body and ">" not in body and "&" not in body
+        assert json.loads(body) == data
+
+    def test_indent_and_attributes(self):
+        """Test indent pretty-prints and kwargs become script attributes."""
+        from rusty_tags import json_ld
+        result = str(json_ld({"a": [1]}, indent=2, id="ld", nonce="n1"))
+        assert result == (
+            '<script type="application/ld+json" id="ld" nonce="n1">'
+            '{\n  "a": [\n    1\n  ]\n}</script>'
+        )
+
+    def test_unserializable_data(self):
+        """Test non-JSON data raises TypeError."""
+        from rusty_tags import json_ld
+        with pytest.raises(TypeError, match="JSON-serializable"):
+            json_ld({"when": object()})
+
+
+class TestResourceHelpers:
+    """Test script_src/stylesheet SRI helpers."""
+
+    def test_script_src_defaults(self):
+        """Test script_src emits crossorigin and omits a missing integrity."""
+        from rusty_tags import script_src
+        assert str(script_src("https://cdn.example.com/app.js")) == (
+            '<script src="https://cdn.example.com/app.js" crossorigin="anonymous"></script>'
+        )
+
+    def test_script_src_with_integrity(self):
+        """Test integrity is emitted when given."""
+        from rusty_tags import script_src
+        result = str(script_src("/app.js", integrity="sha384-abc"))
+        assert result == '<script src="/app.js" integrity="sha384-abc" crossorigin="anonymous"></script>'
+
+    def test_script_src_omits_crossorigin_none(self):
+        """Test crossorigin=None omits the attribute and extra kwargs pass through."""
+        from rusty_tags import script_src
+        result = str(script_src("/app.js", crossorigin=None, type="module", defer=True))
+        assert result == '<script src="/app.js" type="module" defer></script>'
+
+    def test_stylesheet(self):
+        """Test stylesheet builds a rel=stylesheet link."""
+        from rusty_tags import stylesheet
+        result = str(stylesheet("/app.css", integrity="sha384-xyz", media="print"))
+        assert result.startswith('<link rel="stylesheet" href="/app.css" integrity="sha384-xyz" crossorigin="anonymous" media="print"')
+
+    def test_stylesheet_without_integrity(self):
+        """Test integrity is omitted when None."""
+        from rusty_tags import stylesheet
+        assert "integrity" not in str(stylesheet("/app.css"))
+
+
+class TestResponsiveImg:
+    """Test the responsive_img srcset/sizes helper."""
+
+    def test_srcset_and_sizes(self):
+        """Test candidates are comma-joined with their descriptors."""
+        from rusty_tags import responsive_img
+        result = responsive_img(
+            "a-640.jpg",
+            srcset=[("a-320.jpg", "320w"), ("a-640.jpg", "640w")],
+            sizes="(max-width: 600px) 100vw, 50vw",
+            alt="A photo",
+        )
+        assert str(result) == (
+            '<img src="a-640.jpg" srcset="a-320.jpg 320w, a-640.jpg 640w" '
+            'sizes="(max-width: 600px) 100vw, 50vw" alt="A photo"></img>'
+        )
+
+    def test_density_descriptors_and_bare_urls(self):
+        """Test x descriptors and bare URL candidates."""
+        from rusty_tags import responsive_img
+        result = str(responsive_img("a.jpg", srcset=["a.jpg", ("a@2x.jpg", "2x")]))
+        assert 'srcset="a.jpg, a@2x.jpg 2x"' in result
+        assert result.endswith(' alt=""></img>')
+        assert "sizes" not in result
+
+    def test_url_and_attribute_escaping(self):
+        """Test commas/spaces in URLs are encoded and values HTML-escaped."""
+        from rusty_tags import responsive_img
+        result = str(responsive_img(
+            "img.jpg?w=1&h=2",
+            srcset=[("img 1,2.jpg", "1x")],
+            alt='Say "hi"',
+        ))
+        assert 'src="img.jpg?w=1&amp;h=2"' in result
+        assert 'srcset="img%201%2C2.jpg 1x"' in result
+        assert 'alt="Say &quot;hi&quot;"' in result
+
+    def test_extra_attributes(self):
+        """Test extra keyword attributes are passed through."""
+        from rusty_tags import responsive_img
+        result = str(responsive_img("a.jpg", alt="", loading="lazy", width=320))
+        assert 'loading="lazy"' in result
+        assert 'width="320"' in result
+
+    def test_invalid_descriptor_raises(self):
+        """Test malformed descriptors are rejected."""
+        from rusty_tags import responsive_img
+        with pytest.raises(ValueError):
+            responsive_img("a.jpg", srcset=[("a.jpg", "320px")])
+
+
+class TestPictureHelper:
+    """Test the picture() art-direction helper."""
+
+    def test_sources_then_fallback(self):
+        """Test sources render in order followed by the fallback img."""
+        from rusty_tags import picture
+        result = picture(
+            [("(min-width: 800px)", "wide.jpg"), ("(min-width: 400px)", "medium.jpg")],
+            "small.jpg",
+            alt="Hero",
+        )
+        assert str(result) == (
+            '<picture><source media="(min-width: 800px)" srcset="wide.jpg"></source>'
+            '<source media="(min-width: 400px)" srcset="medium.jpg"></source>'
+            '<img src="small.jpg" alt="Hero"></img></picture>'
+        )
+
+    def test_candidate_lists_and_type(self):
+        """Test srcset candidate lists and the optional type entry."""
+        from rusty_tags import picture
+        result = str(picture(
+            [(None, [("a.avif", "1x"), ("a@2x.avif", "2x")], "image/avif")],
+            "a.jpg",
+        ))
+        assert '<source srcset="a.avif 1x, a@2x.avif 2x" type="image/avif"></source>' in result
+        assert "media" not in result
+
+    def test_element_fallback_and_picture_attrs(self):
+        """Test an Img element is used as-is and kwargs go on <picture>."""
+        from rusty_tags import picture, responsive_img
+        fallback = responsive_img("a.jpg", alt="A", loading="lazy")
+        result = str(picture([("(min-width: 600px)", "b.jpg")], fallback, cls="hero"))
+        assert result.startswith('<picture class="hero"><source')
+        assert result.endswith(str(fallback) + "</picture>")
+
+    def test_escaping(self):
+        """Test media queries and URLs are HTML-escaped."""
+        from rusty_tags import picture
+        result = str(picture([('(min-width: 1px) and "x"', "a.jpg?w=1&h=2")], 'b".jpg'))
+        assert 'media="(min-width: 1px) and &quot;x&quot;"' in result
+        assert 'srcset="a.jpg?w=1&amp;h=2"' in result
+        assert 'src="b&quot;.jpg"' in result
+
+    def test_invalid_source_tuple(self):
+        """Test malformed source entries are rejected."""
+        from rusty_tags import picture
+        with pytest.raises(ValueError):
+            picture([("only-media",)], "a.jpg")
+
 
 class TestShowFunction:
     """Test show() function for IPython integration."""